@@ -0,0 +1,5 @@
+-- USER_TOKEN_REVOCATIONS
+DROP TABLE IF EXISTS user_token_revocations CASCADE;
+
+-- REVOKED_TOKENS
+DROP TABLE IF EXISTS revoked_tokens CASCADE;
@@ -0,0 +1,16 @@
+-- REVOKED_TOKENS
+CREATE TABLE revoked_tokens (
+	jti UUID PRIMARY KEY,
+	user_id UUID NOT NULL,
+	expires_at TIMESTAMP NOT NULL,
+	revoked_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX revoked_tokens_expires_at_idx ON revoked_tokens(expires_at);
+
+-- USER_TOKEN_REVOCATIONS
+CREATE TABLE user_token_revocations (
+	user_id UUID PRIMARY KEY,
+	revoked_before TIMESTAMP NOT NULL,
+	FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
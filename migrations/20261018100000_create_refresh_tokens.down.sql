@@ -0,0 +1,2 @@
+-- REFRESH_TOKENS
+DROP TABLE IF EXISTS refresh_tokens CASCADE;
@@ -0,0 +1,17 @@
+-- REFRESH_TOKENS
+CREATE TABLE refresh_tokens (
+	id UUID PRIMARY KEY,
+	family_id UUID NOT NULL,
+	user_id UUID NOT NULL,
+	token_hash TEXT NOT NULL UNIQUE,
+	nonce TEXT NOT NULL,
+	access_jti UUID NOT NULL,
+	access_expires_at TIMESTAMP NOT NULL,
+	expires_at TIMESTAMP NOT NULL,
+	used_at TIMESTAMP,
+	revoked_at TIMESTAMP,
+	created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX refresh_tokens_family_id_idx ON refresh_tokens(family_id);
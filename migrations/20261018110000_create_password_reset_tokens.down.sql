@@ -0,0 +1,2 @@
+-- PASSWORD_RESET_TOKENS
+DROP TABLE IF EXISTS password_reset_tokens CASCADE;
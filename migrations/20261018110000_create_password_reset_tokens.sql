@@ -0,0 +1,10 @@
+-- PASSWORD_RESET_TOKENS
+CREATE TABLE password_reset_tokens (
+	id UUID PRIMARY KEY,
+	user_id UUID NOT NULL,
+	token_hash TEXT NOT NULL UNIQUE,
+	expires_at TIMESTAMP NOT NULL,
+	used_at TIMESTAMP,
+	created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
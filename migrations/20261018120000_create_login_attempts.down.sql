@@ -0,0 +1,5 @@
+-- LOGIN_LOCKOUTS
+DROP TABLE IF EXISTS login_lockouts CASCADE;
+
+-- LOGIN_ATTEMPTS
+DROP TABLE IF EXISTS login_attempts CASCADE;
@@ -0,0 +1,18 @@
+-- LOGIN_ATTEMPTS
+CREATE TABLE login_attempts (
+	subject TEXT PRIMARY KEY,
+	failures INTEGER NOT NULL,
+	last_failure_at TIMESTAMP NOT NULL,
+	locked_until TIMESTAMP
+);
+
+-- LOGIN_LOCKOUTS
+CREATE TABLE login_lockouts (
+	id UUID PRIMARY KEY,
+	subject TEXT NOT NULL,
+	failures INTEGER NOT NULL,
+	locked_until TIMESTAMP NOT NULL,
+	created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX login_lockouts_subject_idx ON login_lockouts(subject);
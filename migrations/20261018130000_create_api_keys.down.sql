@@ -0,0 +1,2 @@
+-- API_KEYS
+DROP TABLE IF EXISTS api_keys CASCADE;
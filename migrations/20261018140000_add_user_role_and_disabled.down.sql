@@ -0,0 +1,7 @@
+-- USERS
+DROP INDEX IF EXISTS users_name_prefix_idx;
+DROP INDEX IF EXISTS users_key_prefix_idx;
+
+ALTER TABLE users
+	DROP COLUMN IF EXISTS disabled_at,
+	DROP COLUMN IF EXISTS role;
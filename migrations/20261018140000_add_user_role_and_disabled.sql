@@ -0,0 +1,7 @@
+-- USERS
+ALTER TABLE users
+	ADD COLUMN role TEXT NOT NULL DEFAULT 'user',
+	ADD COLUMN disabled_at TIMESTAMP;
+
+CREATE INDEX users_key_prefix_idx ON users (lower(key) text_pattern_ops);
+CREATE INDEX users_name_prefix_idx ON users (lower(name) text_pattern_ops);
@@ -0,0 +1,5 @@
+-- USERS
+DROP INDEX IF EXISTS users_delete_after_idx;
+
+ALTER TABLE users
+	DROP COLUMN IF EXISTS delete_after;
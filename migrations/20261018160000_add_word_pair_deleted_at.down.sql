@@ -0,0 +1,5 @@
+-- WORD_PAIRS
+DROP INDEX IF EXISTS word_pairs_deleted_at_idx;
+
+ALTER TABLE word_pairs
+	DROP COLUMN IF EXISTS deleted_at;
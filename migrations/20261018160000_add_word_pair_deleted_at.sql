@@ -0,0 +1,5 @@
+-- WORD_PAIRS
+ALTER TABLE word_pairs
+	ADD COLUMN deleted_at TIMESTAMP;
+
+CREATE INDEX word_pairs_deleted_at_idx ON word_pairs (deleted_at) WHERE deleted_at IS NOT NULL;
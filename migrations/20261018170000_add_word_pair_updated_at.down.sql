@@ -0,0 +1,3 @@
+-- WORD_PAIRS
+ALTER TABLE word_pairs
+	DROP COLUMN IF EXISTS updated_at;
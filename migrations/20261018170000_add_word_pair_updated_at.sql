@@ -0,0 +1,5 @@
+-- WORD_PAIRS
+ALTER TABLE word_pairs
+	ADD COLUMN updated_at TIMESTAMP NOT NULL DEFAULT NOW();
+
+UPDATE word_pairs SET updated_at = created_at;
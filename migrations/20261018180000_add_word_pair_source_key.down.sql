@@ -0,0 +1,5 @@
+-- WORD_PAIRS
+DROP INDEX IF EXISTS word_pairs_source_key_idx;
+
+ALTER TABLE word_pairs
+	DROP COLUMN IF EXISTS source_key;
@@ -0,0 +1,12 @@
+-- WORD_PAIRS
+ALTER TABLE word_pairs
+	ADD COLUMN source_key TEXT NOT NULL DEFAULT '';
+
+UPDATE word_pairs SET source_key = lower(regexp_replace(btrim(source_text), '\s+', ' ', 'g'));
+
+ALTER TABLE word_pairs
+	ALTER COLUMN source_key DROP DEFAULT;
+
+-- Not unique: pairs stored before the rule may still be duplicates until
+-- they are merged.
+CREATE INDEX word_pairs_source_key_idx ON word_pairs (user_id, source_language, target_language, source_key) WHERE deleted_at IS NULL;
@@ -0,0 +1,4 @@
+-- WORD_PAIRS
+DROP INDEX IF EXISTS word_pairs_user_target_text_idx;
+DROP INDEX IF EXISTS word_pairs_user_source_text_idx;
+DROP INDEX IF EXISTS word_pairs_user_created_idx;
@@ -0,0 +1,5 @@
+-- WORD_PAIRS
+-- Keyset pagination of a user's word pairs in each listing order.
+CREATE INDEX word_pairs_user_created_idx ON word_pairs (user_id, created_at, id) WHERE deleted_at IS NULL;
+CREATE INDEX word_pairs_user_source_text_idx ON word_pairs (user_id, source_text, id) WHERE deleted_at IS NULL;
+CREATE INDEX word_pairs_user_target_text_idx ON word_pairs (user_id, target_text, id) WHERE deleted_at IS NULL;
@@ -0,0 +1,7 @@
+-- WORD_PAIRS
+DROP INDEX IF EXISTS word_pairs_target_text_trgm_idx;
+DROP INDEX IF EXISTS word_pairs_source_text_trgm_idx;
+DROP INDEX IF EXISTS word_pairs_target_text_fts_idx;
+DROP INDEX IF EXISTS word_pairs_source_text_fts_idx;
+
+DROP FUNCTION IF EXISTS word_pair_search_config(TEXT);
@@ -0,0 +1,34 @@
+-- WORD_PAIRS
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Text search configuration for a language code, `simple` (no stemming) for
+-- languages Postgres has no configuration for.
+CREATE OR REPLACE FUNCTION word_pair_search_config(language TEXT) RETURNS regconfig AS $$
+	SELECT CASE language
+		WHEN 'ar' THEN 'arabic'
+		WHEN 'da' THEN 'danish'
+		WHEN 'de' THEN 'german'
+		WHEN 'el' THEN 'greek'
+		WHEN 'en' THEN 'english'
+		WHEN 'es' THEN 'spanish'
+		WHEN 'fi' THEN 'finnish'
+		WHEN 'fr' THEN 'french'
+		WHEN 'hu' THEN 'hungarian'
+		WHEN 'id' THEN 'indonesian'
+		WHEN 'it' THEN 'italian'
+		WHEN 'lt' THEN 'lithuanian'
+		WHEN 'nl' THEN 'dutch'
+		WHEN 'no' THEN 'norwegian'
+		WHEN 'pt' THEN 'portuguese'
+		WHEN 'ro' THEN 'romanian'
+		WHEN 'ru' THEN 'russian'
+		WHEN 'sv' THEN 'swedish'
+		WHEN 'tr' THEN 'turkish'
+		ELSE 'simple'
+	END::regconfig
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+CREATE INDEX word_pairs_source_text_fts_idx ON word_pairs USING GIN (to_tsvector(word_pair_search_config(source_language), source_text)) WHERE deleted_at IS NULL;
+CREATE INDEX word_pairs_target_text_fts_idx ON word_pairs USING GIN (to_tsvector(word_pair_search_config(target_language), target_text)) WHERE deleted_at IS NULL;
+CREATE INDEX word_pairs_source_text_trgm_idx ON word_pairs USING GIN (source_text gin_trgm_ops) WHERE deleted_at IS NULL;
+CREATE INDEX word_pairs_target_text_trgm_idx ON word_pairs USING GIN (target_text gin_trgm_ops) WHERE deleted_at IS NULL;
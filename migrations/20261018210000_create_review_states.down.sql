@@ -0,0 +1,5 @@
+-- REVIEW_LOGS
+DROP TABLE IF EXISTS review_logs CASCADE;
+
+-- REVIEW_STATES
+DROP TABLE IF EXISTS review_states CASCADE;
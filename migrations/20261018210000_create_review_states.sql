@@ -0,0 +1,33 @@
+-- REVIEW_STATES
+CREATE TABLE review_states (
+	word_pair_id UUID PRIMARY KEY,
+	user_id UUID NOT NULL,
+	ease DOUBLE PRECISION NOT NULL,
+	interval_days INTEGER NOT NULL,
+	repetitions INTEGER NOT NULL DEFAULT 0,
+	lapses INTEGER NOT NULL DEFAULT 0,
+	stability DOUBLE PRECISION,
+	difficulty DOUBLE PRECISION,
+	due_at TIMESTAMP NOT NULL,
+	last_reviewed_at TIMESTAMP,
+	created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	FOREIGN KEY (word_pair_id) REFERENCES word_pairs(id) ON DELETE CASCADE,
+	FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX review_states_user_due_idx ON review_states (user_id, due_at);
+
+-- REVIEW_LOGS
+CREATE TABLE review_logs (
+	id UUID PRIMARY KEY,
+	user_id UUID NOT NULL,
+	word_pair_id UUID NOT NULL,
+	grade TEXT NOT NULL,
+	previous_interval_days INTEGER NOT NULL,
+	interval_days INTEGER NOT NULL,
+	reviewed_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	FOREIGN KEY (word_pair_id) REFERENCES word_pairs(id) ON DELETE CASCADE,
+	FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX review_logs_user_reviewed_at_idx ON review_logs (user_id, reviewed_at);
@@ -0,0 +1,5 @@
+-- REVIEW_SESSION_CARDS
+DROP TABLE IF EXISTS review_session_cards CASCADE;
+
+-- REVIEW_SESSIONS
+DROP TABLE IF EXISTS review_sessions CASCADE;
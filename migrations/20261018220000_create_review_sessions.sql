@@ -0,0 +1,31 @@
+-- REVIEW_SESSIONS
+CREATE TABLE review_sessions (
+	id UUID PRIMARY KEY,
+	user_id UUID NOT NULL,
+	direction TEXT NOT NULL,
+	source_language TEXT,
+	target_language TEXT,
+	created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	finished_at TIMESTAMP,
+	FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX review_sessions_user_created_at_idx ON review_sessions (user_id, created_at);
+
+-- REVIEW_SESSION_CARDS
+CREATE TABLE review_session_cards (
+	session_id UUID NOT NULL,
+	position INTEGER NOT NULL,
+	word_pair_id UUID NOT NULL,
+	direction TEXT NOT NULL,
+	is_new BOOLEAN NOT NULL,
+	prompt TEXT NOT NULL,
+	expected TEXT NOT NULL,
+	grade TEXT,
+	answer TEXT,
+	answered_at TIMESTAMP,
+	duration_ms BIGINT,
+	PRIMARY KEY (session_id, position),
+	FOREIGN KEY (session_id) REFERENCES review_sessions(id) ON DELETE CASCADE,
+	FOREIGN KEY (word_pair_id) REFERENCES word_pairs(id) ON DELETE CASCADE
+);
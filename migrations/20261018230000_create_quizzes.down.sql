@@ -0,0 +1,5 @@
+-- QUIZ_QUESTIONS
+DROP TABLE IF EXISTS quiz_questions CASCADE;
+
+-- QUIZZES
+DROP TABLE IF EXISTS quizzes CASCADE;
@@ -0,0 +1,23 @@
+-- QUIZZES
+CREATE TABLE quizzes (
+	id UUID PRIMARY KEY,
+	user_id UUID NOT NULL,
+	seed BIGINT NOT NULL,
+	source_language TEXT,
+	target_language TEXT,
+	created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- QUIZ_QUESTIONS
+CREATE TABLE quiz_questions (
+	quiz_id UUID NOT NULL,
+	position INTEGER NOT NULL,
+	word_pair_id UUID NOT NULL,
+	prompt TEXT NOT NULL,
+	options TEXT[] NOT NULL,
+	answer_index INTEGER NOT NULL,
+	PRIMARY KEY (quiz_id, position),
+	FOREIGN KEY (quiz_id) REFERENCES quizzes(id) ON DELETE CASCADE,
+	FOREIGN KEY (word_pair_id) REFERENCES word_pairs(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- REFRESH_TOKENS
+DROP INDEX IF EXISTS refresh_tokens_expires_at_idx;
+DROP INDEX IF EXISTS refresh_tokens_access_jti_idx;
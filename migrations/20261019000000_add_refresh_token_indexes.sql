@@ -0,0 +1,4 @@
+-- REFRESH_TOKENS
+-- Logout looks up the refresh token of an access token, the purge goes by expiry.
+CREATE INDEX refresh_tokens_access_jti_idx ON refresh_tokens (access_jti);
+CREATE INDEX refresh_tokens_expires_at_idx ON refresh_tokens (expires_at);
@@ -0,0 +1,5 @@
+-- WORD_PAIRS
+-- Merged duplicates stay merged.
+DROP INDEX IF EXISTS word_pairs_source_key_idx;
+
+CREATE INDEX word_pairs_source_key_idx ON word_pairs (user_id, source_language, target_language, source_key) WHERE deleted_at IS NULL;
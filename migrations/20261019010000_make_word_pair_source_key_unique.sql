@@ -0,0 +1,50 @@
+-- WORD_PAIRS
+-- `btrim` only strips spaces, the keys are recomputed so leading and trailing
+-- tabs and line breaks are dropped too, as `normalize_text` does.
+UPDATE word_pairs
+SET source_key = lower(btrim(regexp_replace(source_text, '\s+', ' ', 'g')))
+WHERE source_key <> lower(btrim(regexp_replace(source_text, '\s+', ' ', 'g')));
+
+-- Duplicates stored before the rule are merged into their oldest word pair,
+-- as `WordPair::merge` does, and the others are moved to the trash.
+WITH ranked AS (
+	SELECT id, target_text,
+		first_value(id) OVER duplicates AS kept_id,
+		row_number() OVER duplicates AS rank,
+		count(*) OVER (PARTITION BY user_id, source_language, target_language, source_key) AS size
+	FROM word_pairs
+	WHERE deleted_at IS NULL
+	WINDOW duplicates AS (PARTITION BY user_id, source_language, target_language, source_key ORDER BY created_at, id)
+),
+alternatives AS (
+	SELECT DISTINCT ON (ranked.kept_id, lower(btrim(regexp_replace(alternative.text, '\s+', ' ', 'g'))))
+		ranked.kept_id, regexp_replace(alternative.text, '^\s+|\s+$', '', 'g') AS text, ranked.rank, alternative.position
+	FROM ranked, unnest(string_to_array(ranked.target_text, ';')) WITH ORDINALITY AS alternative(text, position)
+	WHERE ranked.size > 1 AND regexp_replace(alternative.text, '^\s+|\s+$', '', 'g') <> ''
+	ORDER BY ranked.kept_id, lower(btrim(regexp_replace(alternative.text, '\s+', ' ', 'g'))), ranked.rank, alternative.position
+),
+merged AS (
+	SELECT kept_id, string_agg(text, '; ' ORDER BY rank, position) AS target_text
+	FROM alternatives
+	GROUP BY kept_id
+)
+UPDATE word_pairs
+SET target_text = merged.target_text, updated_at = NOW()
+FROM merged
+WHERE word_pairs.id = merged.kept_id AND word_pairs.target_text <> merged.target_text;
+
+UPDATE word_pairs
+SET deleted_at = NOW()
+WHERE id IN (
+	SELECT id
+	FROM (
+		SELECT id, row_number() OVER (PARTITION BY user_id, source_language, target_language, source_key ORDER BY created_at, id) AS rank
+		FROM word_pairs
+		WHERE deleted_at IS NULL
+	) AS ranked
+	WHERE rank > 1
+);
+
+DROP INDEX IF EXISTS word_pairs_source_key_idx;
+
+CREATE UNIQUE INDEX word_pairs_source_key_idx ON word_pairs (user_id, source_language, target_language, source_key) WHERE deleted_at IS NULL;
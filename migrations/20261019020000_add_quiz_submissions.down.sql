@@ -0,0 +1,7 @@
+-- QUIZ_QUESTIONS
+ALTER TABLE quiz_questions
+	DROP COLUMN IF EXISTS selected;
+
+-- QUIZZES
+ALTER TABLE quizzes
+	DROP COLUMN IF EXISTS graded_at;
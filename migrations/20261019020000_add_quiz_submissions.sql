@@ -0,0 +1,8 @@
+-- QUIZZES
+-- A quiz is graded once, the picked options are kept with its questions.
+ALTER TABLE quizzes
+	ADD COLUMN graded_at TIMESTAMP;
+
+-- QUIZ_QUESTIONS
+ALTER TABLE quiz_questions
+	ADD COLUMN selected INTEGER;
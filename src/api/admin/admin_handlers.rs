@@ -0,0 +1,241 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+
+use crate::{
+    AppState,
+    api::{
+        admin::models::{
+            AdminUserDTO, ListUsersQuery, PasswordResetTokenDTO, RevokeUserTokensDTO, SetRoleDTO,
+            UserPageDTO, UserRevocationDTO, WordPairCountDTO,
+        },
+        auth::current_user::CurrentUser,
+        types::HandlerError,
+    },
+    application::services::{
+        api_key_service::ApiKeyServiceError, password_reset_service::PasswordResetServiceError,
+        revocation_service::RevocationServiceError, token_service::TokenServiceError,
+        user_service::UserServiceError, word_pair_service::WordPairServiceError,
+    },
+    domain::types::ID,
+};
+
+pub async fn list_users(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Query(query): Query<ListUsersQuery>,
+) -> Result<Json<UserPageDTO>, HandlerError> {
+    current_user.ensure_admin()?;
+
+    let (limit, offset) = (query.limit(), query.offset());
+
+    let (users, total) = state
+        .user_service
+        .list(query.search.as_deref(), limit, offset)
+        .await
+        .map_err(|error| match error {
+            UserServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(UserPageDTO {
+        users: users.into_iter().map(AdminUserDTO::from).collect(),
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Disables the account and signs it out everywhere. The user can't log in
+/// or use existing tokens and API keys until enabled again.
+pub async fn disable_user(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<AdminUserDTO>, HandlerError> {
+    current_user.ensure_admin()?;
+
+    if id == current_user.id {
+        return Err((StatusCode::BAD_REQUEST, "Can't disable yourself"));
+    }
+
+    let res = state.user_service.disable(&id).await.map_err(user_error)?;
+
+    force_logout(&state, &id).await?;
+
+    Ok(Json(AdminUserDTO::from(res)))
+}
+
+pub async fn enable_user(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<AdminUserDTO>, HandlerError> {
+    current_user.ensure_admin()?;
+
+    let res = state.user_service.enable(&id).await.map_err(user_error)?;
+
+    Ok(Json(AdminUserDTO::from(res)))
+}
+
+/// Revokes every refresh and access token and every API key of the user.
+pub async fn logout_user(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<(), HandlerError> {
+    current_user.ensure_admin()?;
+
+    state
+        .user_service
+        .get_by_id(&id)
+        .await
+        .map_err(user_error)?;
+
+    force_logout(&state, &id).await?;
+
+    state
+        .api_key_service
+        .revoke_all(&id)
+        .await
+        .map_err(|error| match error {
+            ApiKeyServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(())
+}
+
+/// Changes the role. Tokens already issued keep the old role until they are
+/// refreshed.
+pub async fn set_user_role(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+    Json(dto): Json<SetRoleDTO>,
+) -> Result<Json<AdminUserDTO>, HandlerError> {
+    current_user.ensure_admin()?;
+
+    if id == current_user.id {
+        return Err((StatusCode::BAD_REQUEST, "Can't change your own role"));
+    }
+
+    let res = state
+        .user_service
+        .set_role(&id, dto.role)
+        .await
+        .map_err(user_error)?;
+
+    Ok(Json(AdminUserDTO::from(res)))
+}
+
+pub async fn count_user_word_pairs(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<WordPairCountDTO>, HandlerError> {
+    current_user.ensure_admin()?;
+
+    state
+        .user_service
+        .get_by_id(&id)
+        .await
+        .map_err(user_error)?;
+
+    let res = state
+        .word_pair_service
+        .count_by_user_id(&id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(WordPairCountDTO::from(res)))
+}
+
+async fn force_logout(state: &AppState, id: &ID) -> Result<(), HandlerError> {
+    state
+        .token_service
+        .revoke_user(id)
+        .await
+        .map_err(|error| match error {
+            TokenServiceError::Database(_) | TokenServiceError::Revocation(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })
+}
+
+fn user_error(error: UserServiceError) -> HandlerError {
+    match error {
+        UserServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "User not found"),
+        UserServiceError::Database(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+        }
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+    }
+}
+
+pub async fn revoke_user_tokens(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+    Json(dto): Json<RevokeUserTokensDTO>,
+) -> Result<Json<UserRevocationDTO>, HandlerError> {
+    current_user.ensure_admin()?;
+
+    let res = state
+        .revocation_service
+        .revoke_user_tokens(&id, dto.issued_before.unwrap_or_else(Utc::now))
+        .await
+        .map_err(|error| match error {
+            RevocationServiceError::Database(sqlx::Error::Database(error))
+                if error.is_foreign_key_violation() =>
+            {
+                (StatusCode::NOT_FOUND, "User not found")
+            }
+            RevocationServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(UserRevocationDTO::from(res)))
+}
+
+/// Issues a one-time password reset token for the user. The token is only
+/// returned here, it is stored hashed.
+pub async fn issue_password_reset(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<PasswordResetTokenDTO>, HandlerError> {
+    current_user.ensure_admin()?;
+
+    let res = state
+        .password_reset_service
+        .issue(&id)
+        .await
+        .map_err(|error| match error {
+            PasswordResetServiceError::User(UserServiceError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, "User not found")
+            }
+            PasswordResetServiceError::Database(_)
+            | PasswordResetServiceError::User(UserServiceError::Database(_)) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(PasswordResetTokenDTO::from(res)))
+}
@@ -0,0 +1,2 @@
+pub mod admin_handlers;
+pub mod models;
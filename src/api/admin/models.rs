@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    models::{
+        password_reset_token::PasswordResetToken, revocation::UserRevocation, role::Role,
+        user::User, word_pair::LanguagePairCount,
+    },
+    types::ID,
+};
+
+const DEFAULT_USERS_LIMIT: i64 = 50;
+const MAX_USERS_LIMIT: i64 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeUserTokensDTO {
+    /// Defaults to now, revoking every token issued so far.
+    pub issued_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserRevocationDTO {
+    pub user_id: ID,
+    pub revoked_before: DateTime<Utc>,
+}
+
+impl From<UserRevocation> for UserRevocationDTO {
+    fn from(value: UserRevocation) -> Self {
+        Self {
+            user_id: value.user_id,
+            revoked_before: value.revoked_before,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetTokenDTO {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<(String, PasswordResetToken)> for PasswordResetTokenDTO {
+    fn from((token, reset_token): (String, PasswordResetToken)) -> Self {
+        Self {
+            token,
+            expires_at: reset_token.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUsersQuery {
+    /// Prefix of the key or name, case-insensitive.
+    pub search: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl ListUsersQuery {
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_USERS_LIMIT)
+            .clamp(1, MAX_USERS_LIMIT)
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminUserDTO {
+    pub id: ID,
+
+    pub key: String,
+    pub name: String,
+    pub role: Role,
+
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<User> for AdminUserDTO {
+    fn from(value: User) -> Self {
+        Self {
+            id: value.id,
+            key: value.key,
+            name: value.name,
+            role: value.role,
+            disabled_at: value.disabled_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserPageDTO {
+    pub users: Vec<AdminUserDTO>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetRoleDTO {
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguagePairCountDTO {
+    pub source_language: String,
+    pub target_language: String,
+    pub count: i64,
+}
+
+impl From<LanguagePairCount> for LanguagePairCountDTO {
+    fn from(value: LanguagePairCount) -> Self {
+        Self {
+            source_language: value.source_language,
+            target_language: value.target_language,
+            count: value.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordPairCountDTO {
+    pub total: i64,
+    pub language_pairs: Vec<LanguagePairCountDTO>,
+}
+
+impl From<Vec<LanguagePairCount>> for WordPairCountDTO {
+    fn from(value: Vec<LanguagePairCount>) -> Self {
+        Self {
+            total: value.iter().map(|pair| pair.count).sum(),
+            language_pairs: value.into_iter().map(LanguagePairCountDTO::from).collect(),
+        }
+    }
+}
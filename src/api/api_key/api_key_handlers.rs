@@ -0,0 +1,78 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+
+use crate::{
+    AppState,
+    api::{
+        api_key::models::{ApiKeyDTO, CreateApiKeyDTO, CreatedApiKeyDTO},
+        auth::current_user::CurrentUser,
+        types::HandlerError,
+    },
+    application::services::api_key_service::ApiKeyServiceError,
+    domain::types::ID,
+};
+
+/// Creates a personal API key. The plain key is only returned here.
+pub async fn create_my_api_key(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(dto): Json<CreateApiKeyDTO>,
+) -> Result<Json<CreatedApiKeyDTO>, HandlerError> {
+    current_user.ensure_access_token()?;
+
+    let res = state
+        .api_key_service
+        .create(&current_user.id, &dto.name, dto.scope)
+        .await
+        .map_err(|error| match error {
+            ApiKeyServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(CreatedApiKeyDTO::from(res)))
+}
+
+pub async fn get_my_api_keys(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<Vec<ApiKeyDTO>>, HandlerError> {
+    let res = state
+        .api_key_service
+        .list(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            ApiKeyServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(res.into_iter().map(ApiKeyDTO::from).collect()))
+}
+
+pub async fn revoke_my_api_key(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<ApiKeyDTO>, HandlerError> {
+    current_user.ensure_access_token()?;
+
+    let res = state
+        .api_key_service
+        .revoke(&current_user.id, &id)
+        .await
+        .map_err(|error| match error {
+            ApiKeyServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "API key not found"),
+            ApiKeyServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(ApiKeyDTO::from(res)))
+}
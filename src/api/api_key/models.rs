@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::domain::{
+    models::api_key::{ApiKey, ApiKeyScope},
+    types::ID,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate)]
+pub struct CreateApiKeyDTO {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    #[serde(default)]
+    pub scope: ApiKeyScope,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiKeyDTO {
+    pub id: ID,
+
+    pub name: String,
+    pub prefix: String,
+    pub scope: ApiKeyScope,
+
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKey> for ApiKeyDTO {
+    fn from(value: ApiKey) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            prefix: value.prefix,
+            scope: value.scope,
+            created_at: value.created_at,
+            last_used_at: value.last_used_at,
+            revoked_at: value.revoked_at,
+        }
+    }
+}
+
+/// Returned once on creation, the plain key cannot be read back later.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreatedApiKeyDTO {
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKeyDTO,
+}
+
+impl From<(String, ApiKey)> for CreatedApiKeyDTO {
+    fn from((key, api_key): (String, ApiKey)) -> Self {
+        Self {
+            key,
+            api_key: ApiKeyDTO::from(api_key),
+        }
+    }
+}
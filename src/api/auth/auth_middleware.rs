@@ -7,7 +7,13 @@ use axum::{
 use reqwest::StatusCode;
 
 use crate::{
-    AppState, api::types::JsonError, application::services::auth_service::AuthServiceError,
+    AppState,
+    api::{
+        auth::models::{ApiKeyPrincipal, Claims},
+        types::JsonError,
+    },
+    application::services::{api_key_service::ApiKeyServiceError, auth_service::AuthServiceError},
+    domain::models::permission::Permissions,
 };
 
 pub async fn auth_middleware(
@@ -21,14 +27,45 @@ pub async fn auth_middleware(
         .and_then(|h| h.to_str().ok())
         .unwrap_or("");
 
+    if let Some(key) = auth_header.strip_prefix("ApiKey ") {
+        let api_key =
+            state
+                .api_key_service
+                .authenticate(key)
+                .await
+                .map_err(|error| match error {
+                    ApiKeyServiceError::InvalidKey => {
+                        (StatusCode::UNAUTHORIZED, "User unauthorized")
+                    }
+                    _ => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+                })?;
+
+        if state.user_service.is_inactive(&api_key.user_id).await {
+            return Err((StatusCode::FORBIDDEN, "Account disabled"));
+        }
+
+        let principal = ApiKeyPrincipal::from(&api_key);
+        let claims = Claims::for_api_key(&api_key);
+
+        req.extensions_mut()
+            .insert(Permissions::granted(claims.role, Some(principal.scope)));
+        req.extensions_mut().insert(principal);
+        req.extensions_mut().insert(claims);
+
+        return Ok(next.run(req).await);
+    }
+
     if !auth_header.starts_with("Bearer") {
-        return Err((StatusCode::UNAUTHORIZED, "User unauthorized"))
+        return Err((StatusCode::UNAUTHORIZED, "User unauthorized"));
     }
 
-    let token = auth_header.strip_prefix("Bearer ").unwrap_or("").to_string();
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .unwrap_or("")
+        .to_string();
 
     if token.is_empty() {
-        return Err((StatusCode::UNAUTHORIZED, "User unauthorized"))
+        return Err((StatusCode::UNAUTHORIZED, "User unauthorized"));
     }
 
     let claims = state
@@ -36,21 +73,25 @@ pub async fn auth_middleware(
         .validate_token(&token)
         .await
         .map_err(|error| match error {
-            AuthServiceError::AuthCommunicator(_)
-            | AuthServiceError::IO(_)
-            | AuthServiceError::Unknown
-            | AuthServiceError::FromUtf8(_)
-            | AuthServiceError::Validation(_) => {
+            AuthServiceError::KeyCache(_) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "Auth keys unavailable")
+            }
+            AuthServiceError::Validation(_) => (StatusCode::UNAUTHORIZED, "User unauthorized"),
+            AuthServiceError::Unknown => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
             }
         })?;
 
-    let is_expired = state.auth_service.check_exp(&claims);
+    if state.revocation_service.is_revoked(&claims).await {
+        return Err((StatusCode::UNAUTHORIZED, "Token revoked"));
+    }
 
-    if is_expired {
-        return Err((StatusCode::UNAUTHORIZED, "User unauthorized"))
+    if state.user_service.is_inactive(&claims.sub).await {
+        return Err((StatusCode::FORBIDDEN, "Account disabled"));
     }
 
+    req.extensions_mut()
+        .insert(Permissions::granted(claims.role, None));
     req.extensions_mut().insert(claims);
 
     Ok(next.run(req).await)
@@ -0,0 +1,144 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+use reqwest::StatusCode;
+
+use crate::{
+    api::{
+        auth::models::{ApiKeyPrincipal, Claims},
+        types::HandlerError,
+    },
+    domain::{models::role::Role, types::ID},
+};
+
+/// Caller identity taken from the `Claims` that `auth_middleware` puts into
+/// the request extensions.
+#[derive(Debug, Clone)]
+pub struct CurrentUser {
+    pub id: ID,
+    pub claims: Claims,
+    /// Set when the caller used an API key instead of an access token.
+    pub api_key: Option<ApiKeyPrincipal>,
+}
+
+impl CurrentUser {
+    pub fn ensure_owner(&self, user_id: &ID) -> Result<(), HandlerError> {
+        if self.id != *user_id {
+            return Err((StatusCode::FORBIDDEN, "Access denied"));
+        }
+
+        Ok(())
+    }
+
+    pub fn ensure_admin(&self) -> Result<(), HandlerError> {
+        if self.claims.role != Role::Admin {
+            return Err((StatusCode::FORBIDDEN, "Access denied"));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects API key callers, for actions that need a signed-in user.
+    pub fn ensure_access_token(&self) -> Result<(), HandlerError> {
+        if self.api_key.is_some() {
+            return Err((StatusCode::FORBIDDEN, "Not allowed with an API key"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    S: Send + Sync,
+{
+    type Rejection = HandlerError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let claims = parts
+            .extensions
+            .get::<Claims>()
+            .ok_or((StatusCode::UNAUTHORIZED, "User unauthorized"))?;
+
+        Ok(Self {
+            id: claims.sub,
+            claims: claims.clone(),
+            api_key: parts.extensions.get::<ApiKeyPrincipal>().copied(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Request;
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use crate::domain::models::api_key::{ApiKey, ApiKeyScope};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_extract_from_claims() {
+        let user_id = Uuid::new_v4();
+        let claims = Claims::new(
+            user_id,
+            Uuid::new_v4(),
+            Utc::now(),
+            Utc::now() + Duration::minutes(5),
+            Role::User,
+        );
+
+        let (mut parts, _) = Request::new(()).into_parts();
+        parts.extensions.insert(claims);
+
+        let current_user = CurrentUser::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+
+        assert_eq!(current_user.id, user_id);
+        assert!(current_user.ensure_owner(&user_id).is_ok());
+        assert_eq!(
+            current_user.ensure_owner(&Uuid::new_v4()).unwrap_err().0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            current_user.ensure_admin().unwrap_err().0,
+            StatusCode::FORBIDDEN
+        );
+        assert!(current_user.ensure_access_token().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_extract_api_key_principal() {
+        let api_key = ApiKey::new(
+            Uuid::new_v4(),
+            "bot".to_string(),
+            "wordy_abcdef".to_string(),
+            "hash".to_string(),
+            ApiKeyScope::ReadOnly,
+        );
+
+        let (mut parts, _) = Request::new(()).into_parts();
+        parts.extensions.insert(Claims::for_api_key(&api_key));
+        parts.extensions.insert(ApiKeyPrincipal::from(&api_key));
+
+        let current_user = CurrentUser::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+
+        assert_eq!(current_user.id, api_key.user_id);
+        assert_eq!(current_user.api_key.unwrap().id, api_key.id);
+        assert_eq!(
+            current_user.ensure_access_token().unwrap_err().0,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_missing_claims() {
+        let (mut parts, _) = Request::new(()).into_parts();
+
+        let res = CurrentUser::from_request_parts(&mut parts, &()).await;
+
+        assert_eq!(res.unwrap_err().0, StatusCode::UNAUTHORIZED);
+    }
+}
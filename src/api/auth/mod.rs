@@ -1,2 +1,5 @@
 pub mod auth_middleware;
+pub mod client_ip;
+pub mod current_user;
 pub mod models;
+pub mod permission_middleware;
@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::types::ID;
+use crate::domain::{
+    models::{
+        api_key::{ApiKey, ApiKeyScope},
+        role::Role,
+    },
+    types::ID,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
@@ -9,10 +15,46 @@ pub struct Claims {
     pub jti: ID,
     pub iat: DateTime<Utc>,
     pub exp: DateTime<Utc>,
+    pub role: Role,
 }
 
 impl Claims {
-    pub fn new(sub: ID, jti: ID, iat: DateTime<Utc>, exp: DateTime<Utc>) -> Self {
-        Self { sub, jti, iat, exp }
+    pub fn new(sub: ID, jti: ID, iat: DateTime<Utc>, exp: DateTime<Utc>, role: Role) -> Self {
+        Self {
+            sub,
+            jti,
+            iat,
+            exp,
+            role,
+        }
+    }
+
+    /// Claims standing in for an API key for the current request. The key id
+    /// is the `jti`, and since keys do not expire `exp` is the request time.
+    pub fn for_api_key(api_key: &ApiKey) -> Self {
+        Self::new(
+            api_key.user_id,
+            api_key.id,
+            api_key.created_at,
+            Utc::now(),
+            Role::User,
+        )
+    }
+}
+
+/// Put next to the `Claims` when the request was authenticated with an API
+/// key rather than an access token.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiKeyPrincipal {
+    pub id: ID,
+    pub scope: ApiKeyScope,
+}
+
+impl From<&ApiKey> for ApiKeyPrincipal {
+    fn from(value: &ApiKey) -> Self {
+        Self {
+            id: value.id,
+            scope: value.scope,
+        }
     }
 }
@@ -0,0 +1,47 @@
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::{Next, from_fn_with_state},
+    response::{IntoResponse, Response},
+    routing::MethodRouter,
+};
+
+use crate::{
+    AppState,
+    domain::models::permission::{Permission, Permissions},
+};
+
+/// Rejects the request with 403 unless `auth_middleware` granted the
+/// caller `permission`.
+pub async fn require_permission(
+    State(permission): State<Permission>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let granted = req
+        .extensions()
+        .get::<Permissions>()
+        .is_some_and(|permissions| permissions.contains(permission));
+
+    if !granted {
+        return (
+            StatusCode::FORBIDDEN,
+            format!("Missing permission: {}", permission),
+        )
+            .into_response();
+    }
+
+    next.run(req).await
+}
+
+/// Declares the permission a route needs, e.g.
+/// `get(get_me).require(Permission::ProfileRead)`.
+pub trait RequirePermission {
+    fn require(self, permission: Permission) -> Self;
+}
+
+impl RequirePermission for MethodRouter<AppState> {
+    fn require(self, permission: Permission) -> Self {
+        self.route_layer(from_fn_with_state(permission, require_permission))
+    }
+}
@@ -1,43 +1,219 @@
-use axum::{Json, extract::State};
+use axum::{
+    Json,
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
 
 use crate::{
     AppState,
     api::{
-        login::models::{ClaimsDTO, LoginByKey},
-        types::JsonError,
+        auth::{client_ip::ClientIp, current_user::CurrentUser},
+        login::models::{LoginByKey, RefreshTokenDTO, TokenDTO},
+        types::HandlerError,
+        user::models::UserDTO,
+    },
+    application::services::{
+        api_key_service::ApiKeyServiceError, login_throttle_service::LoginThrottleServiceError,
+        revocation_service::RevocationServiceError, token_service::TokenServiceError,
+        user_service::UserServiceError,
     },
-    domain::traits::crypto::crypto::ICrypto,
-    infrastructure::utils::password::Argon2Crypto,
+    infrastructure::auth::token::issuer::TokenIssuerError,
 };
 
 pub async fn login_by_key(
     State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    Json(dto): Json<LoginByKey>,
+) -> Result<Json<TokenDTO>, Response> {
+    state
+        .login_throttle_service
+        .check(&dto.key, ip)
+        .await
+        .map_err(throttle_response)?;
+
+    let user = match state
+        .user_service
+        .authenticate(&dto.key, &dto.plain_password)
+        .await
+    {
+        Ok(user) => user,
+        Err(UserServiceError::NotFound(_) | UserServiceError::InvalidCredentials(_)) => {
+            state
+                .login_throttle_service
+                .record_failure(&dto.key, ip)
+                .await
+                .map_err(throttle_response)?;
+
+            return Err((StatusCode::UNAUTHORIZED, "Invalid key or password").into_response());
+        }
+        Err(UserServiceError::Disabled(_)) => {
+            return Err((StatusCode::FORBIDDEN, "Account disabled").into_response());
+        }
+        Err(UserServiceError::PendingDeletion(_)) => {
+            return Err((StatusCode::FORBIDDEN, "Account scheduled for deletion").into_response());
+        }
+        Err(UserServiceError::Database(_)) => {
+            return Err(
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+            );
+        }
+        Err(_) => {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Unknown error").into_response());
+        }
+    };
+
+    state
+        .login_throttle_service
+        .record_success(&dto.key)
+        .await
+        .map_err(throttle_response)?;
+
+    let token_pair = state
+        .token_service
+        .issue(&user.id)
+        .await
+        .map_err(|error| match error {
+            TokenServiceError::Issuer(TokenIssuerError::AuthCommunicator(_)) => {
+                (StatusCode::BAD_GATEWAY, "Auth service unavailable")
+            }
+            TokenServiceError::User(UserServiceError::Disabled(_)) => {
+                (StatusCode::FORBIDDEN, "Account disabled")
+            }
+            TokenServiceError::User(UserServiceError::PendingDeletion(_)) => {
+                (StatusCode::FORBIDDEN, "Account scheduled for deletion")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })
+        .map_err(IntoResponse::into_response)?;
+
+    Ok(Json(TokenDTO::from(token_pair)))
+}
+
+/// Cancels the scheduled deletion of an account. Takes the same credentials
+/// as login, and is throttled the same way.
+pub async fn restore_account(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
     Json(dto): Json<LoginByKey>,
-) -> Result<(String, (String, String)), JsonError> {
-    let user = state.user_service.get_by_key(&dto.key).await.unwrap();
+) -> Result<Json<UserDTO>, Response> {
+    state
+        .login_throttle_service
+        .check(&dto.key, ip)
+        .await
+        .map_err(throttle_response)?;
 
-    let hasher = Argon2Crypto;
+    let user = match state
+        .user_service
+        .restore(&dto.key, &dto.plain_password)
+        .await
+    {
+        Ok(user) => user,
+        Err(UserServiceError::NotFound(_) | UserServiceError::InvalidCredentials(_)) => {
+            state
+                .login_throttle_service
+                .record_failure(&dto.key, ip)
+                .await
+                .map_err(throttle_response)?;
 
-    hasher
-        .verify(&dto.plain_password, &user.hashed_password)
-        .unwrap();
+            return Err((StatusCode::UNAUTHORIZED, "Invalid key or password").into_response());
+        }
+        Err(UserServiceError::Database(_)) => {
+            return Err(
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+            );
+        }
+        Err(_) => {
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Unknown error").into_response());
+        }
+    };
 
-    let claims = ClaimsDTO { sub: user.id };
+    state
+        .login_throttle_service
+        .record_success(&dto.key)
+        .await
+        .map_err(throttle_response)?;
 
-    let client = reqwest::Client::new();
+    Ok(Json(UserDTO::from(user)))
+}
+
+fn throttle_response(error: LoginThrottleServiceError) -> Response {
+    match error {
+        LoginThrottleServiceError::Throttled(retry_after) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            "Too many login attempts",
+        )
+            .into_response(),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+    }
+}
 
-    let resp = client
-        .post(format!(
-            "{}/generate",
-            std::env::var("AUTH_ADDRESS").unwrap()
-        ))
-        .json(&claims)
-        .send()
+pub async fn refresh_token(
+    State(state): State<AppState>,
+    Json(dto): Json<RefreshTokenDTO>,
+) -> Result<Json<TokenDTO>, HandlerError> {
+    let token_pair = state
+        .token_service
+        .refresh(&dto.encrypted_refresh_token, &dto.nonce)
         .await
-        .unwrap();
+        .map_err(|error| match error {
+            TokenServiceError::InvalidRefreshToken | TokenServiceError::RefreshTokenExpired => {
+                (StatusCode::UNAUTHORIZED, "Invalid refresh token")
+            }
+            TokenServiceError::RefreshTokenReused(_) => {
+                (StatusCode::UNAUTHORIZED, "Refresh token reused")
+            }
+            TokenServiceError::User(UserServiceError::Disabled(_)) => {
+                (StatusCode::FORBIDDEN, "Account disabled")
+            }
+            TokenServiceError::User(UserServiceError::PendingDeletion(_)) => {
+                (StatusCode::FORBIDDEN, "Account scheduled for deletion")
+            }
+            TokenServiceError::Issuer(TokenIssuerError::AuthCommunicator(_)) => {
+                (StatusCode::BAD_GATEWAY, "Auth service unavailable")
+            }
+            TokenServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(TokenDTO::from(token_pair)))
+}
 
-    let (access_token, (encrypted_refresh_token, nonce)) =
-        resp.json::<(String, (String, String))>().await.unwrap();
+/// Revokes the credential the request was made with, the API key or the
+/// access token together with its refresh token.
+pub async fn logout(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<(), HandlerError> {
+    if let Some(api_key) = current_user.api_key {
+        state
+            .api_key_service
+            .revoke(&current_user.id, &api_key.id)
+            .await
+            .map_err(|error| match error {
+                ApiKeyServiceError::Database(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+            })?;
+
+        return Ok(());
+    }
+
+    state
+        .token_service
+        .logout(&current_user.claims)
+        .await
+        .map_err(|error| match error {
+            TokenServiceError::Database(_)
+            | TokenServiceError::Revocation(RevocationServiceError::Database(_)) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
 
-    Ok((access_token, (encrypted_refresh_token, nonce)))
+    Ok(())
 }
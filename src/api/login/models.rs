@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::domain::types::ID;
+use crate::domain::{
+    models::{role::Role, token::TokenPair},
+    types::ID,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginByKey {
@@ -11,4 +14,38 @@ pub struct LoginByKey {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaimsDTO {
     pub sub: ID,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshClaimsDTO {
+    pub sub: ID,
+    pub role: Role,
+    pub encrypted_refresh_token: String,
+    pub nonce: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenDTO {
+    pub encrypted_refresh_token: String,
+    pub nonce: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenDTO {
+    pub access_token: String,
+    pub token_type: String,
+    pub encrypted_refresh_token: String,
+    pub nonce: String,
+}
+
+impl From<TokenPair> for TokenDTO {
+    fn from(value: TokenPair) -> Self {
+        Self {
+            access_token: value.access_token,
+            token_type: "Bearer".to_string(),
+            encrypted_refresh_token: value.encrypted_refresh_token,
+            nonce: value.nonce,
+        }
+    }
 }
@@ -1,5 +1,12 @@
+pub mod admin;
+pub mod api_key;
 pub mod auth;
 pub mod login;
+pub mod quiz;
+pub mod review;
+pub mod router;
+pub mod session;
+pub mod stats;
 pub mod translate;
 pub mod types;
 pub mod user;
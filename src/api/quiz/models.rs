@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::domain::{
+    models::{
+        quiz::{Quiz, QuizAnswer, QuizAnswerResult, QuizQuestion, QuizResult},
+        word_pair::WordPairFilter,
+    },
+    types::ID,
+};
+
+const DEFAULT_QUIZ_QUESTIONS: usize = 10;
+const MAX_QUIZ_QUESTIONS: usize = 50;
+const DEFAULT_QUIZ_OPTIONS: usize = 4;
+const MIN_QUIZ_OPTIONS: usize = 2;
+const MAX_QUIZ_OPTIONS: usize = 6;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateQuizDTO {
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub questions: Option<usize>,
+    /// Choices per question, the correct one included.
+    pub options: Option<usize>,
+    /// Draws the same quiz again from the same word pairs.
+    pub seed: Option<u64>,
+}
+
+impl CreateQuizDTO {
+    pub fn questions(&self) -> usize {
+        self.questions
+            .unwrap_or(DEFAULT_QUIZ_QUESTIONS)
+            .clamp(1, MAX_QUIZ_QUESTIONS)
+    }
+
+    pub fn options(&self) -> usize {
+        self.options
+            .unwrap_or(DEFAULT_QUIZ_OPTIONS)
+            .clamp(MIN_QUIZ_OPTIONS, MAX_QUIZ_OPTIONS)
+    }
+
+    pub fn filter(&self) -> WordPairFilter {
+        WordPairFilter {
+            source_language: self.source_language.as_deref().map(str::to_lowercase),
+            target_language: self.target_language.as_deref().map(str::to_lowercase),
+            ..WordPairFilter::default()
+        }
+    }
+}
+
+/// A question without its answer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizQuestionDTO {
+    pub position: i32,
+    pub word_pair_id: ID,
+    pub prompt: String,
+    pub options: Vec<String>,
+}
+
+impl From<QuizQuestion> for QuizQuestionDTO {
+    fn from(value: QuizQuestion) -> Self {
+        Self {
+            position: value.position,
+            word_pair_id: value.word_pair_id,
+            prompt: value.prompt,
+            options: value.options,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizDTO {
+    pub id: ID,
+    pub seed: u64,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub questions: Vec<QuizQuestionDTO>,
+    pub created_at: DateTime<Utc>,
+    pub graded_at: Option<DateTime<Utc>>,
+    /// The first submission, the correct options are only revealed once
+    /// the quiz is graded.
+    pub result: Option<QuizResultDTO>,
+}
+
+impl From<Quiz> for QuizDTO {
+    fn from(value: Quiz) -> Self {
+        let result = value.result().map(QuizResultDTO::from);
+
+        Self {
+            id: value.id,
+            seed: value.seed,
+            source_language: value.source_language,
+            target_language: value.target_language,
+            questions: value
+                .questions
+                .into_iter()
+                .map(QuizQuestionDTO::from)
+                .collect(),
+            created_at: value.created_at,
+            graded_at: value.graded_at,
+            result,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizAnswerDTO {
+    pub position: i32,
+    /// Index of the picked option.
+    pub option: i32,
+}
+
+impl From<&QuizAnswerDTO> for QuizAnswer {
+    fn from(value: &QuizAnswerDTO) -> Self {
+        Self {
+            position: value.position,
+            option: value.option,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct GradeQuizDTO {
+    #[validate(length(max = 50))]
+    pub answers: Vec<QuizAnswerDTO>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizAnswerResultDTO {
+    pub position: i32,
+    pub selected: Option<i32>,
+    pub answer_index: i32,
+    pub is_correct: bool,
+}
+
+impl From<QuizAnswerResult> for QuizAnswerResultDTO {
+    fn from(value: QuizAnswerResult) -> Self {
+        Self {
+            position: value.position,
+            selected: value.selected,
+            answer_index: value.answer_index,
+            is_correct: value.is_correct,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizResultDTO {
+    pub quiz_id: ID,
+    pub questions: i64,
+    pub answered: i64,
+    pub correct: i64,
+    pub score: f64,
+    pub answers: Vec<QuizAnswerResultDTO>,
+}
+
+impl From<QuizResult> for QuizResultDTO {
+    fn from(value: QuizResult) -> Self {
+        Self {
+            quiz_id: value.quiz_id,
+            questions: value.questions,
+            answered: value.answered,
+            correct: value.correct,
+            score: value.score,
+            answers: value
+                .answers
+                .into_iter()
+                .map(QuizAnswerResultDTO::from)
+                .collect(),
+        }
+    }
+}
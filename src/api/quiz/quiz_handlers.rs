@@ -0,0 +1,112 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use validator::Validate;
+
+use crate::{
+    AppState,
+    api::{
+        auth::current_user::CurrentUser,
+        quiz::models::{CreateQuizDTO, GradeQuizDTO, QuizDTO, QuizResultDTO},
+        types::HandlerError,
+    },
+    application::services::quiz_service::QuizServiceError,
+    domain::{
+        models::quiz::{Quiz, QuizAnswer},
+        types::ID,
+    },
+};
+
+async fn get_own_quiz(
+    state: &AppState,
+    current_user: &CurrentUser,
+    id: &ID,
+) -> Result<Quiz, HandlerError> {
+    let quiz = state
+        .quiz_service
+        .get_by_id(id)
+        .await
+        .map_err(|error| match error {
+            QuizServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            QuizServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "Quiz not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    current_user.ensure_owner(&quiz.user_id)?;
+
+    Ok(quiz)
+}
+
+/// Builds a multiple-choice quiz from the caller's word pairs.
+pub async fn create_my_quiz(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(dto): Json<CreateQuizDTO>,
+) -> Result<(StatusCode, Json<QuizDTO>), HandlerError> {
+    let res = state
+        .quiz_service
+        .create(
+            &current_user.id,
+            &dto.filter(),
+            dto.questions(),
+            dto.options(),
+            dto.seed,
+        )
+        .await
+        .map_err(|error| match error {
+            QuizServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            QuizServiceError::NotEnoughWordPairs => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Not enough word pairs for a quiz",
+            ),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok((StatusCode::CREATED, Json(QuizDTO::from(res))))
+}
+
+pub async fn get_my_quiz(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<QuizDTO>, HandlerError> {
+    let quiz = get_own_quiz(&state, &current_user, &id).await?;
+
+    Ok(Json(QuizDTO::from(quiz)))
+}
+
+/// Grades the picked options and reveals the correct ones. Only the first
+/// submission counts.
+pub async fn grade_my_quiz(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+    Json(dto): Json<GradeQuizDTO>,
+) -> Result<Json<QuizResultDTO>, HandlerError> {
+    dto.validate()
+        .map_err(|_| (StatusCode::UNPROCESSABLE_ENTITY, "Invalid answers"))?;
+
+    let quiz = get_own_quiz(&state, &current_user, &id).await?;
+
+    let answers: Vec<QuizAnswer> = dto.answers.iter().map(QuizAnswer::from).collect();
+
+    let res = state
+        .quiz_service
+        .grade(&quiz, &answers)
+        .await
+        .map_err(|error| match error {
+            QuizServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            QuizServiceError::AlreadyGraded => (StatusCode::CONFLICT, "Quiz already graded"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(QuizResultDTO::from(res)))
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::word_pair::models::WordPairDTO,
+    domain::{
+        models::{
+            review::{DueCard, Grade, ReviewLog, ReviewState},
+            word_pair::WordPairFilter,
+        },
+        types::ID,
+    },
+};
+
+const DEFAULT_DUE_LIMIT: i64 = 50;
+const MAX_DUE_LIMIT: i64 = 100;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DueReviewsQuery {
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl DueReviewsQuery {
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_DUE_LIMIT)
+            .clamp(1, MAX_DUE_LIMIT)
+    }
+
+    pub fn filter(&self) -> WordPairFilter {
+        WordPairFilter {
+            source_language: self.source_language.as_deref().map(str::to_lowercase),
+            target_language: self.target_language.as_deref().map(str::to_lowercase),
+            ..WordPairFilter::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewDTO {
+    /// `again`, `hard`, `good` or `easy`.
+    pub grade: Grade,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewStateDTO {
+    pub word_pair_id: ID,
+
+    pub ease: f64,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub lapses: i32,
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
+
+    pub due_at: DateTime<Utc>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl From<ReviewState> for ReviewStateDTO {
+    fn from(value: ReviewState) -> Self {
+        Self {
+            word_pair_id: value.word_pair_id,
+            ease: value.ease,
+            interval_days: value.interval_days,
+            repetitions: value.repetitions,
+            lapses: value.lapses,
+            stability: value.stability,
+            difficulty: value.difficulty,
+            due_at: value.due_at,
+            last_reviewed_at: value.last_reviewed_at,
+        }
+    }
+}
+
+/// One answer to a card.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewLogDTO {
+    pub id: ID,
+    pub word_pair_id: ID,
+
+    pub grade: Grade,
+    pub previous_interval_days: i32,
+    pub interval_days: i32,
+
+    pub reviewed_at: DateTime<Utc>,
+}
+
+impl From<ReviewLog> for ReviewLogDTO {
+    fn from(value: ReviewLog) -> Self {
+        Self {
+            id: value.id,
+            word_pair_id: value.word_pair_id,
+            grade: value.grade,
+            previous_interval_days: value.previous_interval_days,
+            interval_days: value.interval_days,
+            reviewed_at: value.reviewed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DueCardDTO {
+    pub word_pair: WordPairDTO,
+    pub review: ReviewStateDTO,
+}
+
+impl From<DueCard> for DueCardDTO {
+    fn from(value: DueCard) -> Self {
+        Self {
+            word_pair: WordPairDTO::from(value.word_pair),
+            review: ReviewStateDTO::from(value.review),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+
+use crate::{
+    AppState,
+    api::{
+        auth::current_user::CurrentUser,
+        review::models::{DueCardDTO, DueReviewsQuery, ReviewDTO, ReviewStateDTO},
+        types::HandlerError,
+    },
+    application::services::{
+        review_service::ReviewServiceError, word_pair_service::WordPairServiceError,
+    },
+    domain::types::ID,
+};
+
+/// The caller's cards that are due for review, most overdue first.
+pub async fn get_my_due_reviews(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Query(query): Query<DueReviewsQuery>,
+) -> Result<Json<Vec<DueCardDTO>>, HandlerError> {
+    let res = state
+        .review_service
+        .get_due(&current_user.id, &query.filter(), query.limit())
+        .await
+        .map_err(|error| match error {
+            ReviewServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(res.into_iter().map(DueCardDTO::from).collect()))
+}
+
+/// Grades the caller's answer to a word pair and schedules its next review.
+pub async fn review_my_word_pair(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(word_pair_id): Path<ID>,
+    Json(dto): Json<ReviewDTO>,
+) -> Result<Json<ReviewStateDTO>, HandlerError> {
+    let word_pair = state
+        .word_pair_service
+        .get_by_id(&word_pair_id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            WordPairServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "Word pair not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    current_user.ensure_owner(&word_pair.user_id)?;
+
+    let res = state
+        .review_service
+        .grade(&word_pair.id, &word_pair.user_id, dto.grade)
+        .await
+        .map_err(|error| match error {
+            ReviewServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(ReviewStateDTO::from(res)))
+}
@@ -0,0 +1,181 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::domain::{
+    models::{
+        review::Grade,
+        session::{Direction, ReviewSession, SessionAnswer, SessionCard, SessionSummary},
+        word_pair::WordPairFilter,
+    },
+    types::ID,
+};
+
+const DEFAULT_SESSION_SIZE: i64 = 20;
+const MAX_SESSION_SIZE: i64 = 100;
+const DEFAULT_NEW_CARDS: i64 = 10;
+const MAX_NEW_CARDS: i64 = 50;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateSessionDTO {
+    /// `source_to_target` (default), `target_to_source` or `mixed`.
+    #[serde(default)]
+    pub direction: Direction,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    /// Cards in the session, due and new ones together.
+    pub size: Option<i64>,
+    /// Word pairs never reviewed that may fill the session.
+    pub new_cards: Option<i64>,
+}
+
+impl CreateSessionDTO {
+    pub fn size(&self) -> i64 {
+        self.size
+            .unwrap_or(DEFAULT_SESSION_SIZE)
+            .clamp(1, MAX_SESSION_SIZE)
+    }
+
+    pub fn new_cards(&self) -> i64 {
+        self.new_cards
+            .unwrap_or(DEFAULT_NEW_CARDS)
+            .clamp(0, MAX_NEW_CARDS)
+    }
+
+    pub fn filter(&self) -> WordPairFilter {
+        WordPairFilter {
+            source_language: self.source_language.as_deref().map(str::to_lowercase),
+            target_language: self.target_language.as_deref().map(str::to_lowercase),
+            ..WordPairFilter::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct AnswerDTO {
+    pub position: i32,
+    /// Without it the typed `answer` is checked and graded.
+    pub grade: Option<Grade>,
+    #[validate(length(max = 100))]
+    pub answer: Option<String>,
+    /// Time taken to answer, measured by the client.
+    #[validate(range(min = 0))]
+    pub duration_ms: Option<i64>,
+    /// Accepts `cafe` for `café` when checking the answer.
+    #[serde(default)]
+    pub ignore_diacritics: bool,
+}
+
+impl From<AnswerDTO> for SessionAnswer {
+    fn from(value: AnswerDTO) -> Self {
+        Self {
+            position: value.position,
+            grade: value.grade,
+            answer: value.answer,
+            duration_ms: value.duration_ms,
+            ignore_diacritics: value.ignore_diacritics,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionCardDTO {
+    pub position: i32,
+    pub word_pair_id: ID,
+
+    pub direction: Direction,
+    pub is_new: bool,
+    pub prompt: String,
+    pub expected: String,
+
+    pub grade: Option<Grade>,
+    pub answer: Option<String>,
+    pub answered_at: Option<DateTime<Utc>>,
+    pub duration_ms: Option<i64>,
+}
+
+impl From<SessionCard> for SessionCardDTO {
+    fn from(value: SessionCard) -> Self {
+        Self {
+            position: value.position,
+            word_pair_id: value.word_pair_id,
+            direction: value.direction,
+            is_new: value.is_new,
+            prompt: value.prompt,
+            expected: value.expected,
+            grade: value.grade,
+            answer: value.answer,
+            answered_at: value.answered_at,
+            duration_ms: value.duration_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDTO {
+    pub id: ID,
+
+    pub direction: Direction,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub cards: Vec<SessionCardDTO>,
+
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<ReviewSession> for SessionDTO {
+    fn from(value: ReviewSession) -> Self {
+        Self {
+            id: value.id,
+            direction: value.direction,
+            source_language: value.source_language,
+            target_language: value.target_language,
+            cards: value.cards.into_iter().map(SessionCardDTO::from).collect(),
+            created_at: value.created_at,
+            finished_at: value.finished_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummaryDTO {
+    pub session_id: ID,
+
+    pub cards: i64,
+    pub new_cards: i64,
+    pub answered: i64,
+    pub correct: i64,
+    pub accuracy: Option<f64>,
+    pub again: i64,
+    pub hard: i64,
+    pub good: i64,
+    pub easy: i64,
+
+    pub time_spent_ms: i64,
+    pub average_ms: Option<i64>,
+
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<SessionSummary> for SessionSummaryDTO {
+    fn from(value: SessionSummary) -> Self {
+        Self {
+            session_id: value.session_id,
+            cards: value.cards,
+            new_cards: value.new_cards,
+            answered: value.answered,
+            correct: value.correct,
+            accuracy: value.accuracy,
+            again: value.again,
+            hard: value.hard,
+            good: value.good,
+            easy: value.easy,
+            time_spent_ms: value.time_spent_ms,
+            average_ms: value.average_ms,
+            started_at: value.started_at,
+            finished_at: value.finished_at,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use validator::Validate;
+
+use crate::{
+    AppState,
+    api::{
+        auth::current_user::CurrentUser,
+        session::models::{AnswerDTO, CreateSessionDTO, SessionDTO, SessionSummaryDTO},
+        types::HandlerError,
+    },
+    application::services::session_service::SessionServiceError,
+    domain::{
+        models::session::{ReviewSession, SessionAnswer},
+        types::ID,
+    },
+};
+
+async fn get_own_session(
+    state: &AppState,
+    current_user: &CurrentUser,
+    id: &ID,
+) -> Result<ReviewSession, HandlerError> {
+    let session = state
+        .session_service
+        .get_by_id(id)
+        .await
+        .map_err(|error| match error {
+            SessionServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            SessionServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "Session not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    current_user.ensure_owner(&session.user_id)?;
+
+    Ok(session)
+}
+
+/// Starts a review session with the caller's due cards and new word pairs.
+pub async fn create_my_session(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(dto): Json<CreateSessionDTO>,
+) -> Result<(StatusCode, Json<SessionDTO>), HandlerError> {
+    let res = state
+        .session_service
+        .create(
+            &current_user.id,
+            dto.direction,
+            &dto.filter(),
+            dto.size(),
+            dto.new_cards(),
+        )
+        .await
+        .map_err(|error| match error {
+            SessionServiceError::Database(_) | SessionServiceError::Review(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            SessionServiceError::NoCards => {
+                (StatusCode::UNPROCESSABLE_ENTITY, "No cards to review")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok((StatusCode::CREATED, Json(SessionDTO::from(res))))
+}
+
+pub async fn get_my_session(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<SessionDTO>, HandlerError> {
+    let session = get_own_session(&state, &current_user, &id).await?;
+
+    Ok(Json(SessionDTO::from(session)))
+}
+
+/// Answers one card of the session and schedules its next review.
+pub async fn answer_my_session_card(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+    Json(dto): Json<AnswerDTO>,
+) -> Result<Json<SessionDTO>, HandlerError> {
+    dto.validate()
+        .map_err(|_| (StatusCode::UNPROCESSABLE_ENTITY, "Invalid answer"))?;
+
+    let session = get_own_session(&state, &current_user, &id).await?;
+
+    let res = state
+        .session_service
+        .answer(&session, SessionAnswer::from(dto))
+        .await
+        .map_err(|error| match error {
+            SessionServiceError::Database(_) | SessionServiceError::Review(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            SessionServiceError::InvalidCard(_) => (StatusCode::NOT_FOUND, "Card not found"),
+            SessionServiceError::AlreadyAnswered(_) => {
+                (StatusCode::CONFLICT, "Card was already answered")
+            }
+            SessionServiceError::MissingGrade => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Grade or answer is required",
+            ),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(SessionDTO::from(res)))
+}
+
+/// Accuracy and time spent so far, final once the session is finished.
+pub async fn get_my_session_summary(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<SessionSummaryDTO>, HandlerError> {
+    let session = get_own_session(&state, &current_user, &id).await?;
+
+    Ok(Json(SessionSummaryDTO::from(session.summary())))
+}
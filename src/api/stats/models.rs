@@ -0,0 +1,174 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::stats::{DailyCount, DailyReviews, LanguagePairStats, Stats};
+
+const DEFAULT_TIME_ZONE: &str = "UTC";
+const DEFAULT_STATS_DAYS: i64 = 30;
+const MAX_STATS_DAYS: i64 = 365;
+const DEFAULT_STATS_WEEKS: i64 = 12;
+const MAX_STATS_WEEKS: i64 = 104;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsQuery {
+    /// IANA time zone like `Europe/Berlin` the days are counted in.
+    pub tz: Option<String>,
+    pub days: Option<i64>,
+    pub weeks: Option<i64>,
+}
+
+impl StatsQuery {
+    pub fn time_zone(&self) -> &str {
+        self.tz.as_deref().unwrap_or(DEFAULT_TIME_ZONE)
+    }
+
+    pub fn days(&self) -> i64 {
+        self.days
+            .unwrap_or(DEFAULT_STATS_DAYS)
+            .clamp(1, MAX_STATS_DAYS)
+    }
+
+    pub fn weeks(&self) -> i64 {
+        self.weeks
+            .unwrap_or(DEFAULT_STATS_WEEKS)
+            .clamp(1, MAX_STATS_WEEKS)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CardCountsDTO {
+    pub word_pairs: i64,
+    pub new_cards: i64,
+    pub young_cards: i64,
+    pub mature_cards: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguagePairStatsDTO {
+    pub source_language: String,
+    pub target_language: String,
+    #[serde(flatten)]
+    pub cards: CardCountsDTO,
+}
+
+impl From<LanguagePairStats> for LanguagePairStatsDTO {
+    fn from(value: LanguagePairStats) -> Self {
+        Self {
+            source_language: value.source_language,
+            target_language: value.target_language,
+            cards: CardCountsDTO {
+                word_pairs: value.word_pairs,
+                new_cards: value.new_cards,
+                young_cards: value.young_cards,
+                mature_cards: value.mature_cards,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyCountDTO {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+impl From<DailyCount> for DailyCountDTO {
+    fn from(value: DailyCount) -> Self {
+        Self {
+            day: value.day,
+            count: value.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyAccuracyDTO {
+    pub day: NaiveDate,
+    pub reviews: i64,
+    pub correct: i64,
+    pub accuracy: Option<f64>,
+}
+
+impl From<DailyReviews> for DailyAccuracyDTO {
+    fn from(value: DailyReviews) -> Self {
+        Self {
+            day: value.day,
+            reviews: value.reviews,
+            correct: value.correct,
+            accuracy: value.accuracy(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsDTO {
+    pub time_zone: String,
+    pub today: NaiveDate,
+
+    /// All language pairs together.
+    pub totals: CardCountsDTO,
+    pub language_pairs: Vec<LanguagePairStatsDTO>,
+
+    pub added_per_day: Vec<DailyCountDTO>,
+    /// Keyed by the Monday the week starts on.
+    pub added_per_week: Vec<DailyCountDTO>,
+
+    pub accuracy_per_day: Vec<DailyAccuracyDTO>,
+    pub reviews: i64,
+    pub accuracy: Option<f64>,
+    /// Share of learned cards remembered when reviewed.
+    pub retention: Option<f64>,
+
+    pub streak_days: i64,
+}
+
+impl From<Stats> for StatsDTO {
+    fn from(value: Stats) -> Self {
+        let language_pairs: Vec<LanguagePairStatsDTO> = value
+            .language_pairs
+            .into_iter()
+            .map(LanguagePairStatsDTO::from)
+            .collect();
+
+        let totals = language_pairs
+            .iter()
+            .fold(CardCountsDTO::default(), |totals, pair| CardCountsDTO {
+                word_pairs: totals.word_pairs + pair.cards.word_pairs,
+                new_cards: totals.new_cards + pair.cards.new_cards,
+                young_cards: totals.young_cards + pair.cards.young_cards,
+                mature_cards: totals.mature_cards + pair.cards.mature_cards,
+            });
+
+        let reviews: i64 = value.reviews_per_day.iter().map(|day| day.reviews).sum();
+        let correct: i64 = value.reviews_per_day.iter().map(|day| day.correct).sum();
+
+        Self {
+            time_zone: value.time_zone,
+            today: value.today,
+            totals,
+            language_pairs,
+            added_per_day: value
+                .added_per_day
+                .into_iter()
+                .map(DailyCountDTO::from)
+                .collect(),
+            added_per_week: value
+                .added_per_week
+                .into_iter()
+                .map(DailyCountDTO::from)
+                .collect(),
+            accuracy_per_day: value
+                .reviews_per_day
+                .into_iter()
+                .map(DailyAccuracyDTO::from)
+                .collect(),
+            reviews,
+            accuracy: match reviews {
+                0 => None,
+                reviews => Some(correct as f64 / reviews as f64),
+            },
+            retention: value.retention.retention(),
+            streak_days: value.streak_days,
+        }
+    }
+}
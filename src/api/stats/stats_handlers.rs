@@ -0,0 +1,43 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+
+use crate::{
+    AppState,
+    api::{
+        auth::current_user::CurrentUser,
+        stats::models::{StatsDTO, StatsQuery},
+        types::HandlerError,
+    },
+    application::services::stats_service::StatsServiceError,
+};
+
+/// The caller's learning progress, with days counted in the `tz` time zone.
+pub async fn get_my_stats(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<StatsDTO>, HandlerError> {
+    let res = state
+        .stats_service
+        .get(
+            &current_user.id,
+            query.time_zone(),
+            query.days(),
+            query.weeks(),
+        )
+        .await
+        .map_err(|error| match error {
+            StatsServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            StatsServiceError::InvalidTimeZone(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, "Unknown time zone")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(StatsDTO::from(res)))
+}
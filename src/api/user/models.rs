@@ -1,7 +1,20 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::domain::{models::user::User, types::ID};
+use crate::{
+    api::{
+        api_key::models::ApiKeyDTO,
+        quiz::models::QuizDTO,
+        review::models::{ReviewLogDTO, ReviewStateDTO},
+        session::models::SessionDTO,
+        word_pair::models::{TrashedWordPairDTO, WordPairDTO},
+    },
+    domain::{
+        models::{role::Role, user::User},
+        types::ID,
+    },
+};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserDTO {
@@ -38,3 +51,62 @@ pub struct UpdateUserDTO {
     #[validate(length(min = 2, max = 20))]
     pub name: Option<String>,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChangePasswordDTO {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResetPasswordDTO {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeletionScheduledDTO {
+    /// The account can be restored until then.
+    pub delete_after: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExportedUserDTO {
+    pub id: ID,
+
+    pub key: String,
+    pub name: String,
+    pub role: Role,
+
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for ExportedUserDTO {
+    fn from(value: User) -> Self {
+        Self {
+            id: value.id,
+            key: value.key,
+            name: value.name,
+            role: value.role,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+/// Everything stored about a user, as returned by `GET /me/export/`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserExportDTO {
+    pub exported_at: DateTime<Utc>,
+
+    pub user: ExportedUserDTO,
+    pub word_pairs: Vec<WordPairDTO>,
+    pub trashed_word_pairs: Vec<TrashedWordPairDTO>,
+    pub api_keys: Vec<ApiKeyDTO>,
+    pub review_states: Vec<ReviewStateDTO>,
+    pub review_logs: Vec<ReviewLogDTO>,
+    pub sessions: Vec<SessionDTO>,
+    /// Graded quizzes come with their result.
+    pub quizzes: Vec<QuizDTO>,
+}
@@ -1,16 +1,32 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
+    http::{StatusCode, header},
+    response::IntoResponse,
 };
+use chrono::Utc;
 
 use crate::{
     AppState,
     api::{
+        api_key::models::ApiKeyDTO,
+        auth::current_user::CurrentUser,
+        quiz::models::QuizDTO,
+        review::models::{ReviewLogDTO, ReviewStateDTO},
+        session::models::SessionDTO,
         types::HandlerError,
-        user::models::{CreateUserDTO, UpdateUserDTO, UserDTO},
+        user::models::{
+            ChangePasswordDTO, CreateUserDTO, DeletionScheduledDTO, ExportedUserDTO,
+            ResetPasswordDTO, UpdateUserDTO, UserDTO, UserExportDTO,
+        },
+        word_pair::models::{TrashedWordPairDTO, WordPairDTO},
+    },
+    application::services::{
+        api_key_service::ApiKeyServiceError, password_reset_service::PasswordResetServiceError,
+        quiz_service::QuizServiceError, review_service::ReviewServiceError,
+        session_service::SessionServiceError, token_service::TokenServiceError,
+        user_service::UserServiceError, word_pair_service::WordPairServiceError,
     },
-    application::services::user_service::UserServiceError,
     domain::types::ID,
 };
 
@@ -44,8 +60,11 @@ pub async fn create_user(
 
 pub async fn get_user_by_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<ID>,
 ) -> Result<Json<UserDTO>, HandlerError> {
+    current_user.ensure_owner(&id)?;
+
     let res = state
         .user_service
         .get_by_id(&id)
@@ -65,6 +84,7 @@ pub async fn get_user_by_id(
 
 pub async fn get_user_by_key(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(key): Path<String>,
 ) -> Result<Json<UserDTO>, HandlerError> {
     let res = state
@@ -79,6 +99,8 @@ pub async fn get_user_by_key(
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
         })?;
 
+    current_user.ensure_owner(&res.id)?;
+
     let user = UserDTO::from(res);
 
     Ok(Json(user))
@@ -86,9 +108,12 @@ pub async fn get_user_by_key(
 
 pub async fn update_user_by_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<ID>,
     Json(dto): Json<UpdateUserDTO>,
 ) -> Result<Json<UserDTO>, HandlerError> {
+    current_user.ensure_owner(&id)?;
+
     let res = state
         .user_service
         .update_by_id(&id, &dto)
@@ -113,19 +138,249 @@ pub async fn update_user_by_id(
     Ok(Json(user))
 }
 
+/// Same as `DELETE /me/`, only for the caller's own id.
 pub async fn delete_user_by_id(
-    State(state): State<AppState>,
+    state: State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<ID>,
+) -> Result<Json<DeletionScheduledDTO>, HandlerError> {
+    current_user.ensure_owner(&id)?;
+
+    delete_me(state, current_user).await
+}
+
+pub async fn get_me(
+    state: State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<UserDTO>, HandlerError> {
+    let id = current_user.id;
+
+    get_user_by_id(state, current_user, Path(id)).await
+}
+
+pub async fn update_me(
+    state: State<AppState>,
+    current_user: CurrentUser,
+    dto: Json<UpdateUserDTO>,
+) -> Result<Json<UserDTO>, HandlerError> {
+    let id = current_user.id;
+
+    update_user_by_id(state, current_user, Path(id), dto).await
+}
+
+/// Deactivates the account and signs it out everywhere. The account and its
+/// data are deleted once the grace period is over, unless it is restored
+/// with `POST /account/restore/` before.
+pub async fn delete_me(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<DeletionScheduledDTO>, HandlerError> {
+    current_user.ensure_access_token()?;
+
+    let res = state
+        .user_service
+        .schedule_deletion(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            UserServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            UserServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "User not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    state
+        .token_service
+        .revoke_user(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            TokenServiceError::Database(_) | TokenServiceError::Revocation(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(DeletionScheduledDTO {
+        delete_after: res.delete_after.unwrap_or_else(Utc::now),
+    }))
+}
+
+/// Everything stored about the caller as a JSON file download.
+pub async fn export_me(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    let user = state
+        .user_service
+        .get_by_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            UserServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            UserServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "User not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let word_pairs = state
+        .word_pair_service
+        .get_by_user_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let trashed_word_pairs = state
+        .word_pair_service
+        .get_trash_by_user_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let api_keys =
+        state
+            .api_key_service
+            .list(&current_user.id)
+            .await
+            .map_err(|error| match error {
+                ApiKeyServiceError::Database(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+            })?;
+
+    let review_states = state
+        .review_service
+        .get_by_user_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            ReviewServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let review_logs = state
+        .review_service
+        .get_logs_by_user_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            ReviewServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let sessions = state
+        .session_service
+        .get_by_user_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            SessionServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let quizzes = state
+        .quiz_service
+        .get_by_user_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            QuizServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let export = UserExportDTO {
+        exported_at: Utc::now(),
+        user: ExportedUserDTO::from(user),
+        word_pairs: word_pairs.into_iter().map(WordPairDTO::from).collect(),
+        trashed_word_pairs: trashed_word_pairs
+            .into_iter()
+            .filter_map(|word_pair| {
+                let purge_at = state.word_pair_service.purge_at(&word_pair);
+
+                TrashedWordPairDTO::new(word_pair, purge_at)
+            })
+            .collect(),
+        api_keys: api_keys.into_iter().map(ApiKeyDTO::from).collect(),
+        review_states: review_states
+            .into_iter()
+            .map(ReviewStateDTO::from)
+            .collect(),
+        review_logs: review_logs.into_iter().map(ReviewLogDTO::from).collect(),
+        sessions: sessions.into_iter().map(SessionDTO::from).collect(),
+        quizzes: quizzes.into_iter().map(QuizDTO::from).collect(),
+    };
+
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"wordy-export.json\"",
+        )],
+        Json(export),
+    ))
+}
+
+pub async fn change_my_password(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(dto): Json<ChangePasswordDTO>,
 ) -> Result<(), HandlerError> {
+    current_user.ensure_access_token()?;
+
     state
         .user_service
-        .delete_by_id(&id)
+        .change_password(&current_user.id, &dto.current_password, &dto.new_password)
         .await
         .map_err(|error| match error {
             UserServiceError::Database(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
             }
             UserServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "User not found"),
+            UserServiceError::InvalidCredentials(_) => {
+                (StatusCode::FORBIDDEN, "Current password is wrong")
+            }
+            UserServiceError::InvalidPassword(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, "Password is too easy")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(())
+}
+
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(dto): Json<ResetPasswordDTO>,
+) -> Result<(), HandlerError> {
+    state
+        .password_reset_service
+        .reset(&dto.token, &dto.new_password)
+        .await
+        .map_err(|error| match error {
+            PasswordResetServiceError::InvalidToken => {
+                (StatusCode::UNAUTHORIZED, "Invalid or expired reset token")
+            }
+            PasswordResetServiceError::User(UserServiceError::InvalidPassword(_)) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, "Password is too easy")
+            }
+            PasswordResetServiceError::User(UserServiceError::NotFound(_)) => {
+                (StatusCode::NOT_FOUND, "User not found")
+            }
+            PasswordResetServiceError::Database(_)
+            | PasswordResetServiceError::User(UserServiceError::Database(_)) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
         })?;
 
@@ -1,7 +1,20 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::domain::{models::word_pair::WordPair, types::ID};
+use crate::domain::{
+    models::{
+        answer_check::{AnswerCheck, DiffKind, DiffSegment, Verdict},
+        session::Direction,
+        word_pair::{DuplicatePolicy, WordPair, WordPairFilter, WordPairSearchHit, WordPairSort},
+    },
+    types::ID,
+};
+
+const DEFAULT_WORD_PAIRS_LIMIT: i64 = 50;
+const MAX_WORD_PAIRS_LIMIT: i64 = 100;
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+const MAX_SEARCH_LIMIT: i64 = 50;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WordPairDTO {
@@ -12,6 +25,9 @@ pub struct WordPairDTO {
     pub source_text: String,
     pub target_language: String,
     pub source_language: String,
+
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 impl From<WordPair> for WordPairDTO {
@@ -23,10 +39,33 @@ impl From<WordPair> for WordPairDTO {
             source_text: value.source_text,
             target_language: value.target_language,
             source_language: value.source_language,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
         }
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrashedWordPairDTO {
+    #[serde(flatten)]
+    pub word_pair: WordPairDTO,
+
+    pub deleted_at: DateTime<Utc>,
+    /// When the word pair is deleted for good.
+    pub purge_at: DateTime<Utc>,
+}
+
+impl TrashedWordPairDTO {
+    /// `None` unless the word pair is in the trash.
+    pub fn new(word_pair: WordPair, purge_at: Option<DateTime<Utc>>) -> Option<Self> {
+        Some(Self {
+            deleted_at: word_pair.deleted_at?,
+            purge_at: purge_at?,
+            word_pair: WordPairDTO::from(word_pair),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Validate)]
 pub struct CreateWordPairDTO {
     pub target_text: String,
@@ -37,3 +76,158 @@ pub struct CreateWordPairDTO {
     #[validate(length(min = 1, max = 5))]
     pub source_language: String,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate)]
+pub struct UpdateWordPairDTO {
+    #[validate(length(min = 1, max = 100))]
+    pub target_text: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub source_text: Option<String>,
+    #[validate(length(min = 1, max = 5))]
+    pub target_language: Option<String>,
+    #[validate(length(min = 1, max = 5))]
+    pub source_language: Option<String>,
+    /// Replaces `target_text` with a fresh translation of the updated pair.
+    #[serde(default)]
+    pub retranslate: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateWordPairQuery {
+    /// What to do if the user already has this word pair, `reject` by
+    /// default.
+    #[serde(default)]
+    pub on_duplicate: DuplicatePolicy,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DuplicatesMergedDTO {
+    /// The word pairs the duplicates were merged into.
+    pub kept: Vec<WordPairDTO>,
+    /// How many duplicates were moved to the trash.
+    pub trashed: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListWordPairsQuery {
+    /// `newest` (default), `oldest`, `source_text` or `target_text`.
+    #[serde(default)]
+    pub sort: WordPairSort,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    /// Exclusive.
+    pub created_to: Option<DateTime<Utc>>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl ListWordPairsQuery {
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_WORD_PAIRS_LIMIT)
+            .clamp(1, MAX_WORD_PAIRS_LIMIT)
+    }
+
+    pub fn filter(&self) -> WordPairFilter {
+        WordPairFilter {
+            source_language: self.source_language.as_deref().map(str::to_lowercase),
+            target_language: self.target_language.as_deref().map(str::to_lowercase),
+            created_from: self.created_from,
+            created_to: self.created_to,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WordPairPageDTO {
+    pub word_pairs: Vec<WordPairDTO>,
+    /// Pass as `cursor` to get the next page, `None` on the last one.
+    pub next_cursor: Option<String>,
+    /// Number of all word pairs matching the filters.
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchWordPairsQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+impl SearchWordPairsQuery {
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WordPairSearchHitDTO {
+    #[serde(flatten)]
+    pub word_pair: WordPairDTO,
+
+    pub rank: f32,
+    /// The texts with the matches wrapped in `<mark>` and `</mark>`.
+    pub source_highlight: String,
+    pub target_highlight: String,
+}
+
+impl From<WordPairSearchHit> for WordPairSearchHitDTO {
+    fn from(value: WordPairSearchHit) -> Self {
+        Self {
+            word_pair: WordPairDTO::from(value.word_pair),
+            rank: value.rank,
+            source_highlight: value.source_highlight,
+            target_highlight: value.target_highlight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Validate)]
+pub struct CheckAnswerDTO {
+    #[validate(length(min = 1, max = 100))]
+    pub answer: String,
+    /// `source_to_target` (default) checks against the target text,
+    /// `target_to_source` against the source text.
+    #[serde(default)]
+    pub direction: Direction,
+    /// Accepts `cafe` for `café`.
+    #[serde(default)]
+    pub ignore_diacritics: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DiffSegmentDTO {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+impl From<DiffSegment> for DiffSegmentDTO {
+    fn from(value: DiffSegment) -> Self {
+        Self {
+            kind: value.kind,
+            text: value.text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnswerCheckDTO {
+    pub verdict: Verdict,
+    pub expected: String,
+    pub distance: usize,
+    pub diff: Vec<DiffSegmentDTO>,
+}
+
+impl From<AnswerCheck> for AnswerCheckDTO {
+    fn from(value: AnswerCheck) -> Self {
+        Self {
+            verdict: value.verdict,
+            expected: value.expected,
+            distance: value.distance,
+            diff: value.diff.into_iter().map(DiffSegmentDTO::from).collect(),
+        }
+    }
+}
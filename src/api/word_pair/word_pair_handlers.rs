@@ -1,27 +1,47 @@
 use axum::{
-    Json,
-    extract::{Path, State},
+    Extension, Json,
+    extract::{Path, Query, State},
     http::StatusCode,
 };
+use validator::Validate;
 
 use crate::{
     AppState,
     api::{
+        auth::current_user::CurrentUser,
         translate::models::TranslateDTO,
-        word_pair::models::{CreateWordPairDTO, WordPairDTO},
+        word_pair::models::{
+            AnswerCheckDTO, CheckAnswerDTO, CreateWordPairDTO, CreateWordPairQuery,
+            DuplicatesMergedDTO, ListWordPairsQuery, SearchWordPairsQuery, TrashedWordPairDTO,
+            UpdateWordPairDTO, WordPairDTO, WordPairPageDTO, WordPairSearchHitDTO,
+        },
     },
     application::services::{
         translate_service::TranslateServiceError, user_service::UserServiceError,
         word_pair_service::WordPairServiceError,
     },
 };
-use crate::{api::types::HandlerError, domain::types::ID};
+use crate::{
+    api::types::HandlerError,
+    domain::{
+        models::{
+            permission::{Permission, Permissions},
+            session::Direction,
+            word_pair::WordPairCursor,
+        },
+        types::ID,
+    },
+};
 
 pub async fn translate_and_add_word_pair_by_user_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(user_id): Path<ID>,
+    Query(query): Query<CreateWordPairQuery>,
     Json(dto): Json<TranslateDTO>,
 ) -> Result<Json<WordPairDTO>, HandlerError> {
+    current_user.ensure_owner(&user_id)?;
+
     let translated =
         state
             .translate_service
@@ -46,7 +66,7 @@ pub async fn translate_and_add_word_pair_by_user_id(
 
     let res = state
         .word_pair_service
-        .create(&user_id, &create_word_pair_dto)
+        .create(&user_id, &create_word_pair_dto, query.on_duplicate)
         .await
         .map_err(|error| match error {
             WordPairServiceError::Database(_) => {
@@ -65,9 +85,25 @@ pub async fn translate_and_add_word_pair_by_user_id(
 
 pub async fn translate_and_add_word_pair_by_user_key(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(key): Path<String>,
+    Query(query): Query<CreateWordPairQuery>,
     Json(dto): Json<TranslateDTO>,
 ) -> Result<Json<WordPairDTO>, HandlerError> {
+    let user = state
+        .user_service
+        .get_by_key(&key)
+        .await
+        .map_err(|error| match error {
+            UserServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            UserServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "User not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    current_user.ensure_owner(&user.id)?;
+
     let translated =
         state
             .translate_service
@@ -90,21 +126,9 @@ pub async fn translate_and_add_word_pair_by_user_key(
         source_language: translated.source_language,
     };
 
-    let user = state
-        .user_service
-        .get_by_key(&key)
-        .await
-        .map_err(|error| match error {
-            UserServiceError::Database(_) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
-            }
-            UserServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "User not found"),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
-        })?;
-
     let res = state
         .word_pair_service
-        .create(&user.id, &create_word_pair_dto)
+        .create(&user.id, &create_word_pair_dto, query.on_duplicate)
         .await
         .map_err(|error| match error {
             WordPairServiceError::Database(_) => {
@@ -123,12 +147,16 @@ pub async fn translate_and_add_word_pair_by_user_key(
 
 pub async fn add_word_pair_by_user_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(user_id): Path<ID>,
+    Query(query): Query<CreateWordPairQuery>,
     Json(dto): Json<CreateWordPairDTO>,
 ) -> Result<Json<WordPairDTO>, HandlerError> {
+    current_user.ensure_owner(&user_id)?;
+
     let res = state
         .word_pair_service
-        .create(&user_id, &dto)
+        .create(&user_id, &dto, query.on_duplicate)
         .await
         .map_err(|error| match error {
             WordPairServiceError::Database(_) => {
@@ -147,7 +175,9 @@ pub async fn add_word_pair_by_user_id(
 
 pub async fn add_word_pair_by_user_key(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(key): Path<String>,
+    Query(query): Query<CreateWordPairQuery>,
     Json(dto): Json<CreateWordPairDTO>,
 ) -> Result<Json<WordPairDTO>, HandlerError> {
     let user = state
@@ -162,9 +192,11 @@ pub async fn add_word_pair_by_user_key(
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
         })?;
 
+    current_user.ensure_owner(&user.id)?;
+
     let res = state
         .word_pair_service
-        .create(&user.id, &dto)
+        .create(&user.id, &dto, query.on_duplicate)
         .await
         .map_err(|error| match error {
             WordPairServiceError::Database(_) => {
@@ -183,6 +215,7 @@ pub async fn add_word_pair_by_user_key(
 
 pub async fn get_word_pair_by_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<ID>,
 ) -> Result<Json<WordPairDTO>, HandlerError> {
     let res = state
@@ -197,6 +230,8 @@ pub async fn get_word_pair_by_id(
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
         })?;
 
+    current_user.ensure_owner(&res.user_id)?;
+
     let word_pair = WordPairDTO::from(res);
 
     Ok(Json(word_pair))
@@ -204,33 +239,21 @@ pub async fn get_word_pair_by_id(
 
 pub async fn get_word_pairs_by_user_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(user_id): Path<ID>,
-) -> Result<Json<Vec<WordPairDTO>>, HandlerError> {
-    let res = state
-        .word_pair_service
-        .get_by_user_id(&user_id)
-        .await
-        .map_err(|error| match error {
-            WordPairServiceError::Database(_) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
-            }
-            WordPairServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "Word pair not found"),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
-        })?;
-
-    let mut dtos: Vec<WordPairDTO> = vec![];
+    Query(query): Query<ListWordPairsQuery>,
+) -> Result<Json<WordPairPageDTO>, HandlerError> {
+    current_user.ensure_owner(&user_id)?;
 
-    for res_item in res.into_iter() {
-        dtos.push(WordPairDTO::from(res_item))
-    }
-
-    Ok(Json(dtos))
+    list_word_pairs(&state, &user_id, &query).await
 }
 
 pub async fn get_word_pairs_by_user_key(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(key): Path<String>,
-) -> Result<Json<Vec<WordPairDTO>>, HandlerError> {
+    Query(query): Query<ListWordPairsQuery>,
+) -> Result<Json<WordPairPageDTO>, HandlerError> {
     let user = state
         .user_service
         .get_by_key(&key)
@@ -243,9 +266,64 @@ pub async fn get_word_pairs_by_user_key(
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
         })?;
 
-    let res = state
+    current_user.ensure_owner(&user.id)?;
+
+    list_word_pairs(&state, &user.id, &query).await
+}
+
+/// One page of the user's word pairs, see `ListWordPairsQuery`.
+async fn list_word_pairs(
+    state: &AppState,
+    user_id: &ID,
+    query: &ListWordPairsQuery,
+) -> Result<Json<WordPairPageDTO>, HandlerError> {
+    let after = match query.cursor.as_deref() {
+        Some(cursor) => Some(
+            WordPairCursor::decode(cursor).ok_or((StatusCode::BAD_REQUEST, "Invalid cursor"))?,
+        ),
+        None => None,
+    };
+
+    let (word_pairs, next_cursor, total) = state
         .word_pair_service
-        .get_by_user_id(&user.id)
+        .list(
+            user_id,
+            &query.filter(),
+            query.sort,
+            after.as_ref(),
+            query.limit(),
+        )
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            WordPairServiceError::InvalidCursor => (StatusCode::BAD_REQUEST, "Invalid cursor"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(WordPairPageDTO {
+        word_pairs: word_pairs.into_iter().map(WordPairDTO::from).collect(),
+        next_cursor: next_cursor.map(|cursor| cursor.encode()),
+        total,
+    }))
+}
+
+/// Changes the given fields of the word pair. With `retranslate` the target
+/// text is translated again from the updated source text and languages.
+pub async fn update_word_pair_by_id(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Extension(permissions): Extension<Permissions>,
+    Path(id): Path<ID>,
+    Json(mut dto): Json<UpdateWordPairDTO>,
+) -> Result<Json<WordPairDTO>, HandlerError> {
+    dto.validate()
+        .map_err(|_| (StatusCode::UNPROCESSABLE_ENTITY, "Invalid word pair"))?;
+
+    let word_pair = state
+        .word_pair_service
+        .get_by_id(&id)
         .await
         .map_err(|error| match error {
             WordPairServiceError::Database(_) => {
@@ -255,19 +333,98 @@ pub async fn get_word_pairs_by_user_key(
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
         })?;
 
-    let mut dtos: Vec<WordPairDTO> = vec![];
+    current_user.ensure_owner(&word_pair.user_id)?;
+
+    if dto.retranslate {
+        if dto.target_text.is_some() {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Target text can't be set when re-translating",
+            ));
+        }
+
+        if !permissions.contains(Permission::Translate) {
+            return Err((StatusCode::FORBIDDEN, "Missing permission: translate"));
+        }
 
-    for res_item in res.into_iter() {
-        dtos.push(WordPairDTO::from(res_item))
+        // A conflicting update would be rejected anyway, don't pay for its
+        // translation.
+        state
+            .word_pair_service
+            .check_update(&id, &dto)
+            .await
+            .map_err(update_error)?;
+
+        let translate_dto = TranslateDTO {
+            source_text: dto.source_text.clone().unwrap_or(word_pair.source_text),
+            target_language: dto
+                .target_language
+                .clone()
+                .unwrap_or(word_pair.target_language),
+            source_language: dto
+                .source_language
+                .clone()
+                .unwrap_or(word_pair.source_language),
+        };
+
+        let translated = state
+            .translate_service
+            .translate_text(&translate_dto)
+            .await
+            .map_err(|error| match error {
+                TranslateServiceError::TranslatorError(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "Translation failed")
+                }
+                TranslateServiceError::NotFoundLanguage(_) => {
+                    (StatusCode::BAD_REQUEST, "Language not found")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+            })?;
+
+        dto.target_text = Some(translated.target_text);
     }
 
-    Ok(Json(dtos))
+    let res = state
+        .word_pair_service
+        .update_by_id(&id, &dto)
+        .await
+        .map_err(update_error)?;
+
+    Ok(Json(WordPairDTO::from(res)))
+}
+
+fn update_error(error: WordPairServiceError) -> HandlerError {
+    match error {
+        WordPairServiceError::Database(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+        }
+        WordPairServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "Word pair not found"),
+        WordPairServiceError::WordPairAlreadyExists(_) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, "Word pair exists")
+        }
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+    }
 }
 
 pub async fn delete_word_pair_by_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<ID>,
 ) -> Result<(), HandlerError> {
+    let word_pair = state
+        .word_pair_service
+        .get_by_id(&id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            WordPairServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "Word pair not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    current_user.ensure_owner(&word_pair.user_id)?;
+
     state
         .word_pair_service
         .delete_by_id(&id)
@@ -282,3 +439,202 @@ pub async fn delete_word_pair_by_id(
 
     Ok(())
 }
+
+pub async fn translate_and_add_my_word_pair(
+    state: State<AppState>,
+    current_user: CurrentUser,
+    query: Query<CreateWordPairQuery>,
+    dto: Json<TranslateDTO>,
+) -> Result<Json<WordPairDTO>, HandlerError> {
+    let user_id = current_user.id;
+
+    translate_and_add_word_pair_by_user_id(state, current_user, Path(user_id), query, dto).await
+}
+
+pub async fn add_my_word_pair(
+    state: State<AppState>,
+    current_user: CurrentUser,
+    query: Query<CreateWordPairQuery>,
+    dto: Json<CreateWordPairDTO>,
+) -> Result<Json<WordPairDTO>, HandlerError> {
+    let user_id = current_user.id;
+
+    add_word_pair_by_user_id(state, current_user, Path(user_id), query, dto).await
+}
+
+pub async fn get_my_word_pairs(
+    state: State<AppState>,
+    current_user: CurrentUser,
+    query: Query<ListWordPairsQuery>,
+) -> Result<Json<WordPairPageDTO>, HandlerError> {
+    let user_id = current_user.id;
+
+    get_word_pairs_by_user_id(state, current_user, Path(user_id), query).await
+}
+
+/// The caller's word pairs whose source or target text matches `q`, also
+/// with typos, best match first.
+pub async fn search_my_word_pairs(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Query(query): Query<SearchWordPairsQuery>,
+) -> Result<Json<Vec<WordPairSearchHitDTO>>, HandlerError> {
+    let res = state
+        .word_pair_service
+        .search(&current_user.id, &query.q, query.limit())
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            WordPairServiceError::InvalidQuery(_) => {
+                (StatusCode::BAD_REQUEST, "Invalid search query")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(
+        res.into_iter().map(WordPairSearchHitDTO::from).collect(),
+    ))
+}
+
+/// Word pairs the caller deleted and can still restore.
+pub async fn get_my_word_pair_trash(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<Vec<TrashedWordPairDTO>>, HandlerError> {
+    let res = state
+        .word_pair_service
+        .get_trash_by_user_id(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let word_pairs = res
+        .into_iter()
+        .filter_map(|word_pair| {
+            let purge_at = state.word_pair_service.purge_at(&word_pair);
+
+            TrashedWordPairDTO::new(word_pair, purge_at)
+        })
+        .collect();
+
+    Ok(Json(word_pairs))
+}
+
+pub async fn restore_my_word_pair(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+) -> Result<Json<WordPairDTO>, HandlerError> {
+    let res = state
+        .word_pair_service
+        .restore(&current_user.id, &id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            WordPairServiceError::NotFound(_) => {
+                (StatusCode::NOT_FOUND, "Word pair not found in trash")
+            }
+            WordPairServiceError::WordPairAlreadyExists(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, "Word pair exists")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(WordPairDTO::from(res)))
+}
+
+/// Checks a typed answer, tolerating case, punctuation and small typos.
+pub async fn check_my_word_pair_answer(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<ID>,
+    Json(dto): Json<CheckAnswerDTO>,
+) -> Result<Json<AnswerCheckDTO>, HandlerError> {
+    dto.validate()
+        .map_err(|_| (StatusCode::UNPROCESSABLE_ENTITY, "Invalid answer"))?;
+
+    if dto.direction == Direction::Mixed {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Direction must be source_to_target or target_to_source",
+        ));
+    }
+
+    let word_pair = state
+        .word_pair_service
+        .get_by_id(&id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            WordPairServiceError::NotFound(_) => (StatusCode::NOT_FOUND, "Word pair not found"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    current_user.ensure_owner(&word_pair.user_id)?;
+
+    let res = state.word_pair_service.check_answer(
+        &word_pair,
+        &dto.answer,
+        dto.direction,
+        dto.ignore_diacritics,
+    );
+
+    Ok(Json(AnswerCheckDTO::from(res)))
+}
+
+/// The caller's word pairs that share languages and source text, grouped.
+pub async fn get_my_word_pair_duplicates(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<Vec<Vec<WordPairDTO>>>, HandlerError> {
+    let res = state
+        .word_pair_service
+        .find_duplicates(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    let groups = res
+        .into_iter()
+        .map(|group| group.into_iter().map(WordPairDTO::from).collect())
+        .collect();
+
+    Ok(Json(groups))
+}
+
+/// Merges each group of duplicates into its oldest word pair, the rest go
+/// to the trash.
+pub async fn merge_my_word_pair_duplicates(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<DuplicatesMergedDTO>, HandlerError> {
+    let (kept, trashed) = state
+        .word_pair_service
+        .merge_duplicates(&current_user.id)
+        .await
+        .map_err(|error| match error {
+            WordPairServiceError::Database(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "Unknown error"),
+        })?;
+
+    Ok(Json(DuplicatesMergedDTO {
+        kept: kept.into_iter().map(WordPairDTO::from).collect(),
+        trashed,
+    }))
+}
@@ -0,0 +1,387 @@
+use chrono::{Duration, Utc};
+use thiserror::Error;
+use tracing::{error, warn};
+
+use crate::{
+    domain::{
+        models::api_key::{ApiKey, ApiKeyScope},
+        traits::repositories::api_key_repository::IApiKeyRepository,
+        types::ID,
+    },
+    infrastructure::utils::secret_token::{generate_token, hash_token},
+};
+
+/// Marks the keys so they are easy to spot in configs and leaked logs.
+const KEY_PREFIX: &str = "wordy_";
+/// How much of the key is kept in plain text to tell keys apart.
+const SHOWN_PREFIX_LEN: usize = KEY_PREFIX.len() + 6;
+
+#[derive(Clone)]
+pub struct ApiKeyService<Repo> {
+    repo: Repo,
+    /// `last_used_at` is only written when it is older than this, so a busy
+    /// bot does not cause a write per request.
+    last_used_resolution: Duration,
+}
+
+#[derive(Error, Debug)]
+pub enum ApiKeyServiceError {
+    #[error("Invalid or revoked API key")]
+    InvalidKey,
+
+    #[error("API key not found: `{0}`")]
+    NotFound(ID),
+
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Repo> ApiKeyService<Repo>
+where
+    Repo: IApiKeyRepository<Error = sqlx::Error>,
+{
+    pub fn new(repo: Repo) -> Self {
+        Self {
+            repo,
+            last_used_resolution: Duration::minutes(1),
+        }
+    }
+
+    /// Returns the plain key, which is not stored anywhere, with its record.
+    pub async fn create(
+        &self,
+        user_id: &ID,
+        name: &str,
+        scope: ApiKeyScope,
+    ) -> Result<(String, ApiKey), ApiKeyServiceError> {
+        let key = format!("{}{}", KEY_PREFIX, generate_token::<32>());
+
+        let api_key = ApiKey::new(
+            *user_id,
+            name.to_string(),
+            key[..SHOWN_PREFIX_LEN].to_string(),
+            hash_token(&key),
+            scope,
+        );
+
+        let res = self.repo.insert(&api_key).await.map_err(|error| {
+            error!("API key DB error: {}", error);
+            error
+        })?;
+
+        Ok((key, res))
+    }
+
+    pub async fn list(&self, user_id: &ID) -> Result<Vec<ApiKey>, ApiKeyServiceError> {
+        let res = self
+            .repo
+            .select_by_user_id(user_id)
+            .await
+            .map_err(|error| {
+                error!("API key DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    pub async fn revoke(&self, user_id: &ID, id: &ID) -> Result<ApiKey, ApiKeyServiceError> {
+        let res =
+            self.repo
+                .revoke(id, user_id, &Utc::now())
+                .await
+                .map_err(|error| match &error {
+                    sqlx::Error::RowNotFound => ApiKeyServiceError::NotFound(*id),
+                    _ => {
+                        error!("API key DB error: {}", error);
+                        ApiKeyServiceError::Database(error)
+                    }
+                })?;
+
+        Ok(res)
+    }
+
+    /// Revokes every active key of the user, returns how many there were.
+    pub async fn revoke_all(&self, user_id: &ID) -> Result<u64, ApiKeyServiceError> {
+        let res = self
+            .repo
+            .revoke_by_user_id(user_id, &Utc::now())
+            .await
+            .map_err(|error| {
+                error!("API key DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Resolves a plain key to its active record and notes the use.
+    /// Malformed keys are rejected without a lookup.
+    pub async fn authenticate(&self, key: &str) -> Result<ApiKey, ApiKeyServiceError> {
+        if !key.starts_with(KEY_PREFIX) || key.len() <= SHOWN_PREFIX_LEN {
+            return Err(ApiKeyServiceError::InvalidKey);
+        }
+
+        let mut api_key = self
+            .repo
+            .select_active_by_hash(&hash_token(key))
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => ApiKeyServiceError::InvalidKey,
+                _ => {
+                    error!("API key DB error: {}", error);
+                    ApiKeyServiceError::Database(error)
+                }
+            })?;
+
+        let now = Utc::now();
+
+        if api_key
+            .last_used_at
+            .is_none_or(|last_used_at| now - last_used_at >= self.last_used_resolution)
+        {
+            match self.repo.update_last_used(&api_key.id, &now).await {
+                Ok(()) => api_key.last_used_at = Some(now),
+                Err(error) => warn!(
+                    "Couldn't update last use of API key {}: {}",
+                    api_key.id, error
+                ),
+            }
+        }
+
+        Ok(api_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use uuid::Uuid;
+
+    use crate::{
+        domain::traits::repositories::repository::Repository,
+        infrastructure::storage::database::models::api_key::ApiKeyEntity,
+    };
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct TestApiKeyRepository {
+        keys: Arc<Mutex<Vec<ApiKey>>>,
+    }
+
+    #[async_trait]
+    impl Repository for TestApiKeyRepository {
+        type Pool = ();
+        type Item = ApiKey;
+        type Entity = ApiKeyEntity;
+        type Error = sqlx::Error;
+
+        fn new(_db: Self::Pool) -> Self {
+            Self::default()
+        }
+
+        async fn insert(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            self.keys.lock().unwrap().push(item.clone());
+
+            Ok(item.clone())
+        }
+
+        async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+            self.keys
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|key| key.id == *id)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+            self.keys.lock().unwrap().retain(|key| key.id != *id);
+
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl IApiKeyRepository for TestApiKeyRepository {
+        async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|key| key.user_id == *user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn select_active_by_hash(&self, key_hash: &str) -> Result<Self::Item, Self::Error> {
+            self.keys
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|key| key.key_hash == key_hash && key.revoked_at.is_none())
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn revoke(
+            &self,
+            id: &ID,
+            user_id: &ID,
+            revoked_at: &DateTime<Utc>,
+        ) -> Result<Self::Item, Self::Error> {
+            let mut keys = self.keys.lock().unwrap();
+
+            let key = keys
+                .iter_mut()
+                .find(|key| key.id == *id && key.user_id == *user_id && key.revoked_at.is_none())
+                .ok_or(sqlx::Error::RowNotFound)?;
+
+            key.revoked_at = Some(*revoked_at);
+
+            Ok(key.clone())
+        }
+
+        async fn revoke_by_user_id(
+            &self,
+            user_id: &ID,
+            revoked_at: &DateTime<Utc>,
+        ) -> Result<u64, Self::Error> {
+            let mut keys = self.keys.lock().unwrap();
+
+            let revoked = keys
+                .iter_mut()
+                .filter(|key| key.user_id == *user_id && key.revoked_at.is_none())
+                .map(|key| key.revoked_at = Some(*revoked_at))
+                .count();
+
+            Ok(revoked as u64)
+        }
+
+        async fn update_last_used(
+            &self,
+            id: &ID,
+            used_at: &DateTime<Utc>,
+        ) -> Result<(), Self::Error> {
+            if let Some(key) = self
+                .keys
+                .lock()
+                .unwrap()
+                .iter_mut()
+                .find(|key| key.id == *id)
+            {
+                key.last_used_at = Some(*used_at);
+            }
+
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_authenticate() {
+        let repo = TestApiKeyRepository::default();
+        let service = ApiKeyService::new(repo.clone());
+        let user_id = Uuid::new_v4();
+
+        let (key, api_key) = service
+            .create(&user_id, "telegram bot", ApiKeyScope::ReadWrite)
+            .await
+            .unwrap();
+
+        assert!(key.starts_with(&api_key.prefix));
+        assert_ne!(key, api_key.key_hash);
+
+        let res = service.authenticate(&key).await.unwrap();
+
+        assert_eq!(res.id, api_key.id);
+        assert_eq!(res.user_id, user_id);
+        assert_eq!(res.scope, ApiKeyScope::ReadWrite);
+        assert!(
+            repo.select_by_id(&api_key.id)
+                .await
+                .unwrap()
+                .last_used_at
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_revoked_key() {
+        let service = ApiKeyService::new(TestApiKeyRepository::default());
+        let user_id = Uuid::new_v4();
+
+        let (key, api_key) = service
+            .create(&user_id, "cli", ApiKeyScope::ReadOnly)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.revoke(&Uuid::new_v4(), &api_key.id).await,
+            Err(ApiKeyServiceError::NotFound(_))
+        ));
+
+        service.revoke(&user_id, &api_key.id).await.unwrap();
+
+        assert!(matches!(
+            service.authenticate(&key).await,
+            Err(ApiKeyServiceError::InvalidKey)
+        ));
+        assert_eq!(service.list(&user_id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_all() {
+        let service = ApiKeyService::new(TestApiKeyRepository::default());
+        let user_id = Uuid::new_v4();
+
+        let (first, _) = service
+            .create(&user_id, "cli", ApiKeyScope::ReadOnly)
+            .await
+            .unwrap();
+        let (second, api_key) = service
+            .create(&user_id, "bot", ApiKeyScope::ReadWrite)
+            .await
+            .unwrap();
+        let (other, _) = service
+            .create(&Uuid::new_v4(), "cli", ApiKeyScope::ReadOnly)
+            .await
+            .unwrap();
+
+        service.revoke(&user_id, &api_key.id).await.unwrap();
+
+        assert_eq!(service.revoke_all(&user_id).await.unwrap(), 1);
+
+        for key in [&first, &second] {
+            assert!(matches!(
+                service.authenticate(key).await,
+                Err(ApiKeyServiceError::InvalidKey)
+            ));
+        }
+
+        assert!(service.authenticate(&other).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reject_malformed_key() {
+        let service = ApiKeyService::new(TestApiKeyRepository::default());
+
+        for key in ["", "wordy_", "not-a-key"] {
+            assert!(
+                matches!(
+                    service.authenticate(key).await,
+                    Err(ApiKeyServiceError::InvalidKey)
+                ),
+                "{key}"
+            );
+        }
+    }
+}
@@ -1,100 +1,193 @@
-use std::{env::VarError, path::PathBuf, string::FromUtf8Error};
-
 use crate::{
     api::auth::models::Claims,
-    infrastructure::{
-        auth::token::jwks::jwks_validator::{JwksTokenValidator, JwksTokenValidatorError},
-        external_api::auth::auth_requests::{AuthCommunicator, AuthCommunicatorError},
-        utils::io::files::files_io::FileIO,
+    domain::traits::auth::public_key_source::IPublicKeySource,
+    infrastructure::auth::token::{
+        jwks::jwks_validator::{
+            JwksTokenValidator, JwksTokenValidatorError, TokenValidationConfig,
+        },
+        key_cache::{KeyCacheConfig, KeyCacheError, PublicKeyCache},
     },
 };
-use chrono::Utc;
 use thiserror::Error;
-use tracing::error;
+use tracing::warn;
 
-#[derive(Debug, Clone)]
-pub struct AuthService {
+#[derive(Clone)]
+pub struct AuthService<KeySource> {
     pub validator: JwksTokenValidator,
-    pub http_client: reqwest::Client,
-    pub public_pem_file_io: FileIO,
-    pub auth_communicator: AuthCommunicator,
+    pub key_cache: PublicKeyCache<KeySource>,
 }
 
 #[derive(Debug, Error)]
 pub enum AuthServiceError {
-    #[error("IO error: {0}")]
-    IO(#[from] std::io::Error),
-
     #[error("Unknown error")]
     Unknown,
 
-    #[error("From UTF-8 error: {0}")]
-    FromUtf8(#[from] FromUtf8Error),
-
     #[error("Validation error: {0}")]
     Validation(#[from] JwksTokenValidatorError),
 
-    #[error("Auth communicator error: {0}")]
-    AuthCommunicator(#[from] AuthCommunicatorError),
+    #[error("Key cache error: {0}")]
+    KeyCache(#[from] KeyCacheError),
 }
 
-impl AuthService {
-    pub fn new() -> Self {
-        let validator = JwksTokenValidator;
-        let http_client = reqwest::Client::new();
-        let keys_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("/keys");
-
-        if !keys_dir.exists() {
-            let _ = std::fs::create_dir(&keys_dir).map_err(|error| match error {
-                err => {
-                    error!("Couldn't create keys directory: {}", &err);
-                    err
-                }
-            });
-        }
-
-        let public_pem_file_io = FileIO::new(
-            keys_dir
-                .join("public.pem")
-                .to_str()
-                .ok_or("Unexpected error")
-                .unwrap(),
-        );
-        let auth_communicator = AuthCommunicator;
+impl<KeySource> AuthService<KeySource>
+where
+    KeySource: IPublicKeySource,
+{
+    pub fn new(
+        key_source: KeySource,
+        key_cache_config: KeyCacheConfig,
+        validation_config: TokenValidationConfig,
+    ) -> Self {
+        let validator = JwksTokenValidator::new(validation_config);
+        let key_cache = PublicKeyCache::new(key_source, key_cache_config);
 
         Self {
             validator,
-            http_client,
-            public_pem_file_io,
-            auth_communicator,
+            key_cache,
         }
     }
 
+    /// Loads the verification key ahead of the first request.
+    pub async fn warm_up(&self) -> Result<(), AuthServiceError> {
+        self.key_cache.get().await?;
+
+        Ok(())
+    }
+
+    /// Verifies the token against the key named by its `kid`. An unknown
+    /// `kid` triggers one JWKS refresh before the token is rejected.
     pub async fn validate_token(&self, token: &str) -> Result<Claims, AuthServiceError> {
-        let resp = self.public_pem_file_io.read()?;
+        let kid = self.validator.key_id(token)?;
 
-        let public_pem: String;
+        let mut jwks = self.key_cache.get().await?;
 
-        if resp.is_empty() {
-            public_pem = self.auth_communicator.get_public_pem().await?;
+        if self.validator.select_key(kid.as_deref(), &jwks).is_none() {
+            warn!("Unknown signing key {:?}, refreshing JWKS", kid);
 
-            self.public_pem_file_io.write(&public_pem)?;
-        } else {
-            public_pem = String::from_utf8(resp)?;
+            jwks = self.key_cache.refresh().await?;
         }
 
-        let claims = self.validator.verify(&token, &public_pem)?;
+        let jwk = self
+            .validator
+            .select_key(kid.as_deref(), &jwks)
+            .ok_or_else(|| JwksTokenValidatorError::UnknownKey(kid.unwrap_or_default()))?;
+
+        let claims = self.validator.verify(token, jwk)?;
 
         Ok(claims)
     }
+}
 
-    pub fn check_exp(&self, claims: &Claims) -> bool {
-        let datetime_now = Utc::now();
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use jsonwebtoken::jwk::JwkSet;
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{models::role::Role, traits::auth::token_issuer::ITokenIssuer},
+        infrastructure::auth::token::local::local_issuer::{
+            LocalTokenIssuer, LocalTokenIssuerError,
+        },
+    };
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct RotatingKeySource {
+        issuer: Arc<Mutex<LocalTokenIssuer>>,
+        fetches: Arc<AtomicUsize>,
+    }
 
-        if claims.exp < datetime_now {
-            true
-        } else {
-            false
+    impl RotatingKeySource {
+        fn new(issuer: LocalTokenIssuer) -> Self {
+            Self {
+                issuer: Arc::new(Mutex::new(issuer)),
+                fetches: Arc::new(AtomicUsize::new(0)),
+            }
         }
     }
+
+    #[async_trait]
+    impl IPublicKeySource for RotatingKeySource {
+        type Error = LocalTokenIssuerError;
+
+        async fn fetch_jwks(&self) -> Result<JwkSet, Self::Error> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+
+            let issuer = self.issuer.lock().unwrap().clone();
+
+            issuer.fetch_jwks().await
+        }
+    }
+
+    fn key_cache_config() -> KeyCacheConfig {
+        KeyCacheConfig {
+            ttl: Duration::hours(1),
+            min_refresh_interval: Duration::zero(),
+            fallback_file_io: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_after_key_rotation() {
+        let old_issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+        let source = RotatingKeySource::new(old_issuer);
+
+        let auth_service = AuthService::new(
+            source.clone(),
+            key_cache_config(),
+            TokenValidationConfig::default(),
+        );
+        auth_service.warm_up().await.unwrap();
+
+        let new_issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+        *source.issuer.lock().unwrap() = new_issuer.clone();
+
+        let user_id = Uuid::new_v4();
+        let token_pair = new_issuer.issue(&user_id, Role::User).await.unwrap();
+
+        let claims = auth_service
+            .validate_token(&token_pair.access_token)
+            .await
+            .unwrap();
+
+        assert_eq!(claims.sub, user_id);
+        assert_eq!(source.fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reject_unknown_key_after_one_refresh() {
+        let issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+        let source = RotatingKeySource::new(issuer);
+
+        let auth_service = AuthService::new(
+            source.clone(),
+            key_cache_config(),
+            TokenValidationConfig::default(),
+        );
+        auth_service.warm_up().await.unwrap();
+
+        let foreign_issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+        let token_pair = foreign_issuer
+            .issue(&Uuid::new_v4(), Role::User)
+            .await
+            .unwrap();
+
+        let res = auth_service.validate_token(&token_pair.access_token).await;
+
+        assert!(matches!(
+            res,
+            Err(AuthServiceError::Validation(
+                JwksTokenValidatorError::UnknownKey(_)
+            ))
+        ));
+        assert_eq!(source.fetches.load(Ordering::SeqCst), 2);
+    }
 }
@@ -0,0 +1,386 @@
+use std::net::IpAddr;
+
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+use tracing::{error, info, warn};
+
+use crate::domain::{
+    models::login_attempt::{AttemptSubject, Lockout},
+    traits::auth::login_attempt_store::ILoginAttemptStore,
+};
+
+const DEFAULT_PURGE_INTERVAL_SECONDS: u64 = 60 * 60;
+
+#[derive(Debug, Clone, Copy)]
+pub struct AttemptLimits {
+    /// Failures allowed before backoff kicks in.
+    pub free_attempts: u32,
+    /// Failures after which the subject is locked out for `lockout`.
+    pub lockout_after: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginThrottleConfig {
+    pub key_limits: AttemptLimits,
+    /// Looser than `key_limits`, many users can share one address.
+    pub ip_limits: AttemptLimits,
+    /// First backoff delay, doubled with every further failure.
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+    pub lockout: Duration,
+    /// Failures older than this are forgotten.
+    pub window: Duration,
+}
+
+impl LoginThrottleConfig {
+    pub fn from_env() -> Self {
+        let number = |name: &str, default: i64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            key_limits: AttemptLimits {
+                free_attempts: number("LOGIN_KEY_FREE_ATTEMPTS", 3) as u32,
+                lockout_after: number("LOGIN_KEY_LOCKOUT_AFTER", 10) as u32,
+            },
+            ip_limits: AttemptLimits {
+                free_attempts: number("LOGIN_IP_FREE_ATTEMPTS", 10) as u32,
+                lockout_after: number("LOGIN_IP_LOCKOUT_AFTER", 50) as u32,
+            },
+            backoff_base: Duration::seconds(number("LOGIN_BACKOFF_BASE_SECONDS", 1)),
+            backoff_max: Duration::seconds(number("LOGIN_BACKOFF_MAX_SECONDS", 5 * 60)),
+            lockout: Duration::seconds(number("LOGIN_LOCKOUT_SECONDS", 15 * 60)),
+            window: Duration::seconds(number("LOGIN_ATTEMPT_WINDOW_SECONDS", 60 * 60)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LoginThrottleService<Store> {
+    store: Store,
+    config: LoginThrottleConfig,
+}
+
+#[derive(Error, Debug)]
+pub enum LoginThrottleServiceError {
+    #[error("Too many failed logins, retry after {0} seconds")]
+    Throttled(i64),
+
+    #[error("Attempt store error: `{0}`")]
+    Store(String),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Store> LoginThrottleService<Store>
+where
+    Store: ILoginAttemptStore,
+{
+    pub fn new(store: Store, config: LoginThrottleConfig) -> Self {
+        Self { store, config }
+    }
+
+    /// Rejects the attempt while the key or the address is backed off or locked.
+    pub async fn check(
+        &self,
+        key: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<(), LoginThrottleServiceError> {
+        let now = Utc::now();
+
+        let mut locked_until: Option<DateTime<Utc>> = None;
+
+        for (subject, _) in self.subjects(key, ip) {
+            let attempts = self
+                .store
+                .get(&subject.to_string())
+                .await
+                .map_err(Self::store_error)?;
+
+            if let Some(until) = attempts.and_then(|attempts| attempts.locked_until)
+                && until > now
+            {
+                locked_until = locked_until.max(Some(until));
+            }
+        }
+
+        match locked_until {
+            Some(until) => Err(LoginThrottleServiceError::Throttled(Self::retry_after(
+                until, now,
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Counts a failed login. Returns `Throttled` when the failure started a
+    /// backoff or lockout, so the caller can answer with `Retry-After` right away.
+    pub async fn record_failure(
+        &self,
+        key: &str,
+        ip: Option<IpAddr>,
+    ) -> Result<(), LoginThrottleServiceError> {
+        let now = Utc::now();
+
+        let mut locked_until: Option<DateTime<Utc>> = None;
+
+        for (subject, limits) in self.subjects(key, ip) {
+            let subject = subject.to_string();
+
+            let attempts = self
+                .store
+                .record_failure(&subject, &now, &self.config.window)
+                .await
+                .map_err(Self::store_error)?;
+
+            let Some(until) = self.locked_until(attempts.failures, limits, now) else {
+                continue;
+            };
+
+            self.store
+                .lock(&subject, &until)
+                .await
+                .map_err(Self::store_error)?;
+
+            if attempts.failures >= limits.lockout_after {
+                warn!(
+                    "Locked out {} after {} failed logins until {}",
+                    subject, attempts.failures, until
+                );
+
+                self.store
+                    .insert_lockout(&Lockout::new(subject, attempts.failures, until))
+                    .await
+                    .map_err(Self::store_error)?;
+            }
+
+            locked_until = locked_until.max(Some(until));
+        }
+
+        match locked_until {
+            Some(until) => Err(LoginThrottleServiceError::Throttled(Self::retry_after(
+                until, now,
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Forgets the failures of the key. The address keeps its count, so an
+    /// attacker can't reset it by logging into an account of their own.
+    pub async fn record_success(&self, key: &str) -> Result<(), LoginThrottleServiceError> {
+        self.store
+            .clear(&AttemptSubject::Key(key.to_string()).to_string())
+            .await
+            .map_err(Self::store_error)
+    }
+
+    /// Forgets subjects whose failures are outside the window and whose
+    /// lock has run out, they would start over at one failure anyway.
+    pub async fn purge_stale(&self) -> Result<u64, LoginThrottleServiceError> {
+        self.store
+            .delete_stale(&Utc::now(), &self.config.window)
+            .await
+            .map_err(Self::store_error)
+    }
+
+    /// Runs `purge_stale` forever, every `LOGIN_ATTEMPT_PURGE_INTERVAL_SECONDS`.
+    pub async fn run_purge(&self) {
+        let seconds = std::env::var("LOGIN_ATTEMPT_PURGE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_PURGE_INTERVAL_SECONDS);
+
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(seconds));
+
+        loop {
+            interval.tick().await;
+
+            match self.purge_stale().await {
+                Ok(purged) if purged > 0 => info!("Purged {} stale login attempts", purged),
+                Ok(_) => {}
+                Err(error) => error!("Couldn't purge login attempts: {}", error),
+            }
+        }
+    }
+
+    fn subjects(&self, key: &str, ip: Option<IpAddr>) -> Vec<(AttemptSubject, AttemptLimits)> {
+        let mut subjects = vec![(AttemptSubject::Key(key.to_string()), self.config.key_limits)];
+
+        if let Some(ip) = ip {
+            subjects.push((AttemptSubject::Ip(ip), self.config.ip_limits));
+        }
+
+        subjects
+    }
+
+    fn locked_until(
+        &self,
+        failures: u32,
+        limits: AttemptLimits,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        if failures >= limits.lockout_after {
+            return Some(now + self.config.lockout);
+        }
+
+        if failures <= limits.free_attempts {
+            return None;
+        }
+
+        let exponent = (failures - limits.free_attempts - 1).min(30);
+        let delay = self.config.backoff_base * 2_i32.pow(exponent);
+
+        Some(now + delay.min(self.config.backoff_max))
+    }
+
+    fn retry_after(until: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+        let milliseconds = (until - now).num_milliseconds();
+
+        (milliseconds + 999) / 1000
+    }
+
+    fn store_error(error: Store::Error) -> LoginThrottleServiceError {
+        error!("Login attempt store error: {}", error);
+        LoginThrottleServiceError::Store(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use crate::infrastructure::storage::memory::login_attempt_store::LoginAttemptMemoryStore;
+
+    use super::*;
+
+    const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+    fn config() -> LoginThrottleConfig {
+        LoginThrottleConfig {
+            key_limits: AttemptLimits {
+                free_attempts: 2,
+                lockout_after: 5,
+            },
+            ip_limits: AttemptLimits {
+                free_attempts: 4,
+                lockout_after: 8,
+            },
+            backoff_base: Duration::seconds(1),
+            backoff_max: Duration::seconds(4),
+            lockout: Duration::minutes(15),
+            window: Duration::hours(1),
+        }
+    }
+
+    fn retry_after(res: Result<(), LoginThrottleServiceError>) -> Option<i64> {
+        match res {
+            Err(LoginThrottleServiceError::Throttled(seconds)) => Some(seconds),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exponential_backoff() {
+        let service = LoginThrottleService::new(LoginAttemptMemoryStore::new(), config());
+
+        let mut delays = vec![];
+
+        for _ in 0..4 {
+            delays.push(retry_after(service.record_failure("fsdfsf", None).await));
+        }
+
+        assert_eq!(delays, vec![None, None, Some(1), Some(2)]);
+        assert_eq!(retry_after(service.check("fsdfsf", None).await), Some(2));
+        assert!(service.check("other", None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lockout_is_audited() {
+        let store = LoginAttemptMemoryStore::new();
+        let service = LoginThrottleService::new(store.clone(), config());
+
+        for _ in 0..4 {
+            let _ = service.record_failure("fsdfsf", None).await;
+        }
+
+        assert!(store.lockouts().is_empty());
+
+        let res = service.record_failure("fsdfsf", None).await;
+
+        assert_eq!(retry_after(res), Some(15 * 60));
+
+        let lockouts = store.lockouts();
+
+        assert_eq!(lockouts.len(), 1);
+        assert_eq!(lockouts[0].subject, "key:fsdfsf");
+        assert_eq!(lockouts[0].failures, 5);
+    }
+
+    #[tokio::test]
+    async fn test_ip_throttled_across_keys() {
+        let service = LoginThrottleService::new(LoginAttemptMemoryStore::new(), config());
+
+        for index in 0..5 {
+            let _ = service
+                .record_failure(&format!("key{index}"), Some(IP))
+                .await;
+        }
+
+        assert!(retry_after(service.check("fresh", Some(IP)).await).is_some());
+        assert!(service.check("fresh", None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_success_clears_key_only() {
+        let store = LoginAttemptMemoryStore::new();
+        let service = LoginThrottleService::new(store.clone(), config());
+
+        for _ in 0..3 {
+            let _ = service.record_failure("fsdfsf", Some(IP)).await;
+        }
+
+        service.record_success("fsdfsf").await.unwrap();
+
+        assert!(store.get("key:fsdfsf").await.unwrap().is_none());
+        assert_eq!(store.get("ip:10.0.0.1").await.unwrap().unwrap().failures, 3);
+    }
+
+    #[tokio::test]
+    async fn test_purge_stale() {
+        let store = LoginAttemptMemoryStore::new();
+        let mut throttle_config = config();
+        throttle_config.window = Duration::zero();
+
+        let service = LoginThrottleService::new(store.clone(), throttle_config);
+
+        let _ = service.record_failure("stale", None).await;
+
+        let _ = service.record_failure("locked", None).await;
+        store
+            .lock("key:locked", &(Utc::now() + Duration::minutes(1)))
+            .await
+            .unwrap();
+
+        assert_eq!(service.purge_stale().await.unwrap(), 1);
+        assert!(store.get("key:stale").await.unwrap().is_none());
+        assert!(store.get("key:locked").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failures_outside_window_are_forgotten() {
+        let store = LoginAttemptMemoryStore::new();
+        let mut throttle_config = config();
+        throttle_config.window = Duration::zero();
+
+        let service = LoginThrottleService::new(store.clone(), throttle_config);
+
+        for _ in 0..4 {
+            let _ = service.record_failure("fsdfsf", None).await;
+        }
+
+        assert_eq!(store.get("key:fsdfsf").await.unwrap().unwrap().failures, 1);
+    }
+}
@@ -1,4 +1,13 @@
+pub mod api_key_service;
 pub mod auth_service;
+pub mod login_throttle_service;
+pub mod password_reset_service;
+pub mod quiz_service;
+pub mod review_service;
+pub mod revocation_service;
+pub mod session_service;
+pub mod stats_service;
+pub mod token_service;
 pub mod translate_service;
 pub mod user_service;
 pub mod word_pair_service;
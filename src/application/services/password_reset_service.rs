@@ -0,0 +1,380 @@
+use chrono::{Duration, Utc};
+use thiserror::Error;
+use tracing::error;
+
+use crate::{
+    application::services::user_service::{UserService, UserServiceError},
+    domain::{
+        models::{
+            password_policy::check_password_strength, password_reset_token::PasswordResetToken,
+            user::User,
+        },
+        traits::repositories::{
+            password_reset_token_repository::IPasswordResetTokenRepository,
+            user_repository::IUserRepository,
+        },
+        types::ID,
+    },
+    infrastructure::utils::secret_token::{generate_token, hash_token},
+};
+
+const DEFAULT_RESET_TTL_SECONDS: i64 = 60 * 60;
+
+#[derive(Clone)]
+pub struct PasswordResetService<Repo, UserRepo> {
+    repo: Repo,
+    user_service: UserService<UserRepo>,
+    ttl: Duration,
+}
+
+#[derive(Error, Debug)]
+pub enum PasswordResetServiceError {
+    #[error("Invalid or expired reset token")]
+    InvalidToken,
+
+    #[error("User error: `{0}`")]
+    User(#[from] UserServiceError),
+
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Repo, UserRepo> PasswordResetService<Repo, UserRepo>
+where
+    Repo: IPasswordResetTokenRepository<Error = sqlx::Error>,
+    UserRepo: IUserRepository<Error = sqlx::Error>,
+{
+    pub fn new(repo: Repo, user_service: UserService<UserRepo>) -> Self {
+        let ttl = Duration::seconds(
+            std::env::var("PASSWORD_RESET_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_RESET_TTL_SECONDS),
+        );
+
+        Self {
+            repo,
+            user_service,
+            ttl,
+        }
+    }
+
+    /// Returns the plain token, which is not stored anywhere, with its record.
+    pub async fn issue(
+        &self,
+        user_id: &ID,
+    ) -> Result<(String, PasswordResetToken), PasswordResetServiceError> {
+        let user = self.user_service.get_by_id(user_id).await?;
+
+        let token = generate_token::<32>();
+
+        let reset_token =
+            PasswordResetToken::new(user.id, hash_token(&token), Utc::now() + self.ttl);
+
+        let res = self.repo.insert(&reset_token).await.map_err(|error| {
+            error!("Password reset DB error: {}", error);
+            error
+        })?;
+
+        Ok((token, res))
+    }
+
+    pub async fn issue_by_key(
+        &self,
+        key: &str,
+    ) -> Result<(String, PasswordResetToken), PasswordResetServiceError> {
+        let user = self.user_service.get_by_key(key).await?;
+
+        self.issue(&user.id).await
+    }
+
+    /// Sets the new password and uses the token up. A password that breaks
+    /// the strength rules is rejected before the token is spent.
+    pub async fn reset(
+        &self,
+        token: &str,
+        new_password: &str,
+    ) -> Result<User, PasswordResetServiceError> {
+        let reset_token = self
+            .repo
+            .select_by_hash(&hash_token(token))
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => PasswordResetServiceError::InvalidToken,
+                _ => {
+                    error!("Password reset DB error: {}", error);
+                    PasswordResetServiceError::Database(error)
+                }
+            })?;
+
+        let user = self.user_service.get_by_id(&reset_token.user_id).await?;
+
+        check_password_strength(new_password, &user.key)
+            .map_err(UserServiceError::InvalidPassword)?;
+
+        self.repo
+            .mark_used(&reset_token.id, &Utc::now())
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => PasswordResetServiceError::InvalidToken,
+                _ => {
+                    error!("Password reset DB error: {}", error);
+                    PasswordResetServiceError::Database(error)
+                }
+            })?;
+
+        let res = self
+            .user_service
+            .reset_password(&user.id, new_password)
+            .await?;
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{
+            models::role::Role,
+            traits::{crypto::crypto::ICrypto, repositories::repository::Repository},
+        },
+        infrastructure::{
+            storage::database::models::{
+                password_reset_token::PasswordResetTokenEntity, user::UserEntity,
+            },
+            utils::password::Argon2Crypto,
+        },
+    };
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct TestPasswordResetTokenRepository {
+        tokens: Arc<Mutex<Vec<PasswordResetToken>>>,
+    }
+
+    #[async_trait]
+    impl Repository for TestPasswordResetTokenRepository {
+        type Pool = ();
+        type Item = PasswordResetToken;
+        type Entity = PasswordResetTokenEntity;
+        type Error = sqlx::Error;
+
+        fn new(_db: Self::Pool) -> Self {
+            Self::default()
+        }
+
+        async fn insert(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            self.tokens.lock().unwrap().push(item.clone());
+
+            Ok(item.clone())
+        }
+
+        async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.id == *id)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+            self.tokens.lock().unwrap().retain(|token| token.id != *id);
+
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl IPasswordResetTokenRepository for TestPasswordResetTokenRepository {
+        async fn select_by_hash(&self, token_hash: &str) -> Result<Self::Item, Self::Error> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.token_hash == token_hash)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn mark_used(
+            &self,
+            id: &ID,
+            used_at: &DateTime<Utc>,
+        ) -> Result<Self::Item, Self::Error> {
+            let mut tokens = self.tokens.lock().unwrap();
+
+            let token = tokens
+                .iter_mut()
+                .find(|token| token.id == *id && token.used_at.is_none())
+                .filter(|token| token.expires_at > *used_at)
+                .ok_or(sqlx::Error::RowNotFound)?;
+
+            token.used_at = Some(*used_at);
+
+            Ok(token.clone())
+        }
+    }
+
+    #[derive(Clone)]
+    struct TestUserRepository;
+
+    #[async_trait]
+    impl Repository for TestUserRepository {
+        type Pool = ();
+        type Item = User;
+        type Entity = UserEntity;
+        type Error = sqlx::Error;
+
+        fn new(_db: Self::Pool) -> Self {
+            Self
+        }
+
+        async fn insert(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            Ok(item.clone())
+        }
+
+        async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+            Ok(User {
+                id: *id,
+                key: "faksfjas".to_string(),
+                name: "Mdafasdfd".to_string(),
+                hashed_password: Argon2Crypto::default().hash("hefkajdf").unwrap(),
+                role: Role::User,
+                disabled_at: None,
+                delete_after: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+        }
+
+        async fn delete_by_id(&self, _id: &ID) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl IUserRepository for TestUserRepository {
+        async fn select_by_key(&self, _key: &str) -> Result<Self::Item, Self::Error> {
+            self.select_by_id(&Uuid::new_v4()).await
+        }
+
+        async fn update_by_id(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            Ok(item.clone())
+        }
+
+        async fn select_page(
+            &self,
+            _search: Option<&str>,
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            Ok(vec![])
+        }
+
+        async fn count(&self, _search: Option<&str>) -> Result<i64, Self::Error> {
+            Ok(0)
+        }
+
+        async fn update_disabled(
+            &self,
+            id: &ID,
+            _disabled_at: Option<&DateTime<Utc>>,
+        ) -> Result<Self::Item, Self::Error> {
+            self.select_by_id(id).await
+        }
+
+        async fn update_role(&self, id: &ID, _role: Role) -> Result<Self::Item, Self::Error> {
+            self.select_by_id(id).await
+        }
+
+        async fn update_delete_after(
+            &self,
+            id: &ID,
+            _delete_after: Option<&DateTime<Utc>>,
+        ) -> Result<Self::Item, Self::Error> {
+            self.select_by_id(id).await
+        }
+
+        async fn select_inactive_ids(&self) -> Result<Vec<ID>, Self::Error> {
+            Ok(vec![])
+        }
+
+        async fn delete_due(&self, _now: &DateTime<Utc>) -> Result<Vec<ID>, Self::Error> {
+            Ok(vec![])
+        }
+    }
+
+    fn password_reset_service()
+    -> PasswordResetService<TestPasswordResetTokenRepository, TestUserRepository> {
+        PasswordResetService::new(
+            TestPasswordResetTokenRepository::default(),
+            UserService::new(TestUserRepository, Argon2Crypto::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reset_once() {
+        let service = password_reset_service();
+
+        let (token, reset_token) = service.issue(&Uuid::new_v4()).await.unwrap();
+
+        assert_ne!(token, reset_token.token_hash);
+
+        let user = service.reset(&token, "new-password-1").await.unwrap();
+
+        assert!(
+            Argon2Crypto::default()
+                .verify("new-password-1", &user.hashed_password)
+                .is_ok()
+        );
+
+        let reused = service.reset(&token, "new-password-2").await;
+
+        assert!(matches!(
+            reused,
+            Err(PasswordResetServiceError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reset_weak_password_keeps_token() {
+        let service = password_reset_service();
+
+        let (token, _) = service.issue(&Uuid::new_v4()).await.unwrap();
+
+        let weak = service.reset(&token, "12345678").await;
+
+        assert!(matches!(
+            weak,
+            Err(PasswordResetServiceError::User(
+                UserServiceError::InvalidPassword(_)
+            ))
+        ));
+        assert!(service.reset(&token, "new-password-1").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reset_expired_token() {
+        let mut service = password_reset_service();
+        service.ttl = Duration::seconds(-1);
+
+        let (token, _) = service.issue(&Uuid::new_v4()).await.unwrap();
+
+        let res = service.reset(&token, "new-password-1").await;
+
+        assert!(matches!(res, Err(PasswordResetServiceError::InvalidToken)));
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::Utc;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use thiserror::Error;
+use tracing::error;
+
+use crate::{
+    domain::{
+        models::{
+            quiz::{Quiz, QuizAnswer, QuizQuestion, QuizResult},
+            word_pair::{WordPair, WordPairFilter},
+        },
+        traits::repositories::quiz_repository::IQuizRepository,
+        types::ID,
+    },
+    infrastructure::utils::quiz::distractors::pick_distractors,
+};
+
+/// Word pairs considered for the questions of one quiz, and for the
+/// distractors of each target language.
+const MAX_QUIZ_WORD_PAIRS: i64 = 5000;
+
+#[derive(Clone)]
+pub struct QuizService<Repo> {
+    repo: Repo,
+}
+
+#[derive(Debug, Error)]
+pub enum QuizServiceError {
+    #[error("Quiz not found: `{0}`")]
+    NotFound(String),
+
+    #[error("Not enough word pairs for a quiz")]
+    NotEnoughWordPairs,
+
+    #[error("Quiz already graded")]
+    AlreadyGraded,
+
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Repo> QuizService<Repo>
+where
+    Repo: IQuizRepository<Error = sqlx::Error>,
+{
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    /// Draws up to `questions` word pairs matching `filter`, each with
+    /// `options` choices. The wrong choices are target texts of other word
+    /// pairs in the same target language, whether they match `filter` or
+    /// not. Word pairs without enough of them are skipped. Without a `seed`
+    /// a random one is picked.
+    pub async fn create(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        questions: usize,
+        options: usize,
+        seed: Option<u64>,
+    ) -> Result<Quiz, QuizServiceError> {
+        let seed = seed.unwrap_or_else(rand::random);
+
+        let candidates = self
+            .repo
+            .select_word_pairs(user_id, filter, MAX_QUIZ_WORD_PAIRS)
+            .await
+            .map_err(|error| {
+                error!("Quiz DB error: {}", error);
+                error
+            })?;
+
+        let target_languages: BTreeSet<&str> = candidates
+            .iter()
+            .map(|word_pair| word_pair.target_language.as_str())
+            .collect();
+
+        let mut pools = HashMap::with_capacity(target_languages.len());
+
+        for target_language in target_languages {
+            let pool = self
+                .repo
+                .select_by_target_language(user_id, target_language, MAX_QUIZ_WORD_PAIRS)
+                .await
+                .map_err(|error| {
+                    error!("Quiz DB error: {}", error);
+                    error
+                })?;
+
+            pools.insert(target_language.to_string(), pool);
+        }
+
+        let questions = build_questions(
+            &candidates,
+            &pools,
+            questions,
+            options,
+            &mut StdRng::seed_from_u64(seed),
+        );
+
+        if questions.is_empty() {
+            return Err(QuizServiceError::NotEnoughWordPairs);
+        }
+
+        let quiz = Quiz::new(
+            *user_id,
+            seed,
+            filter.source_language.clone(),
+            filter.target_language.clone(),
+            questions,
+        );
+
+        let res = self.repo.insert(&quiz).await.map_err(|error| {
+            error!("Quiz DB error: {}", error);
+            error
+        })?;
+
+        Ok(res)
+    }
+
+    pub async fn get_by_id(&self, id: &ID) -> Result<Quiz, QuizServiceError> {
+        let res = self
+            .repo
+            .select_by_id(id)
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => QuizServiceError::NotFound(error.to_string()),
+                _ => {
+                    error!("Quiz DB error: {}", error);
+                    QuizServiceError::Database(error)
+                }
+            })?;
+
+        Ok(res)
+    }
+
+    pub async fn get_by_user_id(&self, user_id: &ID) -> Result<Vec<Quiz>, QuizServiceError> {
+        let res = self
+            .repo
+            .select_by_user_id(user_id)
+            .await
+            .map_err(|error| {
+                error!("Quiz DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Grades and stores the picked options. A quiz is graded once, later
+    /// submissions fail with `AlreadyGraded`.
+    pub async fn grade(
+        &self,
+        quiz: &Quiz,
+        answers: &[QuizAnswer],
+    ) -> Result<QuizResult, QuizServiceError> {
+        if quiz.is_graded() {
+            return Err(QuizServiceError::AlreadyGraded);
+        }
+
+        let mut quiz = quiz.clone();
+        quiz.submit(answers, Utc::now());
+
+        let res = self.repo.submit(&quiz).await.map_err(|error| match error {
+            sqlx::Error::RowNotFound => QuizServiceError::AlreadyGraded,
+            _ => {
+                error!("Quiz DB error: {}", error);
+                QuizServiceError::Database(error)
+            }
+        })?;
+
+        res.result().ok_or(QuizServiceError::Unknown)
+    }
+}
+
+/// Draws the questions from `candidates`, with the distractors of each from
+/// the `pools` by target language.
+fn build_questions<R: Rng>(
+    candidates: &[WordPair],
+    pools: &HashMap<String, Vec<WordPair>>,
+    count: usize,
+    options: usize,
+    rng: &mut R,
+) -> Vec<QuizQuestion> {
+    let mut candidates: Vec<&WordPair> = candidates.iter().collect();
+
+    candidates.shuffle(rng);
+
+    let mut questions = Vec::with_capacity(count);
+
+    for word_pair in candidates {
+        if questions.len() == count {
+            break;
+        }
+
+        let pool: Vec<&str> = pools
+            .get(&word_pair.target_language)
+            .into_iter()
+            .flatten()
+            .filter(|other| other.id != word_pair.id)
+            .map(|other| other.target_text.as_str())
+            .collect();
+
+        let mut choices = pick_distractors(&word_pair.target_text, &pool, options - 1, rng);
+
+        if choices.len() < options - 1 {
+            continue;
+        }
+
+        let answer_index = rng.random_range(0..options);
+        choices.insert(answer_index, word_pair.target_text.clone());
+
+        questions.push(QuizQuestion {
+            position: questions.len() as i32,
+            word_pair_id: word_pair.id,
+            prompt: word_pair.source_text.clone(),
+            options: choices,
+            answer_index: answer_index as i32,
+            selected: None,
+        });
+    }
+
+    questions
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        domain::models::quiz::QuizAnswer,
+        infrastructure::storage::memory::quiz_repository::QuizMemoryRepository,
+    };
+
+    use super::*;
+
+    fn add_word_pairs(repo: &QuizMemoryRepository, user_id: ID, target_language: &str) {
+        for (target_text, source_text) in [
+            ("Hund", "dog"),
+            ("Katze", "cat"),
+            ("Maus", "mouse"),
+            ("Haus", "house"),
+            ("Baum", "tree"),
+        ] {
+            repo.add_word_pair(&WordPair::new_simple(
+                user_id,
+                target_text.to_string(),
+                source_text.to_string(),
+                target_language.to_string(),
+                "en".to_string(),
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_same_seed_same_quiz() {
+        let repo = QuizMemoryRepository::default();
+        let quiz_service = QuizService::new(repo.clone());
+
+        let user_id = Uuid::new_v4();
+        add_word_pairs(&repo, user_id, "de");
+
+        let first = quiz_service
+            .create(&user_id, &WordPairFilter::default(), 5, 4, Some(42))
+            .await
+            .unwrap();
+        let second = quiz_service
+            .create(&user_id, &WordPairFilter::default(), 5, 4, Some(42))
+            .await
+            .unwrap();
+
+        assert_eq!(first.seed, 42);
+        assert_eq!(first.questions, second.questions);
+        assert_eq!(first.questions.len(), 5);
+
+        for question in &first.questions {
+            assert_eq!(question.options.len(), 4);
+            assert!(question.answer_index < 4);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_distractors_from_same_target_language() {
+        let repo = QuizMemoryRepository::default();
+        let quiz_service = QuizService::new(repo.clone());
+
+        let user_id = Uuid::new_v4();
+        add_word_pairs(&repo, user_id, "de");
+        repo.add_word_pair(&WordPair::new_simple(
+            user_id,
+            "Chien".to_string(),
+            "dog".to_string(),
+            "fr".to_string(),
+            "en".to_string(),
+        ));
+
+        let quiz = quiz_service
+            .create(&user_id, &WordPairFilter::default(), 10, 3, Some(1))
+            .await
+            .unwrap();
+
+        // The French word pair has no other French word pairs to mix in.
+        assert_eq!(quiz.questions.len(), 5);
+        assert!(
+            quiz.questions
+                .iter()
+                .all(|question| !question.options.contains(&"Chien".to_string()))
+        );
+
+        let res = quiz_service
+            .create(
+                &user_id,
+                &WordPairFilter {
+                    target_language: Some("fr".to_string()),
+                    ..WordPairFilter::default()
+                },
+                10,
+                3,
+                None,
+            )
+            .await;
+
+        assert!(matches!(res, Err(QuizServiceError::NotEnoughWordPairs)));
+    }
+
+    #[tokio::test]
+    async fn test_filter_before_limit() {
+        let repo = QuizMemoryRepository::default();
+        let quiz_service = QuizService::new(repo.clone());
+
+        let user_id = Uuid::new_v4();
+
+        for index in 0..MAX_QUIZ_WORD_PAIRS {
+            repo.add_word_pair(&WordPair::new_simple(
+                user_id,
+                format!("Wort {index}"),
+                format!("word {index}"),
+                "de".to_string(),
+                "en".to_string(),
+            ));
+        }
+
+        add_word_pairs(&repo, user_id, "fr");
+
+        let quiz = quiz_service
+            .create(
+                &user_id,
+                &WordPairFilter {
+                    target_language: Some("fr".to_string()),
+                    ..WordPairFilter::default()
+                },
+                5,
+                4,
+                Some(5),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(quiz.questions.len(), 5);
+        assert!(
+            quiz.questions
+                .iter()
+                .flat_map(|question| &question.options)
+                .all(|option| !option.starts_with("Wort"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_grade() {
+        let repo = QuizMemoryRepository::default();
+        let quiz_service = QuizService::new(repo.clone());
+
+        let user_id = Uuid::new_v4();
+        add_word_pairs(&repo, user_id, "de");
+
+        let quiz = quiz_service
+            .create(&user_id, &WordPairFilter::default(), 3, 2, Some(7))
+            .await
+            .unwrap();
+
+        let quiz = quiz_service.get_by_id(&quiz.id).await.unwrap();
+
+        let answers = [
+            QuizAnswer {
+                position: 0,
+                option: quiz.questions[0].answer_index,
+            },
+            QuizAnswer {
+                position: 1,
+                option: 1 - quiz.questions[1].answer_index,
+            },
+        ];
+
+        let res = quiz_service.grade(&quiz, &answers).await.unwrap();
+
+        assert_eq!(res.questions, 3);
+        assert_eq!(res.answered, 2);
+        assert_eq!(res.correct, 1);
+        assert!(res.answers[0].is_correct);
+        assert!(!res.answers[1].is_correct);
+        assert_eq!(res.answers[2].selected, None);
+
+        let quiz = quiz_service.get_by_id(&quiz.id).await.unwrap();
+
+        assert!(quiz.is_graded());
+        assert_eq!(quiz.result(), Some(res));
+    }
+
+    #[tokio::test]
+    async fn test_grade_once() {
+        let repo = QuizMemoryRepository::default();
+        let quiz_service = QuizService::new(repo.clone());
+
+        let user_id = Uuid::new_v4();
+        add_word_pairs(&repo, user_id, "de");
+
+        let quiz = quiz_service
+            .create(&user_id, &WordPairFilter::default(), 2, 2, Some(3))
+            .await
+            .unwrap();
+
+        let answers = [QuizAnswer {
+            position: 0,
+            option: quiz.questions[0].answer_index,
+        }];
+
+        quiz_service.grade(&quiz, &[]).await.unwrap();
+
+        // A stale copy of the quiz is caught by the repository.
+        let res = quiz_service.grade(&quiz, &answers).await;
+        assert!(matches!(res, Err(QuizServiceError::AlreadyGraded)));
+
+        let quiz = quiz_service.get_by_id(&quiz.id).await.unwrap();
+        let res = quiz_service.grade(&quiz, &answers).await;
+        assert!(matches!(res, Err(QuizServiceError::AlreadyGraded)));
+
+        assert_eq!(quiz.result().unwrap().answered, 0);
+    }
+}
@@ -0,0 +1,350 @@
+use chrono::Utc;
+use thiserror::Error;
+use tracing::error;
+
+use crate::domain::{
+    models::{
+        review::{DueCard, Grade, ReviewLog, ReviewState},
+        word_pair::{WordPair, WordPairFilter},
+    },
+    traits::{repositories::review_repository::IReviewRepository, review::scheduler::IScheduler},
+    types::ID,
+};
+
+#[derive(Clone)]
+pub struct ReviewService<Repo, Scheduler> {
+    repo: Repo,
+    scheduler: Scheduler,
+}
+
+#[derive(Debug, Error)]
+pub enum ReviewServiceError {
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Repo, Scheduler> ReviewService<Repo, Scheduler>
+where
+    Repo: IReviewRepository<Error = sqlx::Error>,
+    Scheduler: IScheduler,
+{
+    pub fn new(repo: Repo, scheduler: Scheduler) -> Self {
+        Self { repo, scheduler }
+    }
+
+    /// The user's cards that are due now, most overdue first. New word
+    /// pairs aren't due until they were reviewed once.
+    pub async fn get_due(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<DueCard>, ReviewServiceError> {
+        let res = self
+            .repo
+            .select_due(user_id, filter, &Utc::now(), limit)
+            .await
+            .map_err(|error| {
+                error!("Review DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// The user's word pairs that have never been reviewed, oldest first.
+    pub async fn get_new(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, ReviewServiceError> {
+        let res = self
+            .repo
+            .select_new(user_id, filter, limit)
+            .await
+            .map_err(|error| {
+                error!("Review DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// The review state of the word pair, `None` if it was never reviewed.
+    pub async fn get_state(
+        &self,
+        word_pair_id: &ID,
+    ) -> Result<Option<ReviewState>, ReviewServiceError> {
+        match self.repo.select_by_id(word_pair_id).await {
+            Ok(state) => Ok(Some(state)),
+            Err(sqlx::Error::RowNotFound) => Ok(None),
+            Err(error) => {
+                error!("Review DB error: {}", error);
+                Err(ReviewServiceError::Database(error))
+            }
+        }
+    }
+
+    /// All of the user's review states, by due date.
+    pub async fn get_by_user_id(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<ReviewState>, ReviewServiceError> {
+        let res = self
+            .repo
+            .select_by_user_id(user_id)
+            .await
+            .map_err(|error| {
+                error!("Review DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Every answer the user gave, oldest first.
+    pub async fn get_logs_by_user_id(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<ReviewLog>, ReviewServiceError> {
+        let res = self
+            .repo
+            .select_logs_by_user_id(user_id)
+            .await
+            .map_err(|error| {
+                error!("Review DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Schedules the next review of the word pair after it was answered
+    /// with `grade`. The first review of a word pair starts its schedule.
+    pub async fn grade(
+        &self,
+        word_pair_id: &ID,
+        user_id: &ID,
+        grade: Grade,
+    ) -> Result<ReviewState, ReviewServiceError> {
+        let now = Utc::now();
+
+        let state = self
+            .get_state(word_pair_id)
+            .await?
+            .unwrap_or_else(|| ReviewState::new(*word_pair_id, *user_id, now));
+
+        let next = self.scheduler.schedule(&state, grade, &now);
+
+        let res = self.repo.upsert(&next).await.map_err(|error| {
+            error!("Review DB error: {}", error);
+            error
+        })?;
+
+        self.repo
+            .insert_log(&ReviewLog::new(&state, &res, grade))
+            .await
+            .map_err(|error| {
+                error!("Review DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    use crate::{
+        domain::traits::repositories::repository::Repository,
+        infrastructure::{
+            scheduler::{Scheduler, fsrs::FsrsScheduler},
+            storage::memory::review_repository::ReviewMemoryRepository,
+        },
+    };
+
+    use super::*;
+
+    fn word_pair(user_id: ID) -> WordPair {
+        WordPair::new_simple(
+            user_id,
+            "Hallo".to_string(),
+            "Hello".to_string(),
+            "de".to_string(),
+            "en".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_first_review_starts_schedule() {
+        let repo = ReviewMemoryRepository::default();
+        let review_service = ReviewService::new(repo.clone(), Scheduler::default());
+
+        let word_pair = word_pair(Uuid::new_v4());
+        repo.add_word_pair(&word_pair);
+
+        let res = review_service
+            .grade(&word_pair.id, &word_pair.user_id, Grade::Good)
+            .await
+            .unwrap();
+
+        assert_eq!(res.interval_days, 1);
+        assert_eq!(res.repetitions, 1);
+        assert!(res.due_at > Utc::now());
+
+        let res = review_service
+            .get_due(&word_pair.user_id, &WordPairFilter::default(), 10)
+            .await
+            .unwrap();
+
+        assert!(res.is_empty());
+        assert_eq!(repo.logs().len(), 1);
+        assert_eq!(repo.logs()[0].grade, Grade::Good);
+    }
+
+    #[tokio::test]
+    async fn test_states_and_logs_by_user() {
+        let repo = ReviewMemoryRepository::default();
+        let review_service = ReviewService::new(repo.clone(), Scheduler::default());
+
+        let other = word_pair(Uuid::new_v4());
+        let word_pair = word_pair(Uuid::new_v4());
+
+        for grade in [Grade::Good, Grade::Again] {
+            review_service
+                .grade(&word_pair.id, &word_pair.user_id, grade)
+                .await
+                .unwrap();
+        }
+
+        review_service
+            .grade(&other.id, &other.user_id, Grade::Easy)
+            .await
+            .unwrap();
+
+        let states = review_service
+            .get_by_user_id(&word_pair.user_id)
+            .await
+            .unwrap();
+
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].word_pair_id, word_pair.id);
+
+        let logs = review_service
+            .get_logs_by_user_id(&word_pair.user_id)
+            .await
+            .unwrap();
+
+        assert_eq!(logs.len(), 2);
+        assert!(logs.iter().all(|log| log.word_pair_id == word_pair.id));
+    }
+
+    #[tokio::test]
+    async fn test_due_cards() {
+        let repo = ReviewMemoryRepository::default();
+        let review_service = ReviewService::new(repo.clone(), Scheduler::default());
+
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        for days_overdue in [1, 3, -2] {
+            let word_pair = word_pair(user_id);
+            repo.add_word_pair(&word_pair);
+
+            let mut state = ReviewState::new(word_pair.id, user_id, now);
+            state.due_at = now - Duration::days(days_overdue);
+            repo.insert(&state).await.unwrap();
+        }
+
+        let mut trashed = word_pair(user_id);
+        trashed.deleted_at = Some(now);
+        repo.add_word_pair(&trashed);
+        repo.insert(&ReviewState::new(trashed.id, user_id, now))
+            .await
+            .unwrap();
+
+        let res = review_service
+            .get_due(&user_id, &WordPairFilter::default(), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].review.due_at, now - Duration::days(3));
+
+        let res = review_service
+            .get_due(&user_id, &WordPairFilter::default(), 1)
+            .await
+            .unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert!(
+            review_service
+                .get_due(&Uuid::new_v4(), &WordPairFilter::default(), 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lapse_with_fsrs() {
+        let repo = ReviewMemoryRepository::default();
+        let review_service =
+            ReviewService::new(repo.clone(), Scheduler::Fsrs(FsrsScheduler::default()));
+
+        let word_pair = word_pair(Uuid::new_v4());
+
+        review_service
+            .grade(&word_pair.id, &word_pair.user_id, Grade::Easy)
+            .await
+            .unwrap();
+        let res = review_service
+            .grade(&word_pair.id, &word_pair.user_id, Grade::Again)
+            .await
+            .unwrap();
+
+        assert_eq!(res.lapses, 1);
+        assert!(res.interval_days < 14);
+        assert!(res.stability.is_some());
+        assert_eq!(repo.logs()[1].previous_interval_days, 14);
+    }
+
+    #[tokio::test]
+    async fn test_new_cards() {
+        let repo = ReviewMemoryRepository::default();
+        let review_service = ReviewService::new(repo.clone(), Scheduler::default());
+
+        let user_id = Uuid::new_v4();
+
+        let reviewed = word_pair(user_id);
+        repo.add_word_pair(&reviewed);
+        review_service
+            .grade(&reviewed.id, &user_id, Grade::Good)
+            .await
+            .unwrap();
+
+        let new = word_pair(user_id);
+        repo.add_word_pair(&new);
+
+        let mut other_language = word_pair(user_id);
+        other_language.target_language = "fr".to_string();
+        repo.add_word_pair(&other_language);
+
+        let filter = WordPairFilter {
+            target_language: Some("de".to_string()),
+            ..WordPairFilter::default()
+        };
+
+        let res = review_service.get_new(&user_id, &filter, 10).await.unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, new.id);
+    }
+}
@@ -0,0 +1,286 @@
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use tracing::{error, info};
+
+use crate::{
+    api::auth::models::Claims,
+    domain::{
+        models::revocation::{RevokedToken, UserRevocation},
+        traits::repositories::revocation_repository::IRevocationRepository,
+        types::ID,
+    },
+    infrastructure::auth::revocation_cache::RevocationCache,
+};
+
+const DEFAULT_PURGE_INTERVAL_SECONDS: u64 = 5 * 60;
+
+#[derive(Clone)]
+pub struct RevocationService<Repo> {
+    repo: Repo,
+    cache: RevocationCache,
+}
+
+#[derive(Error, Debug)]
+pub enum RevocationServiceError {
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Repo> RevocationService<Repo>
+where
+    Repo: IRevocationRepository<Error = sqlx::Error>,
+{
+    pub fn new(repo: Repo) -> Self {
+        Self {
+            repo,
+            cache: RevocationCache::new(),
+        }
+    }
+
+    /// Replaces the cache with the current contents of the store.
+    pub async fn load(&self) -> Result<(), RevocationServiceError> {
+        let tokens = self
+            .repo
+            .select_active(&Utc::now())
+            .await
+            .map_err(Self::database_error)?;
+
+        let users = self
+            .repo
+            .select_user_revocations()
+            .await
+            .map_err(Self::database_error)?;
+
+        self.cache.replace(&tokens, &users).await;
+
+        Ok(())
+    }
+
+    /// Only consults the cache, the store is written through on every revocation.
+    pub async fn is_revoked(&self, claims: &Claims) -> bool {
+        self.cache.is_revoked(claims).await
+    }
+
+    pub async fn revoke_token(&self, claims: &Claims) -> Result<(), RevocationServiceError> {
+        self.revoke(&RevokedToken::new(claims.jti, claims.sub, claims.exp))
+            .await
+    }
+
+    pub async fn revoke(&self, token: &RevokedToken) -> Result<(), RevocationServiceError> {
+        let token = self
+            .repo
+            .insert(token)
+            .await
+            .map_err(Self::database_error)?;
+
+        self.cache.revoke_token(&token).await;
+
+        Ok(())
+    }
+
+    pub async fn revoke_user_tokens(
+        &self,
+        user_id: &ID,
+        issued_before: DateTime<Utc>,
+    ) -> Result<UserRevocation, RevocationServiceError> {
+        let revocation = UserRevocation::new(*user_id, issued_before);
+
+        let revocation = self
+            .repo
+            .upsert_user_revocation(&revocation)
+            .await
+            .map_err(Self::database_error)?;
+
+        self.cache.revoke_user(&revocation).await;
+
+        Ok(revocation)
+    }
+
+    /// Drops entries past their `exp` from the store and reloads the cache,
+    /// which also picks up revocations made by other instances.
+    pub async fn purge_expired(&self) -> Result<u64, RevocationServiceError> {
+        let now = Utc::now();
+
+        let purged = self
+            .repo
+            .delete_expired(&now)
+            .await
+            .map_err(Self::database_error)?;
+
+        self.cache.purge_expired(&now).await;
+        self.load().await?;
+
+        Ok(purged)
+    }
+
+    /// Runs `purge_expired` forever, every `REVOCATION_PURGE_INTERVAL_SECONDS`.
+    pub async fn run_purge(&self) {
+        let seconds = std::env::var("REVOCATION_PURGE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_PURGE_INTERVAL_SECONDS);
+
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(seconds));
+
+        loop {
+            interval.tick().await;
+
+            match self.purge_expired().await {
+                Ok(purged) if purged > 0 => info!("Purged {} expired revocations", purged),
+                Ok(_) => {}
+                Err(error) => error!("Couldn't purge revocations: {}", error),
+            }
+        }
+    }
+
+    fn database_error(error: sqlx::Error) -> RevocationServiceError {
+        error!("Revocation DB error: {}", error);
+        RevocationServiceError::Database(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{models::role::Role, traits::repositories::repository::Repository},
+        infrastructure::storage::database::models::revocation::RevokedTokenEntity,
+    };
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct TestRevocationRepository {
+        tokens: Arc<Mutex<Vec<RevokedToken>>>,
+        users: Arc<Mutex<Vec<UserRevocation>>>,
+    }
+
+    #[async_trait]
+    impl Repository for TestRevocationRepository {
+        type Pool = ();
+        type Item = RevokedToken;
+        type Entity = RevokedTokenEntity;
+        type Error = sqlx::Error;
+
+        fn new(_db: Self::Pool) -> Self {
+            Self::default()
+        }
+
+        async fn insert(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            self.tokens.lock().unwrap().push(item.clone());
+
+            Ok(item.clone())
+        }
+
+        async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.jti == *id)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+            self.tokens.lock().unwrap().retain(|token| token.jti != *id);
+
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl IRevocationRepository for TestRevocationRepository {
+        async fn select_active(&self, now: &DateTime<Utc>) -> Result<Vec<Self::Item>, Self::Error> {
+            Ok(self
+                .tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|token| token.expires_at > *now)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete_expired(&self, now: &DateTime<Utc>) -> Result<u64, Self::Error> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let before = tokens.len();
+
+            tokens.retain(|token| token.expires_at > *now);
+
+            Ok((before - tokens.len()) as u64)
+        }
+
+        async fn upsert_user_revocation(
+            &self,
+            revocation: &UserRevocation,
+        ) -> Result<UserRevocation, Self::Error> {
+            self.users.lock().unwrap().push(revocation.clone());
+
+            Ok(revocation.clone())
+        }
+
+        async fn select_user_revocations(&self) -> Result<Vec<UserRevocation>, Self::Error> {
+            Ok(self.users.lock().unwrap().clone())
+        }
+    }
+
+    fn claims(iat: DateTime<Utc>) -> Claims {
+        Claims::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            iat,
+            iat + Duration::minutes(5),
+            Role::User,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_revocations_survive_reload() {
+        let repo = TestRevocationRepository::default();
+        let service = RevocationService::new(repo.clone());
+
+        let logged_out = claims(Utc::now());
+        let force_logged_out = claims(Utc::now() - Duration::minutes(1));
+
+        service.revoke_token(&logged_out).await.unwrap();
+        service
+            .revoke_user_tokens(&force_logged_out.sub, Utc::now())
+            .await
+            .unwrap();
+
+        let restarted = RevocationService::new(repo);
+
+        assert!(!restarted.is_revoked(&logged_out).await);
+
+        restarted.load().await.unwrap();
+
+        assert!(restarted.is_revoked(&logged_out).await);
+        assert!(restarted.is_revoked(&force_logged_out).await);
+        assert!(!restarted.is_revoked(&claims(Utc::now())).await);
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired() {
+        let repo = TestRevocationRepository::default();
+        let service = RevocationService::new(repo.clone());
+
+        let expired = claims(Utc::now() - Duration::minutes(10));
+        let active = claims(Utc::now());
+
+        service.revoke_token(&expired).await.unwrap();
+        service.revoke_token(&active).await.unwrap();
+
+        assert_eq!(service.purge_expired().await.unwrap(), 1);
+        assert_eq!(repo.tokens.lock().unwrap().len(), 1);
+        assert!(service.is_revoked(&active).await);
+    }
+}
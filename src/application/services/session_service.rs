@@ -0,0 +1,520 @@
+use chrono::Utc;
+use thiserror::Error;
+use tracing::error;
+
+use crate::{
+    application::services::review_service::{ReviewService, ReviewServiceError},
+    domain::{
+        models::{
+            session::{Direction, ReviewSession, SessionAnswer, SessionCard},
+            word_pair::WordPairFilter,
+        },
+        traits::{
+            repositories::{
+                review_repository::IReviewRepository, session_repository::ISessionRepository,
+            },
+            review::scheduler::IScheduler,
+        },
+        types::ID,
+    },
+    infrastructure::utils::string::answer_check::check_answer,
+};
+
+#[derive(Clone)]
+pub struct SessionService<Repo, ReviewRepo, Scheduler> {
+    repo: Repo,
+    review_service: ReviewService<ReviewRepo, Scheduler>,
+}
+
+#[derive(Debug, Error)]
+pub enum SessionServiceError {
+    #[error("Session not found: `{0}`")]
+    NotFound(String),
+
+    #[error("No cards to review")]
+    NoCards,
+
+    #[error("Session has no card at position {0}")]
+    InvalidCard(i32),
+
+    #[error("Card at position {0} was already answered")]
+    AlreadyAnswered(i32),
+
+    #[error("Neither a grade nor an answer was given")]
+    MissingGrade,
+
+    #[error("Review error: `{0}`")]
+    Review(#[from] ReviewServiceError),
+
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Repo, ReviewRepo, Scheduler> SessionService<Repo, ReviewRepo, Scheduler>
+where
+    Repo: ISessionRepository<Error = sqlx::Error>,
+    ReviewRepo: IReviewRepository<Error = sqlx::Error>,
+    Scheduler: IScheduler,
+{
+    pub fn new(repo: Repo, review_service: ReviewService<ReviewRepo, Scheduler>) -> Self {
+        Self {
+            repo,
+            review_service,
+        }
+    }
+
+    /// Starts a session of at most `size` cards: the due ones first, most
+    /// overdue first, then up to `new_cards` word pairs never reviewed.
+    pub async fn create(
+        &self,
+        user_id: &ID,
+        direction: Direction,
+        filter: &WordPairFilter,
+        size: i64,
+        new_cards: i64,
+    ) -> Result<ReviewSession, SessionServiceError> {
+        let due = self.review_service.get_due(user_id, filter, size).await?;
+
+        let new_limit = new_cards.min(size - due.len() as i64);
+        let new = match new_limit > 0 {
+            true => {
+                self.review_service
+                    .get_new(user_id, filter, new_limit)
+                    .await?
+            }
+            false => Vec::new(),
+        };
+
+        let cards: Vec<SessionCard> = due
+            .iter()
+            .map(|card| (&card.word_pair, false))
+            .chain(new.iter().map(|word_pair| (word_pair, true)))
+            .enumerate()
+            .map(|(position, (word_pair, is_new))| {
+                SessionCard::new(position as i32, word_pair, direction.for_card(), is_new)
+            })
+            .collect();
+
+        if cards.is_empty() {
+            return Err(SessionServiceError::NoCards);
+        }
+
+        let session = ReviewSession::new(
+            *user_id,
+            direction,
+            filter.source_language.clone(),
+            filter.target_language.clone(),
+            cards,
+        );
+
+        let res = self.repo.insert(&session).await.map_err(|error| {
+            error!("Session DB error: {}", error);
+            error
+        })?;
+
+        Ok(res)
+    }
+
+    pub async fn get_by_id(&self, id: &ID) -> Result<ReviewSession, SessionServiceError> {
+        let res = self
+            .repo
+            .select_by_id(id)
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => SessionServiceError::NotFound(error.to_string()),
+                _ => {
+                    error!("Session DB error: {}", error);
+                    SessionServiceError::Database(error)
+                }
+            })?;
+
+        Ok(res)
+    }
+
+    pub async fn get_by_user_id(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<ReviewSession>, SessionServiceError> {
+        let res = self
+            .repo
+            .select_by_user_id(user_id)
+            .await
+            .map_err(|error| {
+                error!("Session DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Records the answer to a card and schedules the next review of its
+    /// word pair. A typed answer without a grade is graded by its verdict.
+    /// Without `duration_ms` the time since the previous answer is taken.
+    /// The card is claimed in the store before the review is graded, so a
+    /// card answered concurrently is only graded once. The session finishes
+    /// with its last card.
+    pub async fn answer(
+        &self,
+        session: &ReviewSession,
+        answer: SessionAnswer,
+    ) -> Result<ReviewSession, SessionServiceError> {
+        let position = answer.position;
+        let now = Utc::now();
+
+        let mut card = session
+            .cards
+            .iter()
+            .find(|card| card.position == position)
+            .cloned()
+            .ok_or(SessionServiceError::InvalidCard(position))?;
+
+        if card.is_answered() {
+            return Err(SessionServiceError::AlreadyAnswered(position));
+        }
+
+        let grade = match (answer.grade, &answer.answer) {
+            (Some(grade), _) => grade,
+            (None, Some(text)) => check_answer(text, &card.expected, answer.ignore_diacritics)
+                .verdict
+                .grade(),
+            (None, None) => return Err(SessionServiceError::MissingGrade),
+        };
+
+        card.grade = Some(grade);
+        card.answer = answer.answer;
+        card.answered_at = Some(now);
+        card.duration_ms = Some(
+            answer
+                .duration_ms
+                .unwrap_or_else(|| (now - session.last_activity_at()).num_milliseconds())
+                .max(0),
+        );
+
+        card = self
+            .repo
+            .answer_card(&session.id, &card)
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => SessionServiceError::AlreadyAnswered(position),
+                _ => {
+                    error!("Session DB error: {}", error);
+                    SessionServiceError::Database(error)
+                }
+            })?;
+
+        if let Err(error) = self
+            .review_service
+            .grade(&card.word_pair_id, &session.user_id, grade)
+            .await
+        {
+            if let Err(error) = self.repo.clear_card(&session.id, position).await {
+                error!("Session DB error: {}", error);
+            }
+
+            return Err(error.into());
+        }
+
+        // Other cards may have been answered meanwhile, only the stored
+        // session tells whether this was the last one.
+        let mut res = self.get_by_id(&session.id).await?;
+
+        if !res.is_finished() && res.cards.iter().all(SessionCard::is_answered) {
+            res = self.repo.finish(&session.id, &now).await.map_err(|error| {
+                error!("Session DB error: {}", error);
+                error
+            })?;
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{
+            models::{
+                review::{Grade, ReviewState},
+                word_pair::WordPair,
+            },
+            traits::repositories::repository::Repository,
+        },
+        infrastructure::{
+            scheduler::Scheduler,
+            storage::memory::{
+                review_repository::ReviewMemoryRepository,
+                session_repository::SessionMemoryRepository,
+            },
+        },
+    };
+
+    use super::*;
+
+    fn service() -> (
+        SessionService<SessionMemoryRepository, ReviewMemoryRepository, Scheduler>,
+        ReviewMemoryRepository,
+    ) {
+        let review_repo = ReviewMemoryRepository::default();
+        let review_service = ReviewService::new(review_repo.clone(), Scheduler::default());
+
+        (
+            SessionService::new(SessionMemoryRepository::default(), review_service),
+            review_repo,
+        )
+    }
+
+    fn word_pair(user_id: ID, source_text: &str, target_text: &str) -> WordPair {
+        WordPair::new_simple(
+            user_id,
+            target_text.to_string(),
+            source_text.to_string(),
+            "de".to_string(),
+            "en".to_string(),
+        )
+    }
+
+    fn graded(position: i32, grade: Grade) -> SessionAnswer {
+        SessionAnswer {
+            position,
+            grade: Some(grade),
+            ..SessionAnswer::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_due_cards_before_new_cards() {
+        let (session_service, review_repo) = service();
+
+        let user_id = Uuid::new_v4();
+
+        for source_text in ["one", "two", "three"] {
+            review_repo.add_word_pair(&word_pair(user_id, source_text, "x"));
+        }
+
+        let due = word_pair(user_id, "due", "fällig");
+        review_repo.add_word_pair(&due);
+        review_repo
+            .insert(&ReviewState::new(due.id, user_id, Utc::now()))
+            .await
+            .unwrap();
+
+        let res = session_service
+            .create(
+                &user_id,
+                Direction::TargetToSource,
+                &WordPairFilter::default(),
+                20,
+                2,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.cards.len(), 3);
+        assert_eq!(res.cards[0].word_pair_id, due.id);
+        assert!(!res.cards[0].is_new);
+        assert_eq!(res.cards[0].prompt, "Fällig");
+        assert_eq!(res.cards[0].expected, "Due");
+        assert!(res.cards[1..].iter().all(|card| card.is_new));
+
+        let res = session_service
+            .create(
+                &user_id,
+                Direction::SourceToTarget,
+                &WordPairFilter::default(),
+                1,
+                10,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.cards.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_cards() {
+        let (session_service, _) = service();
+
+        let res = session_service
+            .create(
+                &Uuid::new_v4(),
+                Direction::Mixed,
+                &WordPairFilter::default(),
+                20,
+                10,
+            )
+            .await;
+
+        assert!(matches!(res, Err(SessionServiceError::NoCards)));
+    }
+
+    #[tokio::test]
+    async fn test_answers_and_summary() {
+        let (session_service, review_repo) = service();
+
+        let user_id = Uuid::new_v4();
+
+        review_repo.add_word_pair(&word_pair(user_id, "cat", "Katze"));
+        review_repo.add_word_pair(&word_pair(user_id, "dog", "Hund"));
+
+        let session = session_service
+            .create(
+                &user_id,
+                Direction::Mixed,
+                &WordPairFilter::default(),
+                20,
+                10,
+            )
+            .await
+            .unwrap();
+
+        let session = session_service
+            .answer(
+                &session,
+                SessionAnswer {
+                    position: 0,
+                    grade: Some(Grade::Good),
+                    answer: Some("Katze".to_string()),
+                    duration_ms: Some(1500),
+                    ..SessionAnswer::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(!session.is_finished());
+
+        let res = session_service
+            .answer(&session, graded(0, Grade::Good))
+            .await;
+
+        assert!(matches!(res, Err(SessionServiceError::AlreadyAnswered(0))));
+
+        let res = session_service
+            .answer(&session, graded(5, Grade::Good))
+            .await;
+
+        assert!(matches!(res, Err(SessionServiceError::InvalidCard(5))));
+
+        let session = session_service
+            .answer(
+                &session,
+                SessionAnswer {
+                    duration_ms: Some(500),
+                    ..graded(1, Grade::Again)
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(session.is_finished());
+        assert_eq!(review_repo.logs().len(), 2);
+
+        let summary = session_service
+            .get_by_id(&session.id)
+            .await
+            .unwrap()
+            .summary();
+
+        assert_eq!(summary.answered, 2);
+        assert_eq!(summary.correct, 1);
+        assert_eq!(summary.accuracy, Some(0.5));
+        assert_eq!(summary.again, 1);
+        assert_eq!(summary.time_spent_ms, 2000);
+        assert_eq!(summary.average_ms, Some(1000));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_answers_grade_once() {
+        let (session_service, review_repo) = service();
+
+        let user_id = Uuid::new_v4();
+
+        review_repo.add_word_pair(&word_pair(user_id, "cat", "Katze"));
+
+        let session = session_service
+            .create(
+                &user_id,
+                Direction::SourceToTarget,
+                &WordPairFilter::default(),
+                20,
+                10,
+            )
+            .await
+            .unwrap();
+
+        // Both answers start from the session loaded before either was saved.
+        session_service
+            .answer(&session, graded(0, Grade::Good))
+            .await
+            .unwrap();
+
+        let res = session_service
+            .answer(&session, graded(0, Grade::Again))
+            .await;
+
+        assert!(matches!(res, Err(SessionServiceError::AlreadyAnswered(0))));
+        assert_eq!(review_repo.logs().len(), 1);
+
+        let session = session_service.get_by_id(&session.id).await.unwrap();
+
+        assert_eq!(session.cards[0].grade, Some(Grade::Good));
+    }
+
+    #[tokio::test]
+    async fn test_typed_answer() {
+        let (session_service, review_repo) = service();
+
+        let user_id = Uuid::new_v4();
+
+        review_repo.add_word_pair(&word_pair(user_id, "cat", "Katze"));
+        review_repo.add_word_pair(&word_pair(user_id, "coffee", "Café"));
+
+        let session = session_service
+            .create(
+                &user_id,
+                Direction::SourceToTarget,
+                &WordPairFilter::default(),
+                20,
+                10,
+            )
+            .await
+            .unwrap();
+
+        let res = session_service
+            .answer(&session, SessionAnswer::default())
+            .await;
+
+        assert!(matches!(res, Err(SessionServiceError::MissingGrade)));
+
+        let session = session_service
+            .answer(
+                &session,
+                SessionAnswer {
+                    answer: Some("katz".to_string()),
+                    ..SessionAnswer::default()
+                },
+            )
+            .await
+            .unwrap();
+        let session = session_service
+            .answer(
+                &session,
+                SessionAnswer {
+                    position: 1,
+                    answer: Some("cafe".to_string()),
+                    ignore_diacritics: true,
+                    ..SessionAnswer::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(session.cards[0].grade, Some(Grade::Hard));
+        assert_eq!(session.cards[1].grade, Some(Grade::Good));
+    }
+}
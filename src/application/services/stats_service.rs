@@ -0,0 +1,291 @@
+use chrono::{Duration, NaiveDate, Utc};
+use thiserror::Error;
+use tracing::error;
+
+use crate::domain::{models::stats::Stats, traits::stats::stats_store::IStatsStore, types::ID};
+
+/// Review days looked at for the streak.
+const MAX_STREAK_DAYS: i64 = 3650;
+
+#[derive(Clone)]
+pub struct StatsService<Store> {
+    store: Store,
+}
+
+#[derive(Debug, Error)]
+pub enum StatsServiceError {
+    #[error("Unknown time zone: {0}")]
+    InvalidTimeZone(String),
+
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Store> StatsService<Store>
+where
+    Store: IStatsStore<Error = sqlx::Error>,
+{
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// The user's progress over the last `days` days and `weeks` weeks,
+    /// bucketed by local days of `time_zone`. Retention is estimated from
+    /// the reviews of learned cards within the last `days` days.
+    pub async fn get(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        days: i64,
+        weeks: i64,
+    ) -> Result<Stats, StatsServiceError> {
+        let time_zone_exists = self
+            .store
+            .time_zone_exists(time_zone)
+            .await
+            .map_err(|error| {
+                error!("Stats DB error: {}", error);
+                error
+            })?;
+
+        if !time_zone_exists {
+            return Err(StatsServiceError::InvalidTimeZone(time_zone.to_string()));
+        }
+
+        let today = self.store.today(time_zone).await.map_err(|error| {
+            error!("Stats DB error: {}", error);
+            error
+        })?;
+
+        let language_pairs = self.store.language_pairs(user_id).await.map_err(|error| {
+            error!("Stats DB error: {}", error);
+            error
+        })?;
+
+        let added_per_day = self
+            .store
+            .added_per_day(user_id, time_zone, days)
+            .await
+            .map_err(|error| {
+                error!("Stats DB error: {}", error);
+                error
+            })?;
+
+        let added_per_week = self
+            .store
+            .added_per_week(user_id, time_zone, weeks)
+            .await
+            .map_err(|error| {
+                error!("Stats DB error: {}", error);
+                error
+            })?;
+
+        let reviews_per_day = self
+            .store
+            .reviews_per_day(user_id, time_zone, days)
+            .await
+            .map_err(|error| {
+                error!("Stats DB error: {}", error);
+                error
+            })?;
+
+        let retention = self
+            .store
+            .retention(user_id, &(Utc::now() - Duration::days(days)))
+            .await
+            .map_err(|error| {
+                error!("Stats DB error: {}", error);
+                error
+            })?;
+
+        let review_days = self
+            .store
+            .review_days(user_id, time_zone, MAX_STREAK_DAYS)
+            .await
+            .map_err(|error| {
+                error!("Stats DB error: {}", error);
+                error
+            })?;
+
+        Ok(Stats {
+            time_zone: time_zone.to_string(),
+            today,
+            language_pairs,
+            added_per_day,
+            added_per_week,
+            reviews_per_day,
+            retention,
+            streak_days: streak(&review_days, today),
+        })
+    }
+}
+
+/// Counts the days in a row with reviews in `review_days`, latest first,
+/// ending today. A streak ending yesterday still counts as today may not
+/// be over yet.
+fn streak(review_days: &[NaiveDate], today: NaiveDate) -> i64 {
+    let Some(&latest) = review_days.first() else {
+        return 0;
+    };
+
+    if latest != today && Some(latest) != today.pred_opt() {
+        return 0;
+    }
+
+    let mut expected = latest;
+    let mut res = 0;
+
+    for day in review_days {
+        if *day != expected {
+            break;
+        }
+
+        res += 1;
+
+        match expected.pred_opt() {
+            Some(previous) => expected = previous,
+            None => break,
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::DateTime;
+    use uuid::Uuid;
+
+    use crate::domain::models::stats::{
+        DailyCount, DailyReviews, LanguagePairStats, RetentionCount,
+    };
+
+    use super::*;
+
+    fn day(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    struct TestStatsStore {
+        today: NaiveDate,
+        review_days: Vec<NaiveDate>,
+    }
+
+    #[async_trait]
+    impl IStatsStore for TestStatsStore {
+        type Error = sqlx::Error;
+
+        async fn time_zone_exists(&self, time_zone: &str) -> Result<bool, Self::Error> {
+            Ok(["UTC", "Europe/Berlin"].contains(&time_zone))
+        }
+
+        async fn today(&self, _time_zone: &str) -> Result<NaiveDate, Self::Error> {
+            Ok(self.today)
+        }
+
+        async fn language_pairs(
+            &self,
+            _user_id: &ID,
+        ) -> Result<Vec<LanguagePairStats>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn added_per_day(
+            &self,
+            _user_id: &ID,
+            _time_zone: &str,
+            days: i64,
+        ) -> Result<Vec<DailyCount>, Self::Error> {
+            Ok((0..days)
+                .rev()
+                .map(|offset| DailyCount {
+                    day: self.today - Duration::days(offset),
+                    count: 0,
+                })
+                .collect())
+        }
+
+        async fn added_per_week(
+            &self,
+            _user_id: &ID,
+            _time_zone: &str,
+            _weeks: i64,
+        ) -> Result<Vec<DailyCount>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn reviews_per_day(
+            &self,
+            _user_id: &ID,
+            _time_zone: &str,
+            _days: i64,
+        ) -> Result<Vec<DailyReviews>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn retention(
+            &self,
+            _user_id: &ID,
+            _since: &DateTime<Utc>,
+        ) -> Result<RetentionCount, Self::Error> {
+            Ok(RetentionCount {
+                reviews: 4,
+                remembered: 3,
+            })
+        }
+
+        async fn review_days(
+            &self,
+            _user_id: &ID,
+            _time_zone: &str,
+            _limit: i64,
+        ) -> Result<Vec<NaiveDate>, Self::Error> {
+            Ok(self.review_days.clone())
+        }
+    }
+
+    #[test]
+    fn test_streak() {
+        let today = day("2026-03-10");
+
+        assert_eq!(streak(&[], today), 0);
+        assert_eq!(
+            streak(
+                &[day("2026-03-10"), day("2026-03-09"), day("2026-03-07")],
+                today
+            ),
+            2
+        );
+        assert_eq!(streak(&[day("2026-03-09"), day("2026-03-08")], today), 2);
+        assert_eq!(streak(&[day("2026-03-08")], today), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_stats() {
+        let stats_service = StatsService::new(TestStatsStore {
+            today: day("2026-03-10"),
+            review_days: vec![day("2026-03-10")],
+        });
+
+        let res = stats_service
+            .get(&Uuid::new_v4(), "Europe/Berlin", 7, 4)
+            .await
+            .unwrap();
+
+        assert_eq!(res.time_zone, "Europe/Berlin");
+        assert_eq!(res.added_per_day.len(), 7);
+        assert_eq!(res.added_per_day.last().unwrap().day, day("2026-03-10"));
+        assert_eq!(res.retention.retention(), Some(0.75));
+        assert_eq!(res.streak_days, 1);
+
+        let res = stats_service
+            .get(&Uuid::new_v4(), "Mars/Olympus", 7, 4)
+            .await;
+
+        assert!(matches!(res, Err(StatsServiceError::InvalidTimeZone(_))));
+    }
+}
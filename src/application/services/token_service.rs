@@ -0,0 +1,899 @@
+use chrono::{Duration, Utc};
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    api::auth::models::Claims,
+    application::services::{
+        revocation_service::{RevocationService, RevocationServiceError},
+        user_service::{UserService, UserServiceError},
+    },
+    domain::{
+        models::{
+            refresh_token::RefreshToken, revocation::RevokedToken, token::TokenPair, user::User,
+        },
+        traits::{
+            auth::token_issuer::ITokenIssuer,
+            repositories::{
+                refresh_token_repository::IRefreshTokenRepository,
+                revocation_repository::IRevocationRepository, user_repository::IUserRepository,
+            },
+        },
+        types::ID,
+    },
+    infrastructure::{
+        auth::token::{
+            issuer::TokenIssuerError,
+            jwks::claims::{JwksClaims, JwksClaimsError, usize_to_datetime},
+        },
+        utils::secret_token::hash_token,
+    },
+};
+
+const DEFAULT_REFRESH_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
+const DEFAULT_PURGE_INTERVAL_SECONDS: u64 = 60 * 60;
+
+#[derive(Clone)]
+pub struct TokenService<Issuer, Repo, RevocationRepo, UserRepo> {
+    pub issuer: Issuer,
+    repo: Repo,
+    revocation_service: RevocationService<RevocationRepo>,
+    user_service: UserService<UserRepo>,
+    refresh_ttl: Duration,
+}
+
+#[derive(Error, Debug)]
+pub enum TokenServiceError {
+    #[error("Token issuer error: `{0}`")]
+    Issuer(#[from] TokenIssuerError),
+
+    #[error("Invalid refresh token")]
+    InvalidRefreshToken,
+
+    #[error("Refresh token expired")]
+    RefreshTokenExpired,
+
+    #[error("Refresh token reused, token family `{0}` revoked")]
+    RefreshTokenReused(ID),
+
+    #[error("Database error: `{0}`")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Revocation error: `{0}`")]
+    Revocation(#[from] RevocationServiceError),
+
+    #[error("User error: `{0}`")]
+    User(#[from] UserServiceError),
+
+    #[error("Claims error: `{0}`")]
+    Claims(#[from] JwksClaimsError),
+
+    #[error("Unknown error")]
+    Unknown,
+}
+
+impl<Issuer, Repo, RevocationRepo, UserRepo> TokenService<Issuer, Repo, RevocationRepo, UserRepo>
+where
+    Issuer: ITokenIssuer<Error = TokenIssuerError>,
+    Repo: IRefreshTokenRepository<Error = sqlx::Error>,
+    RevocationRepo: IRevocationRepository<Error = sqlx::Error>,
+    UserRepo: IUserRepository<Error = sqlx::Error>,
+{
+    pub fn new(
+        issuer: Issuer,
+        repo: Repo,
+        revocation_service: RevocationService<RevocationRepo>,
+        user_service: UserService<UserRepo>,
+    ) -> Self {
+        let refresh_ttl = Duration::seconds(
+            std::env::var("REFRESH_TOKEN_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_REFRESH_TTL_SECONDS),
+        );
+
+        Self {
+            issuer,
+            repo,
+            revocation_service,
+            user_service,
+            refresh_ttl,
+        }
+    }
+
+    /// Issues a pair that starts a new refresh token family.
+    pub async fn issue(&self, user_id: &ID) -> Result<TokenPair, TokenServiceError> {
+        let user = self.active_user(user_id).await?;
+
+        let res = self
+            .issuer
+            .issue(user_id, user.role)
+            .await
+            .map_err(|error| {
+                error!("Token issuer error: {}", error);
+                error
+            })?;
+
+        self.record(user_id, &Uuid::new_v4(), &res).await?;
+
+        Ok(res)
+    }
+
+    /// Exchanges a refresh token for the next pair of its family. A token
+    /// that was already used revokes the whole family, including the access
+    /// tokens issued with it. If the next pair can't be issued the token
+    /// stays usable, so retrying doesn't count as reuse.
+    pub async fn refresh(
+        &self,
+        encrypted_refresh_token: &str,
+        nonce: &str,
+    ) -> Result<TokenPair, TokenServiceError> {
+        let token = self
+            .repo
+            .select_by_hash(&hash_token(encrypted_refresh_token))
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => TokenServiceError::InvalidRefreshToken,
+                _ => {
+                    error!("Refresh token DB error: {}", error);
+                    TokenServiceError::Database(error)
+                }
+            })?;
+
+        if token.nonce != nonce {
+            return Err(TokenServiceError::InvalidRefreshToken);
+        }
+
+        if token.expires_at <= Utc::now() {
+            return Err(TokenServiceError::RefreshTokenExpired);
+        }
+
+        if let Err(error) = self.repo.mark_used(&token.id, &Utc::now()).await {
+            return match error {
+                sqlx::Error::RowNotFound => {
+                    warn!(
+                        "Refresh token reused, revoking token family {}",
+                        token.family_id
+                    );
+
+                    self.revoke_family(&token.family_id).await?;
+
+                    Err(TokenServiceError::RefreshTokenReused(token.family_id))
+                }
+                _ => {
+                    error!("Refresh token DB error: {}", error);
+                    Err(TokenServiceError::Database(error))
+                }
+            };
+        }
+
+        let res = self
+            .issue_next(&token, encrypted_refresh_token, nonce)
+            .await;
+
+        if res.is_err()
+            && let Err(error) = self.repo.clear_used(&token.id).await
+        {
+            error!("Refresh token DB error: {}", error);
+        }
+
+        res
+    }
+
+    /// Signs out the session the access token belongs to: the token itself
+    /// and its refresh token family, so the session can't be refreshed.
+    pub async fn logout(&self, claims: &Claims) -> Result<(), TokenServiceError> {
+        match self.repo.select_by_access_jti(&claims.jti).await {
+            Ok(token) => self.revoke_family(&token.family_id).await?,
+            Err(sqlx::Error::RowNotFound) => {}
+            Err(error) => {
+                error!("Refresh token DB error: {}", error);
+                return Err(error.into());
+            }
+        }
+
+        self.revocation_service.revoke_token(claims).await?;
+
+        Ok(())
+    }
+
+    /// Drops refresh tokens that can't be used anymore.
+    pub async fn purge_expired(&self) -> Result<u64, TokenServiceError> {
+        let res = self
+            .repo
+            .delete_expired(&Utc::now())
+            .await
+            .map_err(|error| {
+                error!("Refresh token DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Runs `purge_expired` forever, every `REFRESH_TOKEN_PURGE_INTERVAL_SECONDS`.
+    pub async fn run_purge(&self) {
+        let seconds = std::env::var("REFRESH_TOKEN_PURGE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_PURGE_INTERVAL_SECONDS);
+
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(seconds));
+
+        loop {
+            interval.tick().await;
+
+            match self.purge_expired().await {
+                Ok(purged) if purged > 0 => info!("Purged {} expired refresh tokens", purged),
+                Ok(_) => {}
+                Err(error) => error!("Couldn't purge refresh tokens: {}", error),
+            }
+        }
+    }
+
+    /// Signs the user out everywhere: their refresh tokens stop working and
+    /// every access token issued so far is revoked.
+    pub async fn revoke_user(&self, user_id: &ID) -> Result<(), TokenServiceError> {
+        let now = Utc::now();
+
+        self.repo
+            .revoke_by_user_id(user_id, &now)
+            .await
+            .map_err(|error| {
+                error!("Refresh token DB error: {}", error);
+                error
+            })?;
+
+        self.revocation_service
+            .revoke_user_tokens(user_id, now)
+            .await?;
+
+        Ok(())
+    }
+
+    /// The user tokens are issued for, which must not be disabled or waiting
+    /// for deletion. The role is read here so a changed role takes effect on
+    /// the next refresh.
+    async fn active_user(&self, user_id: &ID) -> Result<User, TokenServiceError> {
+        let user = self.user_service.get_by_id(user_id).await?;
+
+        if user.is_disabled() {
+            return Err(UserServiceError::Disabled(user.key).into());
+        }
+
+        if user.is_pending_deletion() {
+            return Err(UserServiceError::PendingDeletion(user.key).into());
+        }
+
+        Ok(user)
+    }
+
+    /// The pair following `token` in its family, recorded for the next
+    /// refresh.
+    async fn issue_next(
+        &self,
+        token: &RefreshToken,
+        encrypted_refresh_token: &str,
+        nonce: &str,
+    ) -> Result<TokenPair, TokenServiceError> {
+        let user = self.active_user(&token.user_id).await?;
+
+        let res = self
+            .issuer
+            .refresh(&token.user_id, user.role, encrypted_refresh_token, nonce)
+            .await
+            .map_err(|error| {
+                error!("Token issuer error: {}", error);
+                error
+            })?;
+
+        self.record(&token.user_id, &token.family_id, &res).await?;
+
+        Ok(res)
+    }
+
+    async fn record(
+        &self,
+        user_id: &ID,
+        family_id: &ID,
+        token_pair: &TokenPair,
+    ) -> Result<(), TokenServiceError> {
+        let claims = JwksClaims::read_unverified(&token_pair.access_token)?;
+
+        let token = RefreshToken::new(
+            *family_id,
+            *user_id,
+            hash_token(&token_pair.encrypted_refresh_token),
+            token_pair.nonce.clone(),
+            claims.jti,
+            usize_to_datetime(claims.exp)?,
+            Utc::now() + self.refresh_ttl,
+        );
+
+        self.repo.insert(&token).await.map_err(|error| {
+            error!("Refresh token DB error: {}", error);
+            error
+        })?;
+
+        Ok(())
+    }
+
+    /// Revokes every token of the family, including the access tokens
+    /// issued with it that haven't expired yet.
+    async fn revoke_family(&self, family_id: &ID) -> Result<(), TokenServiceError> {
+        let now = Utc::now();
+
+        let tokens = self
+            .repo
+            .revoke_family(family_id, &now)
+            .await
+            .map_err(|error| {
+                error!("Refresh token DB error: {}", error);
+                error
+            })?;
+
+        for token in tokens.iter().filter(|token| token.access_expires_at > now) {
+            self.revocation_service
+                .revoke(&RevokedToken::new(
+                    token.access_jti,
+                    token.user_id,
+                    token.access_expires_at,
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    };
+
+    use async_trait::async_trait;
+    use chrono::DateTime;
+
+    use crate::{
+        domain::{
+            models::{revocation::UserRevocation, role::Role},
+            traits::repositories::repository::Repository,
+        },
+        infrastructure::{
+            auth::token::{issuer::TokenIssuer, local::local_issuer::LocalTokenIssuer},
+            external_api::auth::auth_requests::AuthCommunicator,
+            storage::database::models::{
+                refresh_token::RefreshTokenEntity, revocation::RevokedTokenEntity, user::UserEntity,
+            },
+            utils::password::Argon2Crypto,
+        },
+    };
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct TestRefreshTokenRepository {
+        tokens: Arc<Mutex<Vec<RefreshToken>>>,
+    }
+
+    #[async_trait]
+    impl Repository for TestRefreshTokenRepository {
+        type Pool = ();
+        type Item = RefreshToken;
+        type Entity = RefreshTokenEntity;
+        type Error = sqlx::Error;
+
+        fn new(_db: Self::Pool) -> Self {
+            Self::default()
+        }
+
+        async fn insert(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            self.tokens.lock().unwrap().push(item.clone());
+
+            Ok(item.clone())
+        }
+
+        async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.id == *id)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+            self.tokens.lock().unwrap().retain(|token| token.id != *id);
+
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl IRefreshTokenRepository for TestRefreshTokenRepository {
+        async fn select_by_hash(&self, token_hash: &str) -> Result<Self::Item, Self::Error> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.token_hash == token_hash)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn select_by_access_jti(&self, access_jti: &ID) -> Result<Self::Item, Self::Error> {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.access_jti == *access_jti)
+                .cloned()
+                .ok_or(sqlx::Error::RowNotFound)
+        }
+
+        async fn mark_used(
+            &self,
+            id: &ID,
+            used_at: &DateTime<Utc>,
+        ) -> Result<Self::Item, Self::Error> {
+            let mut tokens = self.tokens.lock().unwrap();
+
+            let token = tokens
+                .iter_mut()
+                .find(|token| token.id == *id && token.used_at.is_none())
+                .filter(|token| token.revoked_at.is_none())
+                .ok_or(sqlx::Error::RowNotFound)?;
+
+            token.used_at = Some(*used_at);
+
+            Ok(token.clone())
+        }
+
+        async fn clear_used(&self, id: &ID) -> Result<(), Self::Error> {
+            let mut tokens = self.tokens.lock().unwrap();
+
+            if let Some(token) = tokens
+                .iter_mut()
+                .find(|token| token.id == *id && token.revoked_at.is_none())
+            {
+                token.used_at = None;
+            }
+
+            Ok(())
+        }
+
+        async fn revoke_family(
+            &self,
+            family_id: &ID,
+            revoked_at: &DateTime<Utc>,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            let mut tokens = self.tokens.lock().unwrap();
+
+            let family = tokens
+                .iter_mut()
+                .filter(|token| token.family_id == *family_id)
+                .map(|token| {
+                    token.revoked_at.get_or_insert(*revoked_at);
+                    token.clone()
+                })
+                .collect();
+
+            Ok(family)
+        }
+
+        async fn revoke_by_user_id(
+            &self,
+            user_id: &ID,
+            revoked_at: &DateTime<Utc>,
+        ) -> Result<u64, Self::Error> {
+            let mut tokens = self.tokens.lock().unwrap();
+
+            let revoked = tokens
+                .iter_mut()
+                .filter(|token| token.user_id == *user_id && token.revoked_at.is_none())
+                .map(|token| token.revoked_at = Some(*revoked_at))
+                .count();
+
+            Ok(revoked as u64)
+        }
+
+        async fn delete_expired(&self, now: &DateTime<Utc>) -> Result<u64, Self::Error> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let before = tokens.len();
+
+            tokens.retain(|token| {
+                token.expires_at > *now
+                    && (token.revoked_at.is_none() || token.access_expires_at > *now)
+            });
+
+            Ok((before - tokens.len()) as u64)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct TestRevocationRepository;
+
+    #[async_trait]
+    impl Repository for TestRevocationRepository {
+        type Pool = ();
+        type Item = RevokedToken;
+        type Entity = RevokedTokenEntity;
+        type Error = sqlx::Error;
+
+        fn new(_db: Self::Pool) -> Self {
+            Self
+        }
+
+        async fn insert(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            Ok(item.clone())
+        }
+
+        async fn select_by_id(&self, _id: &ID) -> Result<Self::Item, Self::Error> {
+            Err(sqlx::Error::RowNotFound)
+        }
+
+        async fn delete_by_id(&self, _id: &ID) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl IRevocationRepository for TestRevocationRepository {
+        async fn select_active(
+            &self,
+            _now: &DateTime<Utc>,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            Ok(vec![])
+        }
+
+        async fn delete_expired(&self, _now: &DateTime<Utc>) -> Result<u64, Self::Error> {
+            Ok(0)
+        }
+
+        async fn upsert_user_revocation(
+            &self,
+            revocation: &UserRevocation,
+        ) -> Result<UserRevocation, Self::Error> {
+            Ok(revocation.clone())
+        }
+
+        async fn select_user_revocations(&self) -> Result<Vec<UserRevocation>, Self::Error> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct TestUserRepository {
+        disabled: Arc<Mutex<HashSet<ID>>>,
+    }
+
+    #[async_trait]
+    impl Repository for TestUserRepository {
+        type Pool = ();
+        type Item = User;
+        type Entity = UserEntity;
+        type Error = sqlx::Error;
+
+        fn new(_db: Self::Pool) -> Self {
+            Self::default()
+        }
+
+        async fn insert(&self, item: &Self::Item) -> Result<Self::Item, Self::Error> {
+            Ok(item.clone())
+        }
+
+        async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+            let mut user = User::new_simple(
+                "faksfjas".to_string(),
+                "Mdafasdfd".to_string(),
+                "hashed".to_string(),
+            );
+
+            user.id = *id;
+
+            if self.disabled.lock().unwrap().contains(id) {
+                user.disabled_at = Some(Utc::now());
+            }
+
+            Ok(user)
+        }
+
+        async fn delete_by_id(&self, _id: &ID) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl IUserRepository for TestUserRepository {
+        async fn select_by_key(&self, _key: &str) -> Result<Self::Item, Self::Error> {
+            Err(sqlx::Error::RowNotFound)
+        }
+
+        async fn update_by_id(&self, updated_user: &Self::Item) -> Result<Self::Item, Self::Error> {
+            Ok(updated_user.clone())
+        }
+
+        async fn select_page(
+            &self,
+            _search: Option<&str>,
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            Ok(vec![])
+        }
+
+        async fn count(&self, _search: Option<&str>) -> Result<i64, Self::Error> {
+            Ok(0)
+        }
+
+        async fn update_disabled(
+            &self,
+            id: &ID,
+            disabled_at: Option<&DateTime<Utc>>,
+        ) -> Result<Self::Item, Self::Error> {
+            match disabled_at {
+                Some(_) => self.disabled.lock().unwrap().insert(*id),
+                None => self.disabled.lock().unwrap().remove(id),
+            };
+
+            self.select_by_id(id).await
+        }
+
+        async fn update_role(&self, id: &ID, _role: Role) -> Result<Self::Item, Self::Error> {
+            self.select_by_id(id).await
+        }
+
+        async fn update_delete_after(
+            &self,
+            id: &ID,
+            _delete_after: Option<&DateTime<Utc>>,
+        ) -> Result<Self::Item, Self::Error> {
+            self.select_by_id(id).await
+        }
+
+        async fn select_inactive_ids(&self) -> Result<Vec<ID>, Self::Error> {
+            Ok(self.disabled.lock().unwrap().iter().copied().collect())
+        }
+
+        async fn delete_due(&self, _now: &DateTime<Utc>) -> Result<Vec<ID>, Self::Error> {
+            Ok(vec![])
+        }
+    }
+
+    type TestTokenService = TokenService<
+        TokenIssuer,
+        TestRefreshTokenRepository,
+        TestRevocationRepository,
+        TestUserRepository,
+    >;
+
+    fn token_service() -> TestTokenService {
+        let issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+
+        TokenService::new(
+            TokenIssuer::Local(Box::new(issuer)),
+            TestRefreshTokenRepository::default(),
+            RevocationService::new(TestRevocationRepository),
+            UserService::new(TestUserRepository::default(), Argon2Crypto::default()),
+        )
+    }
+
+    fn access_claims(token_pair: &TokenPair) -> Claims {
+        let claims = JwksClaims::read_unverified(&token_pair.access_token).unwrap();
+
+        Claims::new(
+            claims.sub,
+            claims.jti,
+            usize_to_datetime(claims.iat).unwrap(),
+            usize_to_datetime(claims.exp).unwrap(),
+            Role::User,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_token() {
+        let token_service = token_service();
+        let user_id = Uuid::new_v4();
+
+        let first = token_service.issue(&user_id).await.unwrap();
+        let second = token_service
+            .refresh(&first.encrypted_refresh_token, &first.nonce)
+            .await
+            .unwrap();
+
+        assert_ne!(
+            first.encrypted_refresh_token,
+            second.encrypted_refresh_token
+        );
+        assert_eq!(access_claims(&second).sub, user_id);
+
+        let tokens = token_service.repo.tokens.lock().unwrap().clone();
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].family_id, tokens[1].family_id);
+        assert!(tokens[0].used_at.is_some());
+        assert!(tokens[1].used_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_reuse_revokes_family() {
+        let token_service = token_service();
+
+        let first = token_service.issue(&Uuid::new_v4()).await.unwrap();
+        let second = token_service
+            .refresh(&first.encrypted_refresh_token, &first.nonce)
+            .await
+            .unwrap();
+
+        let reused = token_service
+            .refresh(&first.encrypted_refresh_token, &first.nonce)
+            .await;
+
+        assert!(matches!(
+            reused,
+            Err(TokenServiceError::RefreshTokenReused(_))
+        ));
+
+        let latest = token_service
+            .refresh(&second.encrypted_refresh_token, &second.nonce)
+            .await;
+
+        assert!(matches!(
+            latest,
+            Err(TokenServiceError::RefreshTokenReused(_))
+        ));
+
+        for token_pair in [&first, &second] {
+            assert!(
+                token_service
+                    .revocation_service
+                    .is_revoked(&access_claims(token_pair))
+                    .await
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_keeps_token_usable() {
+        let token_service = token_service();
+
+        let token_pair = token_service.issue(&Uuid::new_v4()).await.unwrap();
+
+        // Without `AUTH_ADDRESS` the remote issuer fails before any request.
+        let failing = TokenService::new(
+            TokenIssuer::Remote(AuthCommunicator),
+            token_service.repo.clone(),
+            RevocationService::new(TestRevocationRepository),
+            UserService::new(TestUserRepository::default(), Argon2Crypto::default()),
+        );
+
+        let res = failing
+            .refresh(&token_pair.encrypted_refresh_token, &token_pair.nonce)
+            .await;
+
+        assert!(matches!(res, Err(TokenServiceError::Issuer(_))));
+
+        let res = token_service
+            .refresh(&token_pair.encrypted_refresh_token, &token_pair.nonce)
+            .await;
+
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rejects_unknown_token() {
+        let token_service = token_service();
+
+        let token_pair = token_service.issue(&Uuid::new_v4()).await.unwrap();
+
+        let unknown = token_service.refresh("unknown", &token_pair.nonce).await;
+        let wrong_nonce = token_service
+            .refresh(&token_pair.encrypted_refresh_token, "wrong")
+            .await;
+
+        assert!(matches!(
+            unknown,
+            Err(TokenServiceError::InvalidRefreshToken)
+        ));
+        assert!(matches!(
+            wrong_nonce,
+            Err(TokenServiceError::InvalidRefreshToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_user_cannot_refresh() {
+        let token_service = token_service();
+        let user_id = Uuid::new_v4();
+
+        let token_pair = token_service.issue(&user_id).await.unwrap();
+
+        token_service.user_service.disable(&user_id).await.unwrap();
+
+        let refreshed = token_service
+            .refresh(&token_pair.encrypted_refresh_token, &token_pair.nonce)
+            .await;
+        let issued = token_service.issue(&user_id).await;
+
+        assert!(matches!(
+            refreshed,
+            Err(TokenServiceError::User(UserServiceError::Disabled(_)))
+        ));
+        assert!(matches!(
+            issued,
+            Err(TokenServiceError::User(UserServiceError::Disabled(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_user() {
+        let token_service = token_service();
+        let user_id = Uuid::new_v4();
+
+        let token_pair = token_service.issue(&user_id).await.unwrap();
+
+        token_service.revoke_user(&user_id).await.unwrap();
+
+        let refreshed = token_service
+            .refresh(&token_pair.encrypted_refresh_token, &token_pair.nonce)
+            .await;
+
+        assert!(refreshed.is_err());
+        assert!(
+            token_service
+                .revocation_service
+                .is_revoked(&access_claims(&token_pair))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_refresh_token() {
+        let token_service = token_service();
+
+        let token_pair = token_service.issue(&Uuid::new_v4()).await.unwrap();
+
+        token_service
+            .logout(&access_claims(&token_pair))
+            .await
+            .unwrap();
+
+        let refreshed = token_service
+            .refresh(&token_pair.encrypted_refresh_token, &token_pair.nonce)
+            .await;
+
+        assert!(refreshed.is_err());
+        assert!(
+            token_service
+                .revocation_service
+                .is_revoked(&access_claims(&token_pair))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired() {
+        let token_service = token_service();
+
+        let first = token_service.issue(&Uuid::new_v4()).await.unwrap();
+        token_service.issue(&Uuid::new_v4()).await.unwrap();
+
+        token_service.repo.tokens.lock().unwrap()[0].expires_at = Utc::now();
+
+        assert_eq!(token_service.purge_expired().await.unwrap(), 1);
+
+        let refreshed = token_service
+            .refresh(&first.encrypted_refresh_token, &first.nonce)
+            .await;
+
+        assert!(matches!(
+            refreshed,
+            Err(TokenServiceError::InvalidRefreshToken)
+        ));
+        assert_eq!(token_service.repo.tokens.lock().unwrap().len(), 1);
+    }
+}
@@ -1,12 +1,14 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet, sync::Arc};
 
+use chrono::{DateTime, Duration, Utc};
 use thiserror::Error;
-use tracing::error;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
 
 use crate::{
     api::user::models::{CreateUserDTO, UpdateUserDTO},
     domain::{
-        models::user::User,
+        models::{password_policy::check_password_strength, role::Role, user::User},
         traits::{
             crypto::crypto::ICrypto,
             repositories::{repository::Repository, user_repository::IUserRepository},
@@ -16,9 +18,17 @@ use crate::{
     infrastructure::{storage::database::models::user::UserEntity, utils::password::Argon2Crypto},
 };
 
+const DEFAULT_DELETION_GRACE_DAYS: i64 = 30;
+const DEFAULT_PURGE_INTERVAL_SECONDS: u64 = 5 * 60;
+
 #[derive(Clone)]
 pub struct UserService<Repo> {
     repo: Repo,
+    crypto_driver: Argon2Crypto,
+    /// Ids of disabled users and users waiting for deletion, so
+    /// `auth_middleware` can reject them without a query per request.
+    inactive: Arc<RwLock<HashSet<ID>>>,
+    deletion_grace: Duration,
 }
 
 #[derive(Error, Debug)]
@@ -35,6 +45,15 @@ pub enum UserServiceError {
     #[error("User not found: `{0}`")]
     NotFound(String),
 
+    #[error("Invalid credentials for user: `{0}`")]
+    InvalidCredentials(String),
+
+    #[error("User is disabled: `{0}`")]
+    Disabled(String),
+
+    #[error("User is scheduled for deletion: `{0}`")]
+    PendingDeletion(String),
+
     #[error("Database error: `{0}`")]
     Database(#[from] sqlx::Error),
 
@@ -49,15 +68,28 @@ impl<Repo> UserService<Repo>
 where
     Repo: Repository<Item = User, Entity = UserEntity, Error = sqlx::Error>,
 {
-    pub fn new(repo: Repo) -> Self {
-        Self { repo: repo }
+    pub fn new(repo: Repo, crypto_driver: Argon2Crypto) -> Self {
+        let deletion_grace = Duration::days(
+            std::env::var("ACCOUNT_DELETION_GRACE_DAYS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_DELETION_GRACE_DAYS),
+        );
+
+        Self {
+            repo,
+            crypto_driver,
+            inactive: Arc::new(RwLock::new(HashSet::new())),
+            deletion_grace,
+        }
     }
 
     pub async fn create(&self, params: &CreateUserDTO) -> Result<User, UserServiceError> {
-        let crypto_driver = Argon2Crypto;
+        check_password_strength(&params.password, &params.key)
+            .map_err(UserServiceError::InvalidPassword)?;
 
         let hashed_password =
-            crypto_driver
+            self.crypto_driver
                 .hash(&params.password)
                 .map_err(|error| match &error {
                     _ => {
@@ -137,6 +169,75 @@ where
         Ok(res)
     }
 
+    pub async fn authenticate(
+        &self,
+        key: &str,
+        plain_password: &str,
+    ) -> Result<User, UserServiceError> {
+        let user = self.verify_credentials(key, plain_password).await?;
+
+        if user.is_disabled() {
+            return Err(UserServiceError::Disabled(key.to_string()));
+        }
+
+        if user.is_pending_deletion() {
+            return Err(UserServiceError::PendingDeletion(key.to_string()));
+        }
+
+        if self.crypto_driver.needs_rehash(&user.hashed_password) {
+            return Ok(self.rehash(user, plain_password).await);
+        }
+
+        Ok(user)
+    }
+
+    async fn verify_credentials(
+        &self,
+        key: &str,
+        plain_password: &str,
+    ) -> Result<User, UserServiceError> {
+        let user = self.get_by_key(key).await?;
+
+        self.crypto_driver
+            .verify(plain_password, &user.hashed_password)
+            .map_err(|error| match &error {
+                argon2::password_hash::Error::Password => {
+                    UserServiceError::InvalidCredentials(key.to_string())
+                }
+                _ => {
+                    error!("User crypto error: {:?}", error);
+                    UserServiceError::Crypto(error.to_string())
+                }
+            })?;
+
+        Ok(user)
+    }
+
+    /// Replaces a hash made with outdated Argon2 settings. A failure here
+    /// must not fail the login, the old hash keeps working.
+    async fn rehash(&self, mut user: User, plain_password: &str) -> User {
+        let hashed_password = match self.crypto_driver.hash(plain_password) {
+            Ok(hashed_password) => hashed_password,
+            Err(error) => {
+                warn!("Couldn't rehash password of user {}: {:?}", user.id, error);
+                return user;
+            }
+        };
+
+        user.set_password(hashed_password);
+
+        match self.repo.update_by_id(&user).await {
+            Ok(user) => user,
+            Err(error) => {
+                warn!(
+                    "Couldn't save rehashed password of user {}: {}",
+                    user.id, error
+                );
+                user
+            }
+        }
+    }
+
     pub async fn update_by_id(
         &self,
         id: &ID,
@@ -163,14 +264,267 @@ where
 
         Ok(res)
     }
+
+    /// Sets a new password after checking the current one.
+    pub async fn change_password(
+        &self,
+        id: &ID,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<User, UserServiceError> {
+        let user = self.get_by_id(id).await?;
+
+        self.crypto_driver
+            .verify(current_password, &user.hashed_password)
+            .map_err(|error| match &error {
+                argon2::password_hash::Error::Password => {
+                    UserServiceError::InvalidCredentials(user.key.clone())
+                }
+                _ => {
+                    error!("User crypto error: {:?}", error);
+                    UserServiceError::Crypto(error.to_string())
+                }
+            })?;
+
+        self.set_password(user, new_password).await
+    }
+
+    /// Sets a new password without the current one, the caller has to have
+    /// checked a reset token.
+    pub async fn reset_password(
+        &self,
+        id: &ID,
+        new_password: &str,
+    ) -> Result<User, UserServiceError> {
+        let user = self.get_by_id(id).await?;
+
+        self.set_password(user, new_password).await
+    }
+
+    /// Users whose key or name starts with `search`, with the number of all
+    /// matching users.
+    pub async fn list(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<User>, i64), UserServiceError> {
+        let users = self
+            .repo
+            .select_page(search, limit, offset)
+            .await
+            .map_err(|error| {
+                error!("User DB error: {}", error);
+                error
+            })?;
+
+        let total = self.repo.count(search).await.map_err(|error| {
+            error!("User DB error: {}", error);
+            error
+        })?;
+
+        Ok((users, total))
+    }
+
+    pub async fn disable(&self, id: &ID) -> Result<User, UserServiceError> {
+        let res = self.set_disabled(id, true).await?;
+
+        self.cache_state(&res).await;
+
+        Ok(res)
+    }
+
+    pub async fn enable(&self, id: &ID) -> Result<User, UserServiceError> {
+        let res = self.set_disabled(id, false).await?;
+
+        self.cache_state(&res).await;
+
+        Ok(res)
+    }
+
+    /// Deactivates the account and schedules it for deletion once the grace
+    /// period is over. Scheduling it again keeps the first date.
+    pub async fn schedule_deletion(&self, id: &ID) -> Result<User, UserServiceError> {
+        let user = self.get_by_id(id).await?;
+
+        if user.is_pending_deletion() {
+            return Ok(user);
+        }
+
+        let res = self
+            .set_delete_after(id, Some(Utc::now() + self.deletion_grace))
+            .await?;
+
+        self.cache_state(&res).await;
+
+        Ok(res)
+    }
+
+    /// Cancels a scheduled deletion. The user can't sign in meanwhile, so
+    /// this takes their credentials instead of a token.
+    pub async fn restore(&self, key: &str, plain_password: &str) -> Result<User, UserServiceError> {
+        let user = self.verify_credentials(key, plain_password).await?;
+
+        if !user.is_pending_deletion() {
+            return Ok(user);
+        }
+
+        let res = self.set_delete_after(&user.id, None).await?;
+
+        self.cache_state(&res).await;
+
+        Ok(res)
+    }
+
+    /// Deletes the accounts whose grace period is over, with all their data,
+    /// and reloads the inactive users, which also picks up accounts disabled
+    /// or enabled by other instances.
+    pub async fn purge_scheduled(&self) -> Result<u64, UserServiceError> {
+        let ids = self.repo.delete_due(&Utc::now()).await.map_err(|error| {
+            error!("User DB error: {}", error);
+            error
+        })?;
+
+        self.load_inactive().await?;
+
+        Ok(ids.len() as u64)
+    }
+
+    /// Runs `purge_scheduled` forever, every `ACCOUNT_PURGE_INTERVAL_SECONDS`.
+    pub async fn run_purge(&self) {
+        let seconds = std::env::var("ACCOUNT_PURGE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_PURGE_INTERVAL_SECONDS);
+
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(seconds));
+
+        loop {
+            interval.tick().await;
+
+            match self.purge_scheduled().await {
+                Ok(purged) if purged > 0 => info!("Purged {} deleted accounts", purged),
+                Ok(_) => {}
+                Err(error) => error!("Couldn't purge deleted accounts: {}", error),
+            }
+        }
+    }
+
+    pub async fn set_role(&self, id: &ID, role: Role) -> Result<User, UserServiceError> {
+        let res = self
+            .repo
+            .update_role(id, role)
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => UserServiceError::NotFound(error.to_string()),
+                _ => {
+                    error!("User DB error: {}", error);
+                    UserServiceError::Database(error)
+                }
+            })?;
+
+        Ok(res)
+    }
+
+    /// Replaces the in-memory set of inactive users with the stored one.
+    pub async fn load_inactive(&self) -> Result<(), UserServiceError> {
+        let ids = self.repo.select_inactive_ids().await.map_err(|error| {
+            error!("User DB error: {}", error);
+            error
+        })?;
+
+        debug!("Loaded {} inactive users", ids.len());
+
+        *self.inactive.write().await = ids.into_iter().collect();
+
+        Ok(())
+    }
+
+    /// Whether the user is disabled or waiting for deletion.
+    pub async fn is_inactive(&self, id: &ID) -> bool {
+        self.inactive.read().await.contains(id)
+    }
+
+    async fn cache_state(&self, user: &User) {
+        let mut inactive = self.inactive.write().await;
+
+        if user.is_active() {
+            inactive.remove(&user.id);
+        } else {
+            inactive.insert(user.id);
+        }
+    }
+
+    async fn set_delete_after(
+        &self,
+        id: &ID,
+        delete_after: Option<DateTime<Utc>>,
+    ) -> Result<User, UserServiceError> {
+        let res = self
+            .repo
+            .update_delete_after(id, delete_after.as_ref())
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => UserServiceError::NotFound(error.to_string()),
+                _ => {
+                    error!("User DB error: {}", error);
+                    UserServiceError::Database(error)
+                }
+            })?;
+
+        Ok(res)
+    }
+
+    async fn set_disabled(&self, id: &ID, disabled: bool) -> Result<User, UserServiceError> {
+        let disabled_at = disabled.then(Utc::now);
+
+        let res = self
+            .repo
+            .update_disabled(id, disabled_at.as_ref())
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => UserServiceError::NotFound(error.to_string()),
+                _ => {
+                    error!("User DB error: {}", error);
+                    UserServiceError::Database(error)
+                }
+            })?;
+
+        Ok(res)
+    }
+
+    async fn set_password(
+        &self,
+        mut user: User,
+        new_password: &str,
+    ) -> Result<User, UserServiceError> {
+        check_password_strength(new_password, &user.key)
+            .map_err(UserServiceError::InvalidPassword)?;
+
+        let hashed_password = self.crypto_driver.hash(new_password).map_err(|error| {
+            error!("User crypto error: {:?}", error);
+            UserServiceError::Crypto(error.to_string())
+        })?;
+
+        user.set_password(hashed_password);
+
+        let res = self.repo.update_by_id(&user).await.map_err(|error| {
+            error!("User DB error: {}", error);
+            error
+        })?;
+
+        Ok(res)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
-    use chrono::Utc;
+    use chrono::DateTime;
     use uuid::Uuid;
 
+    use crate::infrastructure::utils::password::Argon2Config;
+
     use super::*;
 
     struct TestUserRepository {
@@ -197,7 +551,10 @@ mod tests {
                 id: *id,
                 key: "faksfjas".to_string(),
                 name: "Mdafasdfd".to_string(),
-                hashed_password: "JJalksf".to_string(),
+                hashed_password: Argon2Crypto::default().hash("hefkajdf").unwrap(),
+                role: Role::User,
+                disabled_at: None,
+                delete_after: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
             };
@@ -213,11 +570,21 @@ mod tests {
     #[async_trait]
     impl IUserRepository for TestUserRepository {
         async fn select_by_key(&self, key: &str) -> Result<Self::Item, Self::Error> {
+            if key == "unknown" {
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            let disabled_at = (key == "disabled").then(Utc::now);
+            let delete_after = (key == "deleted").then(Utc::now);
+
             let test_user = User {
                 id: Uuid::new_v4(),
                 key: key.to_string(),
                 name: "Mdafasdfd".to_string(),
-                hashed_password: "JJalksf".to_string(),
+                hashed_password: Argon2Crypto::default().hash("hefkajdf").unwrap(),
+                role: Role::User,
+                disabled_at,
+                delete_after,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
             };
@@ -231,18 +598,70 @@ mod tests {
         ) -> Result<Self::Item, Self::Error> {
             Ok(updated_params.clone())
         }
+
+        async fn select_page(
+            &self,
+            search: Option<&str>,
+            limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            let user = self.select_by_key(search.unwrap_or("faksfjas")).await?;
+
+            Ok(vec![user; limit as usize])
+        }
+
+        async fn count(&self, _search: Option<&str>) -> Result<i64, Self::Error> {
+            Ok(42)
+        }
+
+        async fn update_disabled(
+            &self,
+            id: &ID,
+            disabled_at: Option<&DateTime<Utc>>,
+        ) -> Result<Self::Item, Self::Error> {
+            let mut user = self.select_by_id(id).await?;
+            user.disabled_at = disabled_at.copied();
+
+            Ok(user)
+        }
+
+        async fn update_role(&self, id: &ID, role: Role) -> Result<Self::Item, Self::Error> {
+            let mut user = self.select_by_id(id).await?;
+            user.role = role;
+
+            Ok(user)
+        }
+
+        async fn update_delete_after(
+            &self,
+            id: &ID,
+            delete_after: Option<&DateTime<Utc>>,
+        ) -> Result<Self::Item, Self::Error> {
+            let mut user = self.select_by_id(id).await?;
+            user.delete_after = delete_after.copied();
+
+            Ok(user)
+        }
+
+        async fn select_inactive_ids(&self) -> Result<Vec<ID>, Self::Error> {
+            Ok(vec![])
+        }
+
+        async fn delete_due(&self, _now: &DateTime<Utc>) -> Result<Vec<ID>, Self::Error> {
+            Ok(vec![])
+        }
     }
 
     #[tokio::test]
     async fn test_create() {
         let repo = TestUserRepository { _db: 12345 };
 
-        let user_service = UserService::new(repo);
+        let user_service = UserService::new(repo, Argon2Crypto::default());
 
         let test_user = CreateUserDTO {
             key: "fsdfsf".to_string(),
             name: "Me".to_string(),
-            password: "hefkajdf".to_string(),
+            password: "hefkajdf42".to_string(),
         };
 
         let res = user_service.create(&test_user).await.unwrap();
@@ -251,4 +670,193 @@ mod tests {
         assert_eq!(res.key, test_user.key);
         assert_ne!(res.hashed_password, test_user.password);
     }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service
+            .authenticate("fsdfsf", "hefkajdf")
+            .await
+            .unwrap();
+
+        assert_eq!(res.key, "fsdfsf");
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_wrong_password() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service.authenticate("fsdfsf", "wrong-password").await;
+
+        assert!(matches!(res, Err(UserServiceError::InvalidCredentials(_))));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_unknown_key() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service.authenticate("unknown", "hefkajdf").await;
+
+        assert!(matches!(res, Err(UserServiceError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_weak_password() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        for password in ["short1", "onlyletters", "Password1", "fsdfsf-123"] {
+            let test_user = CreateUserDTO {
+                key: "fsdfsf".to_string(),
+                name: "Me".to_string(),
+                password: password.to_string(),
+            };
+
+            let res = user_service.create(&test_user).await;
+
+            assert!(
+                matches!(res, Err(UserServiceError::InvalidPassword(_))),
+                "{password}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_change_password() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service
+            .change_password(&Uuid::new_v4(), "hefkajdf", "new-password-1")
+            .await
+            .unwrap();
+
+        assert!(
+            Argon2Crypto::default()
+                .verify("new-password-1", &res.hashed_password)
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_change_password_wrong_current() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service
+            .change_password(&Uuid::new_v4(), "wrong-password", "new-password-1")
+            .await;
+
+        assert!(matches!(res, Err(UserServiceError::InvalidCredentials(_))));
+    }
+
+    #[tokio::test]
+    async fn test_change_password_too_weak() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service
+            .change_password(&Uuid::new_v4(), "hefkajdf", "12345678")
+            .await;
+
+        assert!(matches!(res, Err(UserServiceError::InvalidPassword(_))));
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rehashes_outdated_hash() {
+        let crypto_driver = Argon2Crypto::new(Argon2Config {
+            m_cost: 8192,
+            ..Argon2Config::default()
+        });
+        let user_service = UserService::new(TestUserRepository { _db: 12345 }, crypto_driver);
+
+        let res = user_service
+            .authenticate("fsdfsf", "hefkajdf")
+            .await
+            .unwrap();
+
+        assert!(res.hashed_password.contains("m=8192"));
+        assert!(
+            !user_service
+                .crypto_driver
+                .needs_rehash(&res.hashed_password)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_disabled() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service.authenticate("disabled", "hefkajdf").await;
+
+        assert!(matches!(res, Err(UserServiceError::Disabled(_))));
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_enable() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+        let user_id = Uuid::new_v4();
+
+        let res = user_service.disable(&user_id).await.unwrap();
+
+        assert!(res.is_disabled());
+        assert!(user_service.is_inactive(&user_id).await);
+
+        let res = user_service.enable(&user_id).await.unwrap();
+
+        assert!(!res.is_disabled());
+        assert!(!user_service.is_inactive(&user_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_purge_reloads_inactive() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+        let user_id = Uuid::new_v4();
+
+        user_service.disable(&user_id).await.unwrap();
+
+        // The test store has no inactive users, as if another instance had
+        // enabled the account again.
+        user_service.purge_scheduled().await.unwrap();
+
+        assert!(!user_service.is_inactive(&user_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_deletion() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+        let user_id = Uuid::new_v4();
+
+        let res = user_service.schedule_deletion(&user_id).await.unwrap();
+
+        assert!(res.delete_after.unwrap() > Utc::now() + Duration::days(29));
+        assert!(user_service.is_inactive(&user_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_pending_deletion_is_restored_not_authenticated() {
+        let user_service =
+            UserService::new(TestUserRepository { _db: 12345 }, Argon2Crypto::default());
+
+        let res = user_service.authenticate("deleted", "hefkajdf").await;
+
+        assert!(matches!(res, Err(UserServiceError::PendingDeletion(_))));
+
+        let res = user_service.restore("deleted", "wrong-password").await;
+
+        assert!(matches!(res, Err(UserServiceError::InvalidCredentials(_))));
+
+        let res = user_service.restore("deleted", "hefkajdf").await.unwrap();
+
+        assert!(res.is_active());
+        assert!(!user_service.is_inactive(&res.id).await);
+    }
 }
@@ -1,22 +1,37 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, Duration, Utc};
 use thiserror::Error;
-use tracing::error;
+use tracing::{error, info};
 
 use crate::{
-    api::word_pair::models::CreateWordPairDTO,
+    api::word_pair::models::{CreateWordPairDTO, UpdateWordPairDTO},
     domain::{
-        models::word_pair::WordPair,
+        models::{
+            answer_check::AnswerCheck,
+            session::Direction,
+            word_pair::{
+                DuplicatePolicy, LanguagePairCount, WordPair, WordPairCursor, WordPairFilter,
+                WordPairSearchHit, WordPairSort,
+            },
+        },
         traits::repositories::{repository::Repository, word_pair_repository::IWordPairRepository},
         types::ID,
     },
     infrastructure::{
         storage::database::models::word_pair::WordPairEntity,
-        utils::string::to_title_case::TitleCase,
+        utils::string::{answer_check::check_answer, to_title_case::TitleCase},
     },
 };
 
+const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+const DEFAULT_PURGE_INTERVAL_SECONDS: u64 = 60 * 60;
+const MAX_SEARCH_QUERY_LENGTH: usize = 100;
+
 #[derive(Clone)]
 pub struct WordPairService<Repo> {
     repo: Repo,
+    trash_retention: Duration,
 }
 
 #[derive(Debug, Error)]
@@ -27,6 +42,12 @@ pub enum WordPairServiceError {
     #[error("Word pair not found: `{0}`")]
     NotFound(String),
 
+    #[error("Invalid cursor")]
+    InvalidCursor,
+
+    #[error("Invalid search query: {0}")]
+    InvalidQuery(String),
+
     #[error("Database error: `{0}`")]
     Database(#[from] sqlx::Error),
 
@@ -39,13 +60,79 @@ where
     Repo: Repository<Item = WordPair, Entity = WordPairEntity, Error = sqlx::Error>,
 {
     pub fn new(repo: Repo) -> Self {
-        Self { repo: repo }
+        let trash_retention = Duration::days(
+            std::env::var("WORD_PAIR_TRASH_DAYS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS),
+        );
+
+        Self {
+            repo,
+            trash_retention,
+        }
+    }
+
+    /// Word pairs in the trash are not found.
+    pub async fn get_by_id(&self, id: &ID) -> Result<WordPair, WordPairServiceError> {
+        let res = self
+            .repo
+            .select_by_id(&id)
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => WordPairServiceError::NotFound(error.to_string()),
+                _ => {
+                    error!("User DB error: {}", error);
+                    WordPairServiceError::Database(error.into())
+                }
+            })?;
+
+        if res.is_deleted() {
+            return Err(WordPairServiceError::NotFound(format!(
+                "word pair {} is in the trash",
+                id
+            )));
+        }
+
+        Ok(res)
+    }
+
+    /// When a word pair in the trash is deleted for good.
+    pub fn purge_at(&self, word_pair: &WordPair) -> Option<DateTime<Utc>> {
+        word_pair
+            .deleted_at
+            .map(|deleted_at| deleted_at + self.trash_retention)
+    }
+
+    /// Checks a typed answer against the side of the word pair `direction`
+    /// asks for, the target text unless it is `TargetToSource`.
+    pub fn check_answer(
+        &self,
+        word_pair: &WordPair,
+        answer: &str,
+        direction: Direction,
+        ignore_diacritics: bool,
+    ) -> AnswerCheck {
+        let expected = match direction {
+            Direction::TargetToSource => &word_pair.source_text,
+            _ => &word_pair.target_text,
+        };
+
+        check_answer(answer, expected, ignore_diacritics)
     }
+}
 
+impl<Repo> WordPairService<Repo>
+where
+    Repo: IWordPairRepository<Error = sqlx::Error>,
+{
+    /// Creates the word pair unless the user already has one with the same
+    /// languages and source text, in which case `on_duplicate` decides.
     pub async fn create(
         &self,
         user_id: &ID,
         params: &CreateWordPairDTO,
+        on_duplicate: DuplicatePolicy,
     ) -> Result<WordPair, WordPairServiceError> {
         let word_pair = WordPair::new_simple(
             *user_id,
@@ -55,18 +142,108 @@ where
             params.source_language.clone(),
         );
 
-        let res = self.repo.insert(&word_pair).await.map_err(|error| {
-            error!("WordPair DB error: {}", error);
-            error
-        })?;
+        if let Some(existing) = self.find_same_source(&word_pair).await? {
+            return self
+                .resolve_duplicate(existing, &word_pair, on_duplicate)
+                .await;
+        }
+
+        match self.repo.insert(&word_pair).await {
+            Ok(res) => Ok(res),
+            // A concurrent create got in first, its word pair is the duplicate.
+            Err(error) if is_unique_violation(&error) => {
+                match self.find_same_source(&word_pair).await? {
+                    Some(existing) => {
+                        self.resolve_duplicate(existing, &word_pair, on_duplicate)
+                            .await
+                    }
+                    None => Err(WordPairServiceError::WordPairAlreadyExists(
+                        word_pair.id.to_string(),
+                    )),
+                }
+            }
+            Err(error) => {
+                error!("WordPair DB error: {}", error);
+                Err(error.into())
+            }
+        }
+    }
+
+    async fn resolve_duplicate(
+        &self,
+        mut existing: WordPair,
+        word_pair: &WordPair,
+        on_duplicate: DuplicatePolicy,
+    ) -> Result<WordPair, WordPairServiceError> {
+        match on_duplicate {
+            DuplicatePolicy::Reject => Err(WordPairServiceError::WordPairAlreadyExists(
+                existing.id.to_string(),
+            )),
+            DuplicatePolicy::ReturnExisting => Ok(existing),
+            DuplicatePolicy::Merge => {
+                let target_text = existing.target_text.clone();
+
+                if existing.merge(word_pair).target_text == target_text {
+                    return Ok(existing);
+                }
+
+                self.save(&existing).await
+            }
+        }
+    }
+
+    /// The oldest other active word pair that is a duplicate of this one.
+    async fn find_same_source(
+        &self,
+        word_pair: &WordPair,
+    ) -> Result<Option<WordPair>, WordPairServiceError> {
+        let res = self
+            .repo
+            .select_same_source(word_pair)
+            .await
+            .map_err(|error| {
+                error!("WordPair DB error: {}", error);
+                error
+            })?;
+
+        Ok(res.into_iter().next())
+    }
+
+    async fn ensure_unique(&self, word_pair: &WordPair) -> Result<(), WordPairServiceError> {
+        match self.find_same_source(word_pair).await? {
+            Some(existing) => Err(WordPairServiceError::WordPairAlreadyExists(
+                existing.id.to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    async fn save(&self, word_pair: &WordPair) -> Result<WordPair, WordPairServiceError> {
+        let res = self
+            .repo
+            .update_by_id(word_pair)
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => WordPairServiceError::NotFound(error.to_string()),
+                _ if is_unique_violation(&error) => {
+                    WordPairServiceError::WordPairAlreadyExists(word_pair.id.to_string())
+                }
+                _ => {
+                    error!("WordPair DB error: {}", error);
+                    WordPairServiceError::Database(error)
+                }
+            })?;
 
         Ok(res)
     }
 
-    pub async fn get_by_id(&self, id: &ID) -> Result<WordPair, WordPairServiceError> {
+    pub async fn get_by_user_id(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<WordPair>, WordPairServiceError> {
         let res = self
             .repo
-            .select_by_id(&id)
+            .select_by_user_id(&user_id)
             .await
             .map_err(|error| match &error {
                 sqlx::Error::RowNotFound => WordPairServiceError::NotFound(error.to_string()),
@@ -79,53 +256,321 @@ where
         Ok(res)
     }
 
-    pub async fn delete_by_id(&self, id: &ID) -> Result<(), WordPairServiceError> {
-        self.repo
-            .delete_by_id(&id)
+    /// One page of the user's word pairs, the cursor of the next page if
+    /// there is one and the number of all matching word pairs.
+    pub async fn list(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        sort: WordPairSort,
+        after: Option<&WordPairCursor>,
+        limit: i64,
+    ) -> Result<(Vec<WordPair>, Option<WordPairCursor>, i64), WordPairServiceError> {
+        if after.is_some_and(|cursor| !sort.accepts(cursor)) {
+            return Err(WordPairServiceError::InvalidCursor);
+        }
+
+        // One extra row tells whether there is a next page.
+        let mut word_pairs = self
+            .repo
+            .select_page(user_id, filter, sort, after, limit + 1)
+            .await
+            .map_err(|error| {
+                error!("WordPair DB error: {}", error);
+                error
+            })?;
+
+        let next_cursor = match word_pairs.len() as i64 > limit {
+            true => {
+                word_pairs.truncate(limit as usize);
+                word_pairs.last().map(|word_pair| sort.cursor(word_pair))
+            }
+            false => None,
+        };
+
+        let total = self.repo.count(user_id, filter).await.map_err(|error| {
+            error!("WordPair DB error: {}", error);
+            error
+        })?;
+
+        Ok((word_pairs, next_cursor, total))
+    }
+
+    /// The user's word pairs matching `query`, best match first.
+    pub async fn search(
+        &self,
+        user_id: &ID,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<WordPairSearchHit>, WordPairServiceError> {
+        let query = query.trim();
+
+        if query.is_empty() {
+            return Err(WordPairServiceError::InvalidQuery(
+                "query is empty".to_string(),
+            ));
+        }
+
+        if query.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+            return Err(WordPairServiceError::InvalidQuery(format!(
+                "query is longer than {} characters",
+                MAX_SEARCH_QUERY_LENGTH
+            )));
+        }
+
+        let res = self
+            .repo
+            .search(user_id, query, limit)
+            .await
+            .map_err(|error| {
+                error!("WordPair DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    pub async fn count_by_user_id(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<LanguagePairCount>, WordPairServiceError> {
+        let res = self
+            .repo
+            .count_by_language_pair(user_id)
+            .await
+            .map_err(|error| {
+                error!("WordPair DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Changes the given texts and languages, keeping `created_at`.
+    pub async fn update_by_id(
+        &self,
+        id: &ID,
+        params: &UpdateWordPairDTO,
+    ) -> Result<WordPair, WordPairServiceError> {
+        let word_pair = self.check_update(id, params).await?;
+
+        self.save(&word_pair).await
+    }
+
+    /// The word pair with the changes applied but not saved. Fails like
+    /// `update_by_id` would if the changes make it a duplicate.
+    pub async fn check_update(
+        &self,
+        id: &ID,
+        params: &UpdateWordPairDTO,
+    ) -> Result<WordPair, WordPairServiceError> {
+        let mut word_pair = self.get_by_id(id).await?;
+
+        word_pair.update(
+            params.target_text.clone(),
+            params.source_text.clone(),
+            params.target_language.clone(),
+            params.source_language.clone(),
+        );
+
+        self.ensure_unique(&word_pair).await?;
+
+        Ok(word_pair)
+    }
+
+    /// Moves the word pair to the trash, it can be restored until it is
+    /// purged.
+    pub async fn delete_by_id(&self, id: &ID) -> Result<WordPair, WordPairServiceError> {
+        let res = self
+            .repo
+            .trash_by_id(id, &Utc::now())
             .await
             .map_err(|error| match &error {
                 sqlx::Error::RowNotFound => WordPairServiceError::NotFound(error.to_string()),
                 _ => {
-                    error!("User DB error: {}", error);
-                    WordPairServiceError::Database(error.into())
+                    error!("WordPair DB error: {}", error);
+                    WordPairServiceError::Database(error)
                 }
             })?;
 
-        Ok(())
+        Ok(res)
     }
-}
 
-impl<Repo> WordPairService<Repo>
-where
-    Repo: IWordPairRepository<Error = sqlx::Error>,
-{
-    pub async fn get_by_user_id(
+    pub async fn get_trash_by_user_id(
         &self,
         user_id: &ID,
     ) -> Result<Vec<WordPair>, WordPairServiceError> {
         let res = self
             .repo
-            .select_by_user_id(&user_id)
+            .select_trash_by_user_id(user_id)
+            .await
+            .map_err(|error| {
+                error!("WordPair DB error: {}", error);
+                error
+            })?;
+
+        Ok(res)
+    }
+
+    /// Fails with `WordPairAlreadyExists` if an active duplicate was added
+    /// while the word pair was in the trash.
+    pub async fn restore(&self, user_id: &ID, id: &ID) -> Result<WordPair, WordPairServiceError> {
+        let word_pair = self
+            .repo
+            .select_by_id(id)
             .await
             .map_err(|error| match &error {
                 sqlx::Error::RowNotFound => WordPairServiceError::NotFound(error.to_string()),
                 _ => {
-                    error!("User DB error: {}", error);
-                    WordPairServiceError::Database(error.into())
+                    error!("WordPair DB error: {}", error);
+                    WordPairServiceError::Database(error)
+                }
+            })?;
+
+        if word_pair.is_deleted() && word_pair.user_id == *user_id {
+            self.ensure_unique(&word_pair).await?;
+        }
+
+        let res = self
+            .repo
+            .restore_by_id(id, user_id)
+            .await
+            .map_err(|error| match &error {
+                sqlx::Error::RowNotFound => WordPairServiceError::NotFound(error.to_string()),
+                _ if is_unique_violation(&error) => {
+                    WordPairServiceError::WordPairAlreadyExists(id.to_string())
+                }
+                _ => {
+                    error!("WordPair DB error: {}", error);
+                    WordPairServiceError::Database(error)
                 }
             })?;
 
         Ok(res)
     }
+
+    /// The user's active word pairs grouped by duplicate, oldest first in
+    /// each group.
+    pub async fn find_duplicates(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<Vec<WordPair>>, WordPairServiceError> {
+        let res = self
+            .repo
+            .select_duplicates(user_id)
+            .await
+            .map_err(|error| {
+                error!("WordPair DB error: {}", error);
+                error
+            })?;
+
+        let mut groups: Vec<Vec<WordPair>> = vec![];
+
+        for word_pair in res.into_iter() {
+            match groups.last_mut() {
+                Some(group) if group[0].is_duplicate_of(&word_pair) => group.push(word_pair),
+                _ => groups.push(vec![word_pair]),
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Merges every group of duplicates into its oldest word pair and moves
+    /// the others to the trash. Returns the kept word pairs and how many
+    /// were trashed.
+    pub async fn merge_duplicates(
+        &self,
+        user_id: &ID,
+    ) -> Result<(Vec<WordPair>, u64), WordPairServiceError> {
+        let mut kept = vec![];
+        let mut trashed = 0;
+
+        for group in self.find_duplicates(user_id).await? {
+            let mut group = group.into_iter();
+
+            let Some(mut word_pair) = group.next() else {
+                continue;
+            };
+
+            let duplicates: Vec<WordPair> = group.collect();
+
+            for duplicate in duplicates.iter() {
+                word_pair.merge(duplicate);
+            }
+
+            let word_pair = self.save(&word_pair).await?;
+
+            for duplicate in duplicates.iter() {
+                self.delete_by_id(&duplicate.id).await?;
+                trashed += 1;
+            }
+
+            kept.push(word_pair);
+        }
+
+        info!(
+            "Merged {} duplicate word pairs of user {}",
+            trashed, user_id
+        );
+
+        Ok((kept, trashed))
+    }
+
+    /// Deletes the word pairs that have been in the trash for longer than
+    /// `WORD_PAIR_TRASH_DAYS`.
+    pub async fn purge_trash(&self) -> Result<u64, WordPairServiceError> {
+        let purged = self
+            .repo
+            .delete_trashed(&(Utc::now() - self.trash_retention))
+            .await
+            .map_err(|error| {
+                error!("WordPair DB error: {}", error);
+                error
+            })?;
+
+        Ok(purged)
+    }
+
+    /// Runs `purge_trash` forever, every `WORD_PAIR_PURGE_INTERVAL_SECONDS`.
+    pub async fn run_purge(&self) {
+        let seconds = std::env::var("WORD_PAIR_PURGE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_PURGE_INTERVAL_SECONDS);
+
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(seconds));
+
+        loop {
+            interval.tick().await;
+
+            match self.purge_trash().await {
+                Ok(purged) if purged > 0 => info!("Purged {} word pairs from the trash", purged),
+                Ok(_) => {}
+                Err(error) => error!("Couldn't purge word pair trash: {}", error),
+            }
+        }
+    }
+}
+
+/// The unique index on the source key rejected the row, a concurrent
+/// request stored a duplicate after the check.
+fn is_unique_violation(error: &sqlx::Error) -> bool {
+    matches!(error, sqlx::Error::Database(error) if error.code() == Some(Cow::Borrowed("23505")))
 }
 
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
-    use chrono::Utc;
     use uuid::Uuid;
 
-    use crate::infrastructure::storage::database::models::word_pair::WordPairEntity;
+    use crate::{
+        domain::models::word_pair::{HIGHLIGHT_END, HIGHLIGHT_START},
+        infrastructure::{
+            storage::database::models::word_pair::WordPairEntity,
+            utils::string::normalize_text::normalize_text,
+        },
+    };
 
     use super::*;
 
@@ -157,8 +602,14 @@ mod tests {
                 target_language: "de".to_string(),
                 source_language: "en".to_string(),
                 created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: (*id == Uuid::max()).then(Utc::now),
             };
 
+            if id.is_nil() {
+                return Err(sqlx::Error::RowNotFound);
+            }
+
             Ok(test_word_pair)
         }
 
@@ -178,8 +629,196 @@ mod tests {
                 target_language: "de".to_string(),
                 source_language: "en".to_string(),
                 created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
             }])
         }
+
+        async fn select_page(
+            &self,
+            user_id: &ID,
+            _filter: &WordPairFilter,
+            _sort: WordPairSort,
+            _after: Option<&WordPairCursor>,
+            limit: i64,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            let word_pairs = ["Eins", "Zwei", "Drei"]
+                .into_iter()
+                .map(|target_text| {
+                    WordPair::new_simple(
+                        *user_id,
+                        target_text.to_string(),
+                        target_text.to_string(),
+                        "de".to_string(),
+                        "en".to_string(),
+                    )
+                })
+                .take(limit as usize)
+                .collect();
+
+            Ok(word_pairs)
+        }
+
+        async fn search(
+            &self,
+            user_id: &ID,
+            query: &str,
+            limit: i64,
+        ) -> Result<Vec<WordPairSearchHit>, Self::Error> {
+            let query = normalize_text(query);
+
+            let highlight = |text: &str| {
+                let start = text.to_lowercase().find(&query)?;
+                let end = start + query.len();
+
+                Some(format!(
+                    "{}{HIGHLIGHT_START}{}{HIGHLIGHT_END}{}",
+                    &text[..start],
+                    &text[start..end],
+                    &text[end..]
+                ))
+            };
+
+            let mut hits: Vec<WordPairSearchHit> = [("Hallo", "Hello"), ("Haus", "House")]
+                .into_iter()
+                .map(|(target_text, source_text)| {
+                    WordPair::new_simple(
+                        *user_id,
+                        target_text.to_string(),
+                        source_text.to_string(),
+                        "de".to_string(),
+                        "en".to_string(),
+                    )
+                })
+                .filter_map(|word_pair| {
+                    let source_highlight = highlight(&word_pair.source_text);
+                    let target_highlight = highlight(&word_pair.target_text);
+
+                    let rank = [&source_highlight, &target_highlight]
+                        .iter()
+                        .filter(|highlight| highlight.is_some())
+                        .count() as f32;
+
+                    (rank > 0.0).then(|| WordPairSearchHit {
+                        rank,
+                        source_highlight: source_highlight
+                            .unwrap_or_else(|| word_pair.source_text.clone()),
+                        target_highlight: target_highlight
+                            .unwrap_or_else(|| word_pair.target_text.clone()),
+                        word_pair,
+                    })
+                })
+                .collect();
+
+            hits.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+            hits.truncate(limit as usize);
+
+            Ok(hits)
+        }
+
+        async fn count(&self, _user_id: &ID, _filter: &WordPairFilter) -> Result<i64, Self::Error> {
+            Ok(3)
+        }
+
+        async fn count_by_language_pair(
+            &self,
+            _user_id: &ID,
+        ) -> Result<Vec<LanguagePairCount>, Self::Error> {
+            Ok(vec![LanguagePairCount {
+                source_language: "en".to_string(),
+                target_language: "de".to_string(),
+                count: 1,
+            }])
+        }
+
+        async fn update_by_id(
+            &self,
+            updated_word_pair: &Self::Item,
+        ) -> Result<Self::Item, Self::Error> {
+            Ok(updated_word_pair.clone())
+        }
+
+        async fn select_same_source(
+            &self,
+            word_pair: &Self::Item,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            if word_pair.source_key() != "duplicate" {
+                return Ok(vec![]);
+            }
+
+            Ok(vec![WordPair::new(
+                word_pair.user_id,
+                "Doppelt".to_string(),
+                "Duplicate".to_string(),
+                word_pair.target_language.clone(),
+                word_pair.source_language.clone(),
+                Some(Utc::now() - Duration::days(1)),
+            )])
+        }
+
+        async fn select_duplicates(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+            let word_pair = |target_text: &str, source_text: &str| {
+                WordPair::new(
+                    *user_id,
+                    target_text.to_string(),
+                    source_text.to_string(),
+                    "de".to_string(),
+                    "en".to_string(),
+                    None,
+                )
+            };
+
+            Ok(vec![
+                word_pair("Doppelt", "Duplicate"),
+                word_pair("Duplikat", "duplicate "),
+                word_pair("Doppelt", "Duplicate"),
+                word_pair("Kopie", "Copy"),
+                word_pair("Kopie", "Copy"),
+            ])
+        }
+
+        async fn select_trash_by_user_id(
+            &self,
+            _user_id: &ID,
+        ) -> Result<Vec<Self::Item>, Self::Error> {
+            Ok(vec![])
+        }
+
+        async fn trash_by_id(
+            &self,
+            id: &ID,
+            deleted_at: &DateTime<Utc>,
+        ) -> Result<Self::Item, Self::Error> {
+            let mut word_pair = self.select_by_id(id).await?;
+
+            if word_pair.is_deleted() {
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            word_pair.deleted_at = Some(*deleted_at);
+
+            Ok(word_pair)
+        }
+
+        async fn restore_by_id(&self, id: &ID, user_id: &ID) -> Result<Self::Item, Self::Error> {
+            let mut word_pair = self.select_by_id(id).await?;
+
+            if !word_pair.is_deleted() {
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            word_pair.user_id = *user_id;
+            word_pair.deleted_at = None;
+
+            Ok(word_pair)
+        }
+
+        async fn delete_trashed(
+            &self,
+            _deleted_before: &DateTime<Utc>,
+        ) -> Result<u64, Self::Error> {
+            Ok(0)
+        }
     }
 
     #[tokio::test]
@@ -197,7 +836,7 @@ mod tests {
         };
 
         let res = word_pair_service
-            .create(&test_user_id, &test_params)
+            .create(&test_user_id, &test_params, DuplicatePolicy::Reject)
             .await
             .unwrap();
 
@@ -219,7 +858,7 @@ mod tests {
         };
 
         let res = word_pair_service
-            .create(&test_user_id, &test_params)
+            .create(&test_user_id, &test_params, DuplicatePolicy::Reject)
             .await
             .unwrap();
 
@@ -248,8 +887,271 @@ mod tests {
             target_language: "de".to_string(),
             source_language: "en".to_string(),
             created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
         }];
 
         assert_eq!(res[0].user_id, val[0].user_id);
     }
+
+    #[tokio::test]
+    async fn test_delete_moves_to_trash() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let res = word_pair_service
+            .delete_by_id(&Uuid::new_v4())
+            .await
+            .unwrap();
+
+        assert!(res.is_deleted());
+        assert_eq!(
+            word_pair_service.purge_at(&res),
+            res.deleted_at
+                .map(|deleted_at| deleted_at + Duration::days(30))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_missing_or_trashed_is_not_found() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        for id in [Uuid::nil(), Uuid::max()] {
+            let res = word_pair_service.delete_by_id(&id).await;
+
+            assert!(
+                matches!(res, Err(WordPairServiceError::NotFound(_))),
+                "{id}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trashed_is_hidden_and_restorable() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+        let user_id = Uuid::new_v4();
+
+        let res = word_pair_service.get_by_id(&Uuid::max()).await;
+
+        assert!(matches!(res, Err(WordPairServiceError::NotFound(_))));
+
+        let res = word_pair_service
+            .restore(&user_id, &Uuid::max())
+            .await
+            .unwrap();
+
+        assert!(!res.is_deleted());
+
+        let res = word_pair_service.restore(&user_id, &Uuid::new_v4()).await;
+
+        assert!(matches!(res, Err(WordPairServiceError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let params = UpdateWordPairDTO {
+            target_text: Some("guten tag".to_string()),
+            source_text: None,
+            target_language: None,
+            source_language: Some("EN".to_string()),
+            retranslate: false,
+        };
+
+        let res = word_pair_service
+            .update_by_id(&Uuid::new_v4(), &params)
+            .await
+            .unwrap();
+
+        assert_eq!(res.target_text, "Guten tag");
+        assert_eq!(res.source_text, "Hello");
+        assert_eq!(res.source_language, "en");
+        assert!(res.updated_at >= res.created_at);
+
+        let res = word_pair_service.update_by_id(&Uuid::max(), &params).await;
+
+        assert!(matches!(res, Err(WordPairServiceError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_check_update() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let mut params = UpdateWordPairDTO {
+            target_text: None,
+            source_text: Some("duplicate".to_string()),
+            target_language: None,
+            source_language: None,
+            retranslate: true,
+        };
+
+        let res = word_pair_service
+            .check_update(&Uuid::new_v4(), &params)
+            .await;
+
+        assert!(matches!(
+            res,
+            Err(WordPairServiceError::WordPairAlreadyExists(_))
+        ));
+
+        params.source_text = Some("good day".to_string());
+
+        let res = word_pair_service
+            .check_update(&Uuid::new_v4(), &params)
+            .await
+            .unwrap();
+
+        assert_eq!(res.source_text, "Good day");
+        assert_eq!(res.target_text, "Hallo");
+    }
+
+    #[tokio::test]
+    async fn test_create_duplicate() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let user_id = Uuid::new_v4();
+        let params = CreateWordPairDTO {
+            target_text: "duplikat".to_string(),
+            source_text: "  DUPLICATE ".to_string(),
+            target_language: "de".to_string(),
+            source_language: "en".to_string(),
+        };
+
+        let res = word_pair_service
+            .create(&user_id, &params, DuplicatePolicy::Reject)
+            .await;
+
+        assert!(matches!(
+            res,
+            Err(WordPairServiceError::WordPairAlreadyExists(_))
+        ));
+
+        let res = word_pair_service
+            .create(&user_id, &params, DuplicatePolicy::ReturnExisting)
+            .await
+            .unwrap();
+
+        assert_eq!(res.target_text, "Doppelt");
+        assert_eq!(res.updated_at, res.created_at);
+
+        let res = word_pair_service
+            .create(&user_id, &params, DuplicatePolicy::Merge)
+            .await
+            .unwrap();
+
+        assert_eq!(res.target_text, "Doppelt; Duplikat");
+        assert!(res.updated_at > res.created_at);
+    }
+
+    #[tokio::test]
+    async fn test_merge_duplicates() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let user_id = Uuid::new_v4();
+
+        let groups = word_pair_service.find_duplicates(&user_id).await.unwrap();
+
+        assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 2]);
+
+        let (kept, trashed) = word_pair_service.merge_duplicates(&user_id).await.unwrap();
+
+        assert_eq!(trashed, 3);
+        assert_eq!(kept[0].target_text, "Doppelt; Duplikat");
+        assert_eq!(kept[1].target_text, "Kopie");
+    }
+
+    #[tokio::test]
+    async fn test_list() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let user_id = Uuid::new_v4();
+        let filter = WordPairFilter::default();
+
+        let (word_pairs, next_cursor, total) = word_pair_service
+            .list(&user_id, &filter, WordPairSort::SourceText, None, 2)
+            .await
+            .unwrap();
+
+        assert_eq!(word_pairs.len(), 2);
+        assert_eq!(total, 3);
+
+        let next_cursor = next_cursor.unwrap();
+
+        assert_eq!(next_cursor.key, "Zwei");
+        assert_eq!(next_cursor.id, word_pairs[1].id);
+        assert_eq!(
+            WordPairCursor::decode(&next_cursor.encode()),
+            Some(next_cursor.clone())
+        );
+
+        let (word_pairs, next_cursor, _) = word_pair_service
+            .list(
+                &user_id,
+                &filter,
+                WordPairSort::SourceText,
+                Some(&next_cursor),
+                3,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(word_pairs.len(), 3);
+        assert!(next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_rejects_cursor_of_other_order() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let cursor = WordPairCursor {
+            sort: WordPairSort::SourceText,
+            key: "Zwei".to_string(),
+            id: Uuid::new_v4(),
+        };
+
+        for sort in [WordPairSort::Newest, WordPairSort::TargetText] {
+            let res = word_pair_service
+                .list(
+                    &Uuid::new_v4(),
+                    &WordPairFilter::default(),
+                    sort,
+                    Some(&cursor),
+                    10,
+                )
+                .await;
+
+            assert!(
+                matches!(res, Err(WordPairServiceError::InvalidCursor)),
+                "{sort:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let word_pair_service = WordPairService::new(TestWordPairRepository { _db: 12345 });
+
+        let user_id = Uuid::new_v4();
+
+        let res = word_pair_service
+            .search(&user_id, " HO ", 10)
+            .await
+            .unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].word_pair.source_text, "House");
+        assert_eq!(res[0].source_highlight, "<mark>Ho</mark>use");
+        assert_eq!(res[0].target_highlight, "Haus");
+
+        let res = word_pair_service.search(&user_id, "h", 1).await.unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].rank, 2.0);
+
+        for query in ["   ", &"a".repeat(101)] {
+            let res = word_pair_service.search(&user_id, query, 10).await;
+
+            assert!(matches!(res, Err(WordPairServiceError::InvalidQuery(_))));
+        }
+    }
 }
@@ -0,0 +1,30 @@
+use crate::{AppState, domain::models::role::Role};
+
+const USAGE: &str =
+    "Usage: backend password-reset <user key>\n       backend set-role <user key> <user|admin>";
+
+/// One-off maintenance commands, run as `backend <command> [args]` instead of
+/// starting the server.
+pub async fn run(state: &AppState, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    match args {
+        [command, key] if command == "password-reset" => {
+            let (token, reset_token) = state.password_reset_service.issue_by_key(key).await?;
+
+            println!("Password reset token for `{}`: {}", key, token);
+            println!("Expires at {}", reset_token.expires_at);
+
+            Ok(())
+        }
+        [command, key, role] if command == "set-role" => {
+            let role: Role = role.parse()?;
+
+            let user = state.user_service.get_by_key(key).await?;
+            state.user_service.set_role(&user.id, role).await?;
+
+            println!("Role of `{}` set to {}", key, role.as_str());
+
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::review::Grade;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    /// A typo away from the expected text.
+    Almost,
+    Wrong,
+}
+
+impl Verdict {
+    /// The grade a typed answer earns in a review.
+    pub fn grade(&self) -> Grade {
+        match self {
+            Self::Correct => Grade::Good,
+            Self::Almost => Grade::Hard,
+            Self::Wrong => Grade::Again,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffKind {
+    Same,
+    /// In the expected text but not in the answer.
+    Missing,
+    /// In the answer but not in the expected text.
+    Extra,
+}
+
+/// A run of characters of the normalized texts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffSegment {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnswerCheck {
+    pub verdict: Verdict,
+    /// The accepted text closest to the answer.
+    pub expected: String,
+    /// Edits between the normalized answer and the normalized expected text.
+    pub distance: usize,
+    /// How to get from the answer to the expected text, empty unless the
+    /// verdict is `Almost`.
+    pub diff: Vec<DiffSegment>,
+}
@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::types::ID;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    #[default]
+    ReadOnly,
+    ReadWrite,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::ReadWrite => "read_write",
+        }
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "read_only" => Ok(Self::ReadOnly),
+            "read_write" => Ok(Self::ReadWrite),
+            _ => Err(format!("Unknown API key scope: {}", value)),
+        }
+    }
+}
+
+/// Long-lived personal credential for bots and scripts. Only the hash is
+/// stored, `prefix` is kept in plain text so the owner can tell keys apart.
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: ID,
+    pub user_id: ID,
+
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scope: ApiKeyScope,
+
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn new(
+        user_id: ID,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        scope: ApiKeyScope,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name,
+            prefix,
+            key_hash,
+            scope,
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::{fmt, net::IpAddr};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::types::ID;
+
+/// What failed logins are counted against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptSubject {
+    Key(String),
+    Ip(IpAddr),
+}
+
+impl fmt::Display for AttemptSubject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "key:{}", key),
+            Self::Ip(ip) => write!(f, "ip:{}", ip),
+        }
+    }
+}
+
+/// Failed logins of one subject since the attempt window last started over.
+#[derive(Debug, Clone)]
+pub struct LoginAttempts {
+    pub subject: String,
+    pub failures: u32,
+    pub last_failure_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+/// Audit record written every time a subject gets locked out.
+#[derive(Debug, Clone)]
+pub struct Lockout {
+    pub id: ID,
+    pub subject: String,
+    pub failures: u32,
+    pub locked_until: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Lockout {
+    pub fn new(subject: String, failures: u32, locked_until: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            subject,
+            failures,
+            locked_until,
+            created_at: Utc::now(),
+        }
+    }
+}
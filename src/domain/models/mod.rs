@@ -1,3 +1,17 @@
+pub mod answer_check;
+pub mod api_key;
+pub mod login_attempt;
+pub mod password_policy;
+pub mod password_reset_token;
+pub mod permission;
+pub mod quiz;
+pub mod refresh_token;
+pub mod review;
+pub mod revocation;
+pub mod role;
+pub mod session;
+pub mod stats;
+pub mod token;
 pub mod translate;
 pub mod user;
 pub mod word_pair;
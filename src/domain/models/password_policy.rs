@@ -0,0 +1,58 @@
+const MIN_LENGTH: usize = 8;
+const MAX_LENGTH: usize = 128;
+const MIN_CHARACTER_CLASSES: usize = 2;
+
+const COMMON_PASSWORDS: [&str; 10] = [
+    "password",
+    "password1",
+    "12345678",
+    "123456789",
+    "11111111",
+    "qwerty123",
+    "qwertyuiop",
+    "1q2w3e4r",
+    "abc12345",
+    "iloveyou",
+];
+
+/// Strength rules for new passwords. The error is the reason the password
+/// was rejected and can be shown to the user.
+pub fn check_password_strength(password: &str, key: &str) -> Result<(), String> {
+    let length = password.chars().count();
+
+    if length < MIN_LENGTH {
+        return Err(format!("must be at least {} characters", MIN_LENGTH));
+    }
+
+    if length > MAX_LENGTH {
+        return Err(format!("must be at most {} characters", MAX_LENGTH));
+    }
+
+    let character_classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_numeric()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .iter()
+    .filter(|present| **present)
+    .count();
+
+    if character_classes < MIN_CHARACTER_CLASSES {
+        return Err(
+            "must mix at least two of lowercase, uppercase, digits and symbols".to_string(),
+        );
+    }
+
+    let lowercase = password.to_lowercase();
+
+    if !key.is_empty() && lowercase.contains(&key.to_lowercase()) {
+        return Err("must not contain the user key".to_string());
+    }
+
+    if COMMON_PASSWORDS.contains(&lowercase.as_str()) {
+        return Err("is too common".to_string());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::types::ID;
+
+/// One-time token that allows setting a password without the current one.
+/// Only the hash is stored, the token itself is handed out once.
+#[derive(Debug, Clone)]
+pub struct PasswordResetToken {
+    pub id: ID,
+    pub user_id: ID,
+
+    pub token_hash: String,
+
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PasswordResetToken {
+    pub fn new(user_id: ID, token_hash: String, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            token_hash,
+            expires_at,
+            used_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::{collections::HashSet, fmt};
+
+use crate::domain::models::{api_key::ApiKeyScope, role::Role};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    ProfileRead,
+    ProfileWrite,
+    WordPairsRead,
+    WordPairsWrite,
+    Translate,
+    UsersAdmin,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 6] = [
+        Self::ProfileRead,
+        Self::ProfileWrite,
+        Self::WordPairsRead,
+        Self::WordPairsWrite,
+        Self::Translate,
+        Self::UsersAdmin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ProfileRead => "profile:read",
+            Self::ProfileWrite => "profile:write",
+            Self::WordPairsRead => "wordpairs:read",
+            Self::WordPairsWrite => "wordpairs:write",
+            Self::Translate => "translate",
+            Self::UsersAdmin => "users:admin",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What the caller of a request is allowed to do.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Permissions(HashSet<Permission>);
+
+impl Permissions {
+    /// Permissions of the role, narrowed down to the API key scope when the
+    /// request was made with a key.
+    pub fn granted(role: Role, api_key_scope: Option<ApiKeyScope>) -> Self {
+        let permissions = Self::for_role(role);
+
+        match api_key_scope {
+            Some(scope) => permissions.intersection(&Self::for_api_key_scope(scope)),
+            None => permissions,
+        }
+    }
+
+    pub fn for_role(role: Role) -> Self {
+        match role {
+            Role::User => Self::from_iter(
+                Permission::ALL
+                    .into_iter()
+                    .filter(|permission| *permission != Permission::UsersAdmin),
+            ),
+            Role::Admin => Self::from_iter(Permission::ALL),
+        }
+    }
+
+    pub fn for_api_key_scope(scope: ApiKeyScope) -> Self {
+        match scope {
+            ApiKeyScope::ReadOnly => {
+                Self::from_iter([Permission::ProfileRead, Permission::WordPairsRead])
+            }
+            ApiKeyScope::ReadWrite => Self::from_iter([
+                Permission::ProfileRead,
+                Permission::ProfileWrite,
+                Permission::WordPairsRead,
+                Permission::WordPairsWrite,
+                Permission::Translate,
+            ]),
+        }
+    }
+
+    pub fn contains(&self, permission: Permission) -> bool {
+        self.0.contains(&permission)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(self.0.intersection(&other.0).copied().collect())
+    }
+}
+
+impl FromIterator<Permission> for Permissions {
+    fn from_iter<T: IntoIterator<Item = Permission>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
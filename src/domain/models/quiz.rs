@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::types::ID;
+
+/// A multiple-choice question: the source text of a word pair with its
+/// target text among look-alike target texts of other word pairs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuizQuestion {
+    pub position: i32,
+    pub word_pair_id: ID,
+
+    pub prompt: String,
+    pub options: Vec<String>,
+    /// Index of the correct option.
+    pub answer_index: i32,
+    /// Index of the option picked when the quiz was graded.
+    pub selected: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quiz {
+    pub id: ID,
+    pub user_id: ID,
+
+    /// The same seed draws the same quiz from the same word pairs.
+    pub seed: u64,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub questions: Vec<QuizQuestion>,
+
+    pub created_at: DateTime<Utc>,
+    pub graded_at: Option<DateTime<Utc>>,
+}
+
+impl Quiz {
+    pub fn new(
+        user_id: ID,
+        seed: u64,
+        source_language: Option<String>,
+        target_language: Option<String>,
+        questions: Vec<QuizQuestion>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            seed,
+            source_language,
+            target_language,
+            questions,
+            created_at: Utc::now(),
+            graded_at: None,
+        }
+    }
+
+    pub fn is_graded(&self) -> bool {
+        self.graded_at.is_some()
+    }
+
+    /// Keeps the picked options with the questions and marks the quiz as
+    /// graded. Answers are matched to questions the same way as in `grade`.
+    pub fn submit(&mut self, answers: &[QuizAnswer], graded_at: DateTime<Utc>) {
+        let selected = selected_options(answers);
+
+        for question in &mut self.questions {
+            question.selected = selected.get(&question.position).copied();
+        }
+
+        self.graded_at = Some(graded_at);
+    }
+
+    /// The result of the submitted answers, `None` until the quiz is graded.
+    pub fn result(&self) -> Option<QuizResult> {
+        self.graded_at?;
+
+        let answers: Vec<QuizAnswer> = self
+            .questions
+            .iter()
+            .filter_map(|question| {
+                question.selected.map(|option| QuizAnswer {
+                    position: question.position,
+                    option,
+                })
+            })
+            .collect();
+
+        Some(self.grade(&answers))
+    }
+
+    /// Unanswered questions count as wrong. Answers to unknown positions
+    /// are ignored, the last answer to a position wins.
+    pub fn grade(&self, answers: &[QuizAnswer]) -> QuizResult {
+        let selected = selected_options(answers);
+
+        let answers: Vec<QuizAnswerResult> = self
+            .questions
+            .iter()
+            .map(|question| {
+                let selected = selected.get(&question.position).copied();
+
+                QuizAnswerResult {
+                    position: question.position,
+                    selected,
+                    answer_index: question.answer_index,
+                    is_correct: selected == Some(question.answer_index),
+                }
+            })
+            .collect();
+
+        let correct = answers.iter().filter(|answer| answer.is_correct).count() as i64;
+
+        QuizResult {
+            quiz_id: self.id,
+            questions: self.questions.len() as i64,
+            answered: answers
+                .iter()
+                .filter(|answer| answer.selected.is_some())
+                .count() as i64,
+            correct,
+            score: match self.questions.is_empty() {
+                true => 0.0,
+                false => correct as f64 / self.questions.len() as f64,
+            },
+            answers,
+        }
+    }
+}
+
+fn selected_options(answers: &[QuizAnswer]) -> HashMap<i32, i32> {
+    answers
+        .iter()
+        .map(|answer| (answer.position, answer.option))
+        .collect()
+}
+
+/// The option picked for the question at `position`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuizAnswer {
+    pub position: i32,
+    pub option: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuizAnswerResult {
+    pub position: i32,
+    pub selected: Option<i32>,
+    pub answer_index: i32,
+    pub is_correct: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuizResult {
+    pub quiz_id: ID,
+
+    pub questions: i64,
+    pub answered: i64,
+    pub correct: i64,
+    /// Share of correctly answered questions.
+    pub score: f64,
+    pub answers: Vec<QuizAnswerResult>,
+}
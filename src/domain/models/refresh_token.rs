@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::types::ID;
+
+/// One issued refresh token. Every refresh uses it up and issues the next
+/// token of the same family, so a used token showing up again means it leaked.
+#[derive(Debug, Clone)]
+pub struct RefreshToken {
+    pub id: ID,
+    pub family_id: ID,
+    pub user_id: ID,
+
+    pub token_hash: String,
+    pub nonce: String,
+
+    /// The access token issued together with this refresh token.
+    pub access_jti: ID,
+    pub access_expires_at: DateTime<Utc>,
+
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RefreshToken {
+    pub fn new(
+        family_id: ID,
+        user_id: ID,
+        token_hash: String,
+        nonce: String,
+        access_jti: ID,
+        access_expires_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            family_id,
+            user_id,
+            token_hash,
+            nonce,
+            access_jti,
+            access_expires_at,
+            expires_at,
+            used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{models::word_pair::WordPair, types::ID};
+
+/// How well a card was remembered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Grade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Grade {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Again => "again",
+            Self::Hard => "hard",
+            Self::Good => "good",
+            Self::Easy => "easy",
+        }
+    }
+
+    /// Everything but `Again` counts as remembered.
+    pub fn is_pass(&self) -> bool {
+        *self != Self::Again
+    }
+}
+
+impl FromStr for Grade {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "again" => Ok(Self::Again),
+            "hard" => Ok(Self::Hard),
+            "good" => Ok(Self::Good),
+            "easy" => Ok(Self::Easy),
+            _ => Err(format!("Unknown grade: {}", value)),
+        }
+    }
+}
+
+pub const INITIAL_EASE: f64 = 2.5;
+
+/// Learning progress of one word pair. A word pair without a review state
+/// is a new card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewState {
+    pub word_pair_id: ID,
+    pub user_id: ID,
+
+    /// SM-2 ease factor, the interval grows by it after every good answer.
+    pub ease: f64,
+    pub interval_days: i32,
+    /// Successful reviews in a row.
+    pub repetitions: i32,
+    /// How often the card was forgotten after having been learned.
+    pub lapses: i32,
+    /// FSRS memory stability in days, set once scheduled with FSRS.
+    pub stability: Option<f64>,
+    /// FSRS difficulty from 1 to 10, set once scheduled with FSRS.
+    pub difficulty: Option<f64>,
+
+    pub due_at: DateTime<Utc>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ReviewState {
+    /// A card that has never been reviewed, due right away.
+    pub fn new(word_pair_id: ID, user_id: ID, now: DateTime<Utc>) -> Self {
+        Self {
+            word_pair_id,
+            user_id,
+            ease: INITIAL_EASE,
+            interval_days: 0,
+            repetitions: 0,
+            lapses: 0,
+            stability: None,
+            difficulty: None,
+            due_at: now,
+            last_reviewed_at: None,
+            created_at: now,
+        }
+    }
+
+    pub fn is_due(&self, now: &DateTime<Utc>) -> bool {
+        self.due_at <= *now
+    }
+}
+
+/// One answer to a card, kept for statistics.
+#[derive(Debug, Clone)]
+pub struct ReviewLog {
+    pub id: ID,
+    pub user_id: ID,
+    pub word_pair_id: ID,
+
+    pub grade: Grade,
+    /// Interval before and after the review.
+    pub previous_interval_days: i32,
+    pub interval_days: i32,
+
+    pub reviewed_at: DateTime<Utc>,
+}
+
+impl ReviewLog {
+    pub fn new(previous: &ReviewState, next: &ReviewState, grade: Grade) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id: next.user_id,
+            word_pair_id: next.word_pair_id,
+            grade,
+            previous_interval_days: previous.interval_days,
+            interval_days: next.interval_days,
+            reviewed_at: next.last_reviewed_at.unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+/// A word pair that is due for review.
+#[derive(Debug, Clone)]
+pub struct DueCard {
+    pub word_pair: WordPair,
+    pub review: ReviewState,
+}
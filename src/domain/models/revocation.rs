@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::types::ID;
+
+/// A single access token that must not be accepted before its `exp`.
+#[derive(Debug, Clone)]
+pub struct RevokedToken {
+    pub jti: ID,
+    pub user_id: ID,
+
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: DateTime<Utc>,
+}
+
+impl RevokedToken {
+    pub fn new(jti: ID, user_id: ID, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            jti,
+            user_id,
+            expires_at,
+            revoked_at: Utc::now(),
+        }
+    }
+}
+
+/// Every token of the user issued at or before `revoked_before` is rejected.
+#[derive(Debug, Clone)]
+pub struct UserRevocation {
+    pub user_id: ID,
+    pub revoked_before: DateTime<Utc>,
+}
+
+impl UserRevocation {
+    pub fn new(user_id: ID, revoked_before: DateTime<Utc>) -> Self {
+        Self {
+            user_id,
+            revoked_before,
+        }
+    }
+}
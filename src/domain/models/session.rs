@@ -0,0 +1,233 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    models::{review::Grade, word_pair::WordPair},
+    types::ID,
+};
+
+/// Which side of a word pair is asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// Shows the source text, asks for the target text.
+    #[default]
+    SourceToTarget,
+    TargetToSource,
+    /// Picks one of the other two for every card. Cards never have it.
+    Mixed,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SourceToTarget => "source_to_target",
+            Self::TargetToSource => "target_to_source",
+            Self::Mixed => "mixed",
+        }
+    }
+
+    /// The direction of a single card.
+    pub fn for_card(&self) -> Self {
+        match self {
+            Self::Mixed if rand::random::<bool>() => Self::SourceToTarget,
+            Self::Mixed => Self::TargetToSource,
+            direction => *direction,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "source_to_target" => Ok(Self::SourceToTarget),
+            "target_to_source" => Ok(Self::TargetToSource),
+            "mixed" => Ok(Self::Mixed),
+            _ => Err(format!("Unknown direction: {}", value)),
+        }
+    }
+}
+
+/// One card of a session. The texts are copied when the session starts so
+/// later edits of the word pair don't change it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionCard {
+    pub position: i32,
+    pub word_pair_id: ID,
+
+    pub direction: Direction,
+    /// Never reviewed before the session.
+    pub is_new: bool,
+    pub prompt: String,
+    pub expected: String,
+
+    pub grade: Option<Grade>,
+    /// What the user typed, if the answer was typed.
+    pub answer: Option<String>,
+    pub answered_at: Option<DateTime<Utc>>,
+    /// Time taken to answer.
+    pub duration_ms: Option<i64>,
+}
+
+impl SessionCard {
+    pub fn new(position: i32, word_pair: &WordPair, direction: Direction, is_new: bool) -> Self {
+        let (prompt, expected) = match direction {
+            Direction::TargetToSource => (&word_pair.target_text, &word_pair.source_text),
+            _ => (&word_pair.source_text, &word_pair.target_text),
+        };
+
+        Self {
+            position,
+            word_pair_id: word_pair.id,
+            direction,
+            is_new,
+            prompt: prompt.clone(),
+            expected: expected.clone(),
+            grade: None,
+            answer: None,
+            answered_at: None,
+            duration_ms: None,
+        }
+    }
+
+    pub fn is_answered(&self) -> bool {
+        self.grade.is_some()
+    }
+}
+
+/// The answer to the card at `position`. Without a `grade` the typed
+/// `answer` is checked and graded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionAnswer {
+    pub position: i32,
+    pub grade: Option<Grade>,
+    pub answer: Option<String>,
+    /// Time taken to answer, measured by the client.
+    pub duration_ms: Option<i64>,
+    /// Accepts `cafe` for `café` when checking the answer.
+    pub ignore_diacritics: bool,
+}
+
+/// A run through a fixed set of cards, in the order of their `position`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewSession {
+    pub id: ID,
+    pub user_id: ID,
+
+    pub direction: Direction,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub cards: Vec<SessionCard>,
+
+    pub created_at: DateTime<Utc>,
+    /// Set when the last card was answered.
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ReviewSession {
+    pub fn new(
+        user_id: ID,
+        direction: Direction,
+        source_language: Option<String>,
+        target_language: Option<String>,
+        cards: Vec<SessionCard>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            direction,
+            source_language,
+            target_language,
+            cards,
+            created_at: Utc::now(),
+            finished_at: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished_at.is_some()
+    }
+
+    /// When the user started on the next card: the last answer, or the
+    /// start of the session.
+    pub fn last_activity_at(&self) -> DateTime<Utc> {
+        self.cards
+            .iter()
+            .filter_map(|card| card.answered_at)
+            .max()
+            .unwrap_or(self.created_at)
+    }
+
+    pub fn summary(&self) -> SessionSummary {
+        let answered: Vec<&SessionCard> = self
+            .cards
+            .iter()
+            .filter(|card| card.is_answered())
+            .collect();
+
+        let correct = answered
+            .iter()
+            .filter(|card| card.grade.is_some_and(|grade| grade.is_pass()))
+            .count() as i64;
+
+        let time_spent_ms: i64 = answered.iter().filter_map(|card| card.duration_ms).sum();
+
+        let count = |grade: Grade| {
+            answered
+                .iter()
+                .filter(|card| card.grade == Some(grade))
+                .count() as i64
+        };
+
+        SessionSummary {
+            session_id: self.id,
+            cards: self.cards.len() as i64,
+            new_cards: self.cards.iter().filter(|card| card.is_new).count() as i64,
+            answered: answered.len() as i64,
+            correct,
+            accuracy: match answered.is_empty() {
+                true => None,
+                false => Some(correct as f64 / answered.len() as f64),
+            },
+            again: count(Grade::Again),
+            hard: count(Grade::Hard),
+            good: count(Grade::Good),
+            easy: count(Grade::Easy),
+            time_spent_ms,
+            average_ms: match answered.is_empty() {
+                true => None,
+                false => Some(time_spent_ms / answered.len() as i64),
+            },
+            started_at: self.created_at,
+            finished_at: self.finished_at,
+        }
+    }
+}
+
+/// How a session went. Answers other than `again` count as correct.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSummary {
+    pub session_id: ID,
+
+    pub cards: i64,
+    pub new_cards: i64,
+    pub answered: i64,
+    pub correct: i64,
+    /// Share of correct answers, `None` before the first answer.
+    pub accuracy: Option<f64>,
+    pub again: i64,
+    pub hard: i64,
+    pub good: i64,
+    pub easy: i64,
+
+    pub time_spent_ms: i64,
+    pub average_ms: Option<i64>,
+
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
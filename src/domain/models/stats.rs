@@ -0,0 +1,75 @@
+use chrono::NaiveDate;
+
+/// Cards scheduled this many days ahead or more are mature, the rest of the
+/// reviewed cards are young.
+pub const MATURE_INTERVAL_DAYS: i32 = 21;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguagePairStats {
+    pub source_language: String,
+    pub target_language: String,
+
+    pub word_pairs: i64,
+    /// Never reviewed.
+    pub new_cards: i64,
+    pub young_cards: i64,
+    pub mature_cards: i64,
+}
+
+/// Word pairs added on the local day, or in the week starting on it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyReviews {
+    pub day: NaiveDate,
+    pub reviews: i64,
+    /// Reviews not graded `again`.
+    pub correct: i64,
+}
+
+impl DailyReviews {
+    /// `None` on days without reviews.
+    pub fn accuracy(&self) -> Option<f64> {
+        match self.reviews {
+            0 => None,
+            reviews => Some(self.correct as f64 / reviews as f64),
+        }
+    }
+}
+
+/// Reviews of cards that had already been learned, i.e. had an interval.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RetentionCount {
+    pub reviews: i64,
+    pub remembered: i64,
+}
+
+impl RetentionCount {
+    /// The share of learned cards that were remembered, `None` without
+    /// such reviews.
+    pub fn retention(&self) -> Option<f64> {
+        match self.reviews {
+            0 => None,
+            reviews => Some(self.remembered as f64 / reviews as f64),
+        }
+    }
+}
+
+/// A user's learning progress, days are local to `time_zone`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub time_zone: String,
+    pub today: NaiveDate,
+
+    pub language_pairs: Vec<LanguagePairStats>,
+    pub added_per_day: Vec<DailyCount>,
+    pub added_per_week: Vec<DailyCount>,
+    pub reviews_per_day: Vec<DailyReviews>,
+    pub retention: RetentionCount,
+    /// Days in a row with at least one review, up to today or yesterday.
+    pub streak_days: i64,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub encrypted_refresh_token: String,
+    pub nonce: String,
+}
+
+impl TokenPair {
+    pub fn new(access_token: String, encrypted_refresh_token: String, nonce: String) -> Self {
+        Self {
+            access_token,
+            encrypted_refresh_token,
+            nonce,
+        }
+    }
+}
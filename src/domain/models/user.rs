@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use heck::ToTitleCase;
 use uuid::Uuid;
 
-use crate::domain::types::ID;
+use crate::domain::{models::role::Role, types::ID};
 
 #[derive(Debug, Clone)]
 pub struct User {
@@ -12,6 +12,12 @@ pub struct User {
     pub key: String,
     pub name: String,
 
+    pub role: Role,
+    /// Set while an admin has the account disabled.
+    pub disabled_at: Option<DateTime<Utc>>,
+    /// Set while the account waits for deletion, it is purged after this.
+    pub delete_after: Option<DateTime<Utc>>,
+
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -52,6 +58,9 @@ impl User {
             hashed_password: hashed_password,
             key: key,
             name: name.to_title_case(),
+            role: Role::User,
+            disabled_at: None,
+            delete_after: None,
             created_at: created_time,
             updated_at: updated_time,
         }
@@ -68,6 +77,9 @@ impl User {
             hashed_password: hashed_password,
             key: key,
             name: name.to_title_case(),
+            role: Role::User,
+            disabled_at: None,
+            delete_after: None,
             created_at: created_time,
             updated_at: updated_time,
         }
@@ -86,4 +98,25 @@ impl User {
 
         self
     }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled_at.is_some()
+    }
+
+    pub fn is_pending_deletion(&self) -> bool {
+        self.delete_after.is_some()
+    }
+
+    /// Whether the user may sign in and use their tokens.
+    pub fn is_active(&self) -> bool {
+        !self.is_disabled() && !self.is_pending_deletion()
+    }
+
+    pub fn set_password(&mut self, hashed_password: String) -> &mut Self {
+        self.hashed_password = hashed_password;
+
+        self.updated_at = Utc::now();
+
+        self
+    }
 }
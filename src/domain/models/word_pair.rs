@@ -1,7 +1,12 @@
-use chrono::{DateTime, Utc};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{domain::types::ID, infrastructure::utils::string::to_title_case::TitleCase};
+use crate::{
+    domain::types::ID,
+    infrastructure::utils::string::{normalize_text::normalize_text, to_title_case::TitleCase},
+};
 
 #[derive(Debug, Clone)]
 pub struct WordPair {
@@ -14,6 +19,9 @@ pub struct WordPair {
     pub source_language: String,
 
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Set while the word pair is in the trash.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl WordPair {
@@ -46,6 +54,8 @@ impl WordPair {
             target_language: target_language.to_lowercase(),
             source_language: source_language.to_lowercase(),
             created_at: created_time,
+            updated_at: created_time,
+            deleted_at: None,
         }
     }
 
@@ -68,6 +78,223 @@ impl WordPair {
             target_language: target_language.to_lowercase(),
             source_language: source_language.to_lowercase(),
             created_at: created_time,
+            updated_at: created_time,
+            deleted_at: None,
         }
     }
+
+    /// Changes the given fields, normalized the same way as on creation.
+    pub fn update(
+        &mut self,
+        target_text: Option<String>,
+        source_text: Option<String>,
+        target_language: Option<String>,
+        source_language: Option<String>,
+    ) -> &mut Self {
+        if let Some(target_text) = target_text {
+            self.target_text = target_text.to_title_case();
+        }
+
+        if let Some(source_text) = source_text {
+            self.source_text = source_text.to_title_case();
+        }
+
+        if let Some(target_language) = target_language {
+            self.target_language = target_language.to_lowercase();
+        }
+
+        if let Some(source_language) = source_language {
+            self.source_language = source_language.to_lowercase();
+        }
+
+        self.updated_at = Utc::now();
+
+        self
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// The source text as compared for duplicates. A user can't have two
+    /// word pairs with the same key and languages.
+    pub fn source_key(&self) -> String {
+        normalize_text(&self.source_text)
+    }
+
+    pub fn is_duplicate_of(&self, other: &WordPair) -> bool {
+        self.user_id == other.user_id
+            && self.source_language == other.source_language
+            && self.target_language == other.target_language
+            && self.source_key() == other.source_key()
+    }
+
+    /// Takes over the target texts of a duplicate this pair doesn't have
+    /// yet, as alternatives separated by `ALTERNATIVE_SEPARATOR`. Only texts
+    /// joined by an earlier merge are split, commas are part of the text.
+    pub fn merge(&mut self, duplicate: &WordPair) -> &mut Self {
+        let mut target_texts: Vec<&str> = alternatives(&self.target_text).collect();
+        let mut known: Vec<String> = target_texts
+            .iter()
+            .map(|text| normalize_text(text))
+            .collect();
+        let before = target_texts.len();
+
+        for target_text in alternatives(&duplicate.target_text) {
+            let key = normalize_text(target_text);
+
+            if !known.contains(&key) {
+                target_texts.push(target_text);
+                known.push(key);
+            }
+        }
+
+        if target_texts.len() > before {
+            self.target_text = target_texts.join(&format!("{ALTERNATIVE_SEPARATOR} "));
+            self.updated_at = Utc::now();
+        }
+
+        self
+    }
+}
+
+/// Separates the accepted target texts a merge leaves in one word pair.
+pub const ALTERNATIVE_SEPARATOR: char = ';';
+
+/// The accepted answers in a target text, see `WordPair::merge`.
+pub fn alternatives(target_text: &str) -> impl Iterator<Item = &str> {
+    target_text
+        .split(ALTERNATIVE_SEPARATOR)
+        .map(str::trim)
+        .filter(|alternative| !alternative.is_empty())
+}
+
+/// What creating a word pair does when the user already has a duplicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// Fails with `WordPairAlreadyExists`.
+    #[default]
+    Reject,
+    /// Returns the existing pair unchanged.
+    ReturnExisting,
+    /// Adds the new target text to the existing pair.
+    Merge,
+}
+
+/// Order of a word pair listing. Ties are broken by id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordPairSort {
+    #[default]
+    Newest,
+    Oldest,
+    SourceText,
+    TargetText,
+}
+
+const CURSOR_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+impl WordPairSort {
+    /// Cursor pointing right after `word_pair` in this order.
+    pub fn cursor(&self, word_pair: &WordPair) -> WordPairCursor {
+        let key = match self {
+            Self::Newest | Self::Oldest => word_pair
+                .created_at
+                .naive_utc()
+                .format(CURSOR_TIME_FORMAT)
+                .to_string(),
+            Self::SourceText => word_pair.source_text.clone(),
+            Self::TargetText => word_pair.target_text.clone(),
+        };
+
+        WordPairCursor {
+            sort: *self,
+            key,
+            id: word_pair.id,
+        }
+    }
+
+    /// Whether the cursor was made for this order.
+    pub fn accepts(&self, cursor: &WordPairCursor) -> bool {
+        cursor.sort == *self
+            && match self {
+                Self::Newest | Self::Oldest => {
+                    NaiveDateTime::parse_from_str(&cursor.key, CURSOR_TIME_FORMAT).is_ok()
+                }
+                Self::SourceText | Self::TargetText => true,
+            }
+    }
+}
+
+/// Position in a word pair listing: the sort key and id of the last word
+/// pair of the previous page. Handed out as an opaque base64url string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordPairCursor {
+    pub sort: WordPairSort,
+    pub key: String,
+    pub id: ID,
+}
+
+impl WordPairCursor {
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// Narrows a word pair listing. `created_to` is exclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordPairFilter {
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+}
+
+impl WordPairFilter {
+    pub fn matches(&self, word_pair: &WordPair) -> bool {
+        self.source_language
+            .as_ref()
+            .is_none_or(|language| *language == word_pair.source_language)
+            && self
+                .target_language
+                .as_ref()
+                .is_none_or(|language| *language == word_pair.target_language)
+            && self
+                .created_from
+                .is_none_or(|created_from| word_pair.created_at >= created_from)
+            && self
+                .created_to
+                .is_none_or(|created_to| word_pair.created_at < created_to)
+    }
+}
+
+/// Marks the matched parts of a highlighted search result.
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+/// A word pair matching a search, better matches have a higher `rank`.
+#[derive(Debug, Clone)]
+pub struct WordPairSearchHit {
+    pub word_pair: WordPair,
+
+    pub rank: f32,
+    /// The texts with matches wrapped in `HIGHLIGHT_START` and
+    /// `HIGHLIGHT_END`.
+    pub source_highlight: String,
+    pub target_highlight: String,
+}
+
+/// Number of a user's word pairs for one language pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguagePairCount {
+    pub source_language: String,
+    pub target_language: String,
+    pub count: i64,
 }
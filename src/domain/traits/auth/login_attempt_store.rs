@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::models::login_attempt::{Lockout, LoginAttempts};
+
+#[async_trait]
+pub trait ILoginAttemptStore: Send + Sync {
+    type Error: std::error::Error + Send + Sync;
+
+    async fn get(&self, subject: &str) -> Result<Option<LoginAttempts>, Self::Error>;
+
+    /// Counts one more failure, starting over at one when the previous
+    /// failure is older than `window`.
+    async fn record_failure(
+        &self,
+        subject: &str,
+        now: &DateTime<Utc>,
+        window: &Duration,
+    ) -> Result<LoginAttempts, Self::Error>;
+
+    async fn lock(&self, subject: &str, until: &DateTime<Utc>) -> Result<(), Self::Error>;
+
+    async fn clear(&self, subject: &str) -> Result<(), Self::Error>;
+
+    async fn insert_lockout(&self, lockout: &Lockout) -> Result<(), Self::Error>;
+
+    /// Deletes subjects whose last failure is older than `window` and that
+    /// aren't locked anymore, returns how many were deleted.
+    async fn delete_stale(
+        &self,
+        now: &DateTime<Utc>,
+        window: &Duration,
+    ) -> Result<u64, Self::Error>;
+}
@@ -0,0 +1,3 @@
+pub mod login_attempt_store;
+pub mod public_key_source;
+pub mod token_issuer;
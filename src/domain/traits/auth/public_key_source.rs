@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+
+#[async_trait]
+pub trait IPublicKeySource: Send + Sync {
+    type Error: std::error::Error + Send + Sync;
+
+    async fn fetch_jwks(&self) -> Result<JwkSet, Self::Error>;
+}
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use crate::domain::{
+    models::{role::Role, token::TokenPair},
+    types::ID,
+};
+
+#[async_trait]
+pub trait ITokenIssuer: Send + Sync {
+    type Error: std::error::Error + Send + Sync;
+
+    async fn issue(&self, user_id: &ID, role: Role) -> Result<TokenPair, Self::Error>;
+
+    /// Exchanges a refresh token for a new pair. Callers check reuse and
+    /// expiry before, so the issuer only has to mint the next pair.
+    async fn refresh(
+        &self,
+        user_id: &ID,
+        role: Role,
+        encrypted_refresh_token: &str,
+        nonce: &str,
+    ) -> Result<TokenPair, Self::Error>;
+}
@@ -4,4 +4,8 @@ pub trait ICrypto: Send + Sync {
     fn hash(&self, source: &str) -> Result<String, Self::Error>;
 
     fn verify(&self, source: &str, hashed: &str) -> Result<(), Self::Error>;
+
+    /// Whether `hashed` was made with other settings than the current ones
+    /// and should be replaced after the next successful `verify`.
+    fn needs_rehash(&self, hashed: &str) -> bool;
 }
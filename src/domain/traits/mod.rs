@@ -1,3 +1,6 @@
+pub mod auth;
 pub mod crypto;
 pub mod repositories;
+pub mod review;
+pub mod stats;
 pub mod translate;
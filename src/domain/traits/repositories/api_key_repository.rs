@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{models::api_key::ApiKey, traits::repositories::repository::Repository, types::ID},
+    infrastructure::storage::database::models::api_key::ApiKeyEntity,
+};
+
+#[async_trait]
+pub trait IApiKeyRepository: Repository<Item = ApiKey, Entity = ApiKeyEntity> {
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Returns `RowNotFound` for unknown and revoked keys alike.
+    async fn select_active_by_hash(&self, key_hash: &str) -> Result<Self::Item, Self::Error>;
+
+    /// Sets `revoked_at` on a key of `user_id`, `RowNotFound` if there is no
+    /// such active key.
+    async fn revoke(
+        &self,
+        id: &ID,
+        user_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<Self::Item, Self::Error>;
+
+    /// Revokes every active key of the user, returns how many there were.
+    async fn revoke_by_user_id(
+        &self,
+        user_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<u64, Self::Error>;
+
+    async fn update_last_used(&self, id: &ID, used_at: &DateTime<Utc>) -> Result<(), Self::Error>;
+}
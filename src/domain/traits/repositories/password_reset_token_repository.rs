@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::password_reset_token::PasswordResetToken,
+        traits::repositories::repository::Repository, types::ID,
+    },
+    infrastructure::storage::database::models::password_reset_token::PasswordResetTokenEntity,
+};
+
+#[async_trait]
+pub trait IPasswordResetTokenRepository:
+    Repository<Item = PasswordResetToken, Entity = PasswordResetTokenEntity>
+{
+    async fn select_by_hash(&self, token_hash: &str) -> Result<Self::Item, Self::Error>;
+
+    /// Sets `used_at` unless the token is already used or expired at
+    /// `used_at`, in which case `RowNotFound` is returned.
+    async fn mark_used(&self, id: &ID, used_at: &DateTime<Utc>) -> Result<Self::Item, Self::Error>;
+}
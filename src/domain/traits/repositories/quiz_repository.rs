@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        models::{
+            quiz::Quiz,
+            word_pair::{WordPair, WordPairFilter},
+        },
+        traits::repositories::repository::Repository,
+        types::ID,
+    },
+    infrastructure::storage::database::models::quiz::QuizEntity,
+};
+
+/// Quizzes are stored and loaded together with their questions.
+#[async_trait]
+pub trait IQuizRepository: Repository<Item = Quiz, Entity = QuizEntity> {
+    /// Up to `limit` of the user's active word pairs matching `filter`,
+    /// oldest first, to draw questions from.
+    async fn select_word_pairs(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error>;
+
+    /// Up to `limit` of the user's active word pairs in `target_language`,
+    /// oldest first, to draw distractors from.
+    async fn select_by_target_language(
+        &self,
+        user_id: &ID,
+        target_language: &str,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error>;
+
+    /// All of the user's quizzes with their questions, oldest first.
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Quiz>, Self::Error>;
+
+    /// Stores `graded_at` and the picked options of a graded quiz. Fails with
+    /// `RowNotFound` if the quiz was graded before.
+    async fn submit(&self, quiz: &Quiz) -> Result<Quiz, Self::Error>;
+}
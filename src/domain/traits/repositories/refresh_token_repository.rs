@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::refresh_token::RefreshToken, traits::repositories::repository::Repository,
+        types::ID,
+    },
+    infrastructure::storage::database::models::refresh_token::RefreshTokenEntity,
+};
+
+#[async_trait]
+pub trait IRefreshTokenRepository:
+    Repository<Item = RefreshToken, Entity = RefreshTokenEntity>
+{
+    async fn select_by_hash(&self, token_hash: &str) -> Result<Self::Item, Self::Error>;
+
+    /// The refresh token issued together with the access token `access_jti`.
+    async fn select_by_access_jti(&self, access_jti: &ID) -> Result<Self::Item, Self::Error>;
+
+    /// Sets `used_at` unless the token is already used or revoked, in which
+    /// case `RowNotFound` is returned. Two concurrent refreshes with the same
+    /// token can't both succeed.
+    async fn mark_used(&self, id: &ID, used_at: &DateTime<Utc>) -> Result<Self::Item, Self::Error>;
+
+    /// Takes back `mark_used` for a token that couldn't be exchanged, unless
+    /// it was revoked in the meantime.
+    async fn clear_used(&self, id: &ID) -> Result<(), Self::Error>;
+
+    /// Revokes every token of the family and returns them.
+    async fn revoke_family(
+        &self,
+        family_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Revokes every token of the user, returns how many were still active.
+    async fn revoke_by_user_id(
+        &self,
+        user_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<u64, Self::Error>;
+
+    /// Deletes expired tokens and revoked ones whose access token has
+    /// expired too, returns how many were deleted. Used tokens are kept until
+    /// they expire, reuse detection needs them.
+    async fn delete_expired(&self, now: &DateTime<Utc>) -> Result<u64, Self::Error>;
+}
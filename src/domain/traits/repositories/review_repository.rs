@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::{
+            review::{DueCard, ReviewLog, ReviewState},
+            word_pair::{WordPair, WordPairFilter},
+        },
+        traits::repositories::repository::Repository,
+        types::ID,
+    },
+    infrastructure::storage::database::models::review::ReviewStateEntity,
+};
+
+/// Review states are identified by their word pair's id.
+#[async_trait]
+pub trait IReviewRepository: Repository<Item = ReviewState, Entity = ReviewStateEntity> {
+    /// Up to `limit` of the user's cards matching `filter` that are due at
+    /// `now`, most overdue first. Word pairs in the trash are left out.
+    async fn select_due(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        now: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<DueCard>, Self::Error>;
+
+    /// Up to `limit` of the user's active word pairs matching `filter` that
+    /// have never been reviewed, oldest first.
+    async fn select_new(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error>;
+
+    /// All of the user's review states, by due date.
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// All of the user's review logs, oldest first.
+    async fn select_logs_by_user_id(&self, user_id: &ID) -> Result<Vec<ReviewLog>, Self::Error>;
+
+    /// Inserts the state or replaces the stored one.
+    async fn upsert(&self, state: &Self::Item) -> Result<Self::Item, Self::Error>;
+
+    async fn insert_log(&self, log: &ReviewLog) -> Result<ReviewLog, Self::Error>;
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::revocation::{RevokedToken, UserRevocation},
+        traits::repositories::repository::Repository,
+    },
+    infrastructure::storage::database::models::revocation::RevokedTokenEntity,
+};
+
+#[async_trait]
+pub trait IRevocationRepository:
+    Repository<Item = RevokedToken, Entity = RevokedTokenEntity>
+{
+    /// Revoked tokens whose `exp` has not passed yet.
+    async fn select_active(&self, now: &DateTime<Utc>) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Removes revoked tokens that expired anyway. Returns the number of removed rows.
+    async fn delete_expired(&self, now: &DateTime<Utc>) -> Result<u64, Self::Error>;
+
+    /// Stores the cutoff, keeping the later one if the user already has one.
+    async fn upsert_user_revocation(
+        &self,
+        revocation: &UserRevocation,
+    ) -> Result<UserRevocation, Self::Error>;
+
+    async fn select_user_revocations(&self) -> Result<Vec<UserRevocation>, Self::Error>;
+}
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::session::{ReviewSession, SessionCard},
+        traits::repositories::repository::Repository,
+        types::ID,
+    },
+    infrastructure::storage::database::models::session::ReviewSessionEntity,
+};
+
+/// Sessions are stored and loaded together with their cards.
+#[async_trait]
+pub trait ISessionRepository:
+    Repository<Item = ReviewSession, Entity = ReviewSessionEntity>
+{
+    /// All of the user's sessions with their cards, oldest first.
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Saves the answer of a card that hasn't been answered yet. `RowNotFound`
+    /// if the session has no such card at its position, so of two concurrent
+    /// answers only one gets through.
+    async fn answer_card(
+        &self,
+        session_id: &ID,
+        card: &SessionCard,
+    ) -> Result<SessionCard, Self::Error>;
+
+    /// Takes back the answer of a card, for when its review couldn't be
+    /// graded.
+    async fn clear_card(&self, session_id: &ID, position: i32) -> Result<(), Self::Error>;
+
+    async fn finish(
+        &self,
+        session_id: &ID,
+        finished_at: &DateTime<Utc>,
+    ) -> Result<Self::Item, Self::Error>;
+}
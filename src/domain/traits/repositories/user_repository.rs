@@ -1,7 +1,12 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
-    domain::{models::user::User, traits::repositories::repository::Repository},
+    domain::{
+        models::{role::Role, user::User},
+        traits::repositories::repository::Repository,
+        types::ID,
+    },
     infrastructure::storage::database::models::user::UserEntity,
 };
 
@@ -10,4 +15,38 @@ pub trait IUserRepository: Repository<Item = User, Entity = UserEntity> {
     async fn select_by_key(&self, key: &str) -> Result<Self::Item, Self::Error>;
 
     async fn update_by_id(&self, updated_user: &Self::Item) -> Result<Self::Item, Self::Error>;
+
+    /// Users whose key or name starts with `search`, ignoring case, ordered
+    /// by key. `None` matches everyone.
+    async fn select_page(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Number of users `select_page` pages through for `search`.
+    async fn count(&self, search: Option<&str>) -> Result<i64, Self::Error>;
+
+    /// Disables the user at `disabled_at`, or enables them with `None`.
+    async fn update_disabled(
+        &self,
+        id: &ID,
+        disabled_at: Option<&DateTime<Utc>>,
+    ) -> Result<Self::Item, Self::Error>;
+
+    async fn update_role(&self, id: &ID, role: Role) -> Result<Self::Item, Self::Error>;
+
+    /// Schedules the deletion for `delete_after`, or cancels it with `None`.
+    async fn update_delete_after(
+        &self,
+        id: &ID,
+        delete_after: Option<&DateTime<Utc>>,
+    ) -> Result<Self::Item, Self::Error>;
+
+    /// Users that are disabled or waiting for deletion.
+    async fn select_inactive_ids(&self) -> Result<Vec<ID>, Self::Error>;
+
+    /// Deletes the users whose deletion is due, returning their ids.
+    async fn delete_due(&self, now: &DateTime<Utc>) -> Result<Vec<ID>, Self::Error>;
 }
@@ -1,13 +1,82 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::{
     domain::{
-        models::word_pair::WordPair, traits::repositories::repository::Repository, types::ID,
+        models::word_pair::{
+            LanguagePairCount, WordPair, WordPairCursor, WordPairFilter, WordPairSearchHit,
+            WordPairSort,
+        },
+        traits::repositories::repository::Repository,
+        types::ID,
     },
     infrastructure::storage::database::models::word_pair::WordPairEntity,
 };
 
 #[async_trait]
 pub trait IWordPairRepository: Repository<Item = WordPair, Entity = WordPairEntity> {
+    /// The user's word pairs, without the ones in the trash.
     async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Up to `limit` of the user's active word pairs matching `filter`, in
+    /// `sort` order, starting after `after`.
+    async fn select_page(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        sort: WordPairSort,
+        after: Option<&WordPairCursor>,
+        limit: i64,
+    ) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Up to `limit` of the user's active word pairs whose source or target
+    /// text matches `query`, by words or by similarity, best match first.
+    async fn search(
+        &self,
+        user_id: &ID,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<WordPairSearchHit>, Self::Error>;
+
+    /// Number of the user's active word pairs matching `filter`.
+    async fn count(&self, user_id: &ID, filter: &WordPairFilter) -> Result<i64, Self::Error>;
+
+    /// Saves the texts and languages. `RowNotFound` if the word pair is in
+    /// the trash.
+    async fn update_by_id(&self, updated_word_pair: &Self::Item)
+    -> Result<Self::Item, Self::Error>;
+
+    /// The other active word pairs of the same user with the same
+    /// languages and normalized source text, oldest first.
+    async fn select_same_source(
+        &self,
+        word_pair: &Self::Item,
+    ) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// The user's active word pairs that have a duplicate, ordered so that
+    /// duplicates follow each other, oldest first.
+    async fn select_duplicates(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// The user's word pairs in the trash, most recently deleted first.
+    async fn select_trash_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error>;
+
+    /// Moves the word pair to the trash. `RowNotFound` if it doesn't exist
+    /// or already is there.
+    async fn trash_by_id(
+        &self,
+        id: &ID,
+        deleted_at: &DateTime<Utc>,
+    ) -> Result<Self::Item, Self::Error>;
+
+    /// Takes the user's word pair out of the trash. `RowNotFound` if it
+    /// isn't there.
+    async fn restore_by_id(&self, id: &ID, user_id: &ID) -> Result<Self::Item, Self::Error>;
+
+    /// Deletes the word pairs trashed before `deleted_before`.
+    async fn delete_trashed(&self, deleted_before: &DateTime<Utc>) -> Result<u64, Self::Error>;
+
+    async fn count_by_language_pair(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<LanguagePairCount>, Self::Error>;
 }
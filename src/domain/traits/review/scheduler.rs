@@ -0,0 +1,8 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::review::{Grade, ReviewState};
+
+pub trait IScheduler: Send + Sync {
+    /// The state of the card after it was answered with `grade` at `now`.
+    fn schedule(&self, state: &ReviewState, grade: Grade, now: &DateTime<Utc>) -> ReviewState;
+}
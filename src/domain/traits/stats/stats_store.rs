@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::domain::{
+    models::stats::{DailyCount, DailyReviews, LanguagePairStats, RetentionCount},
+    types::ID,
+};
+
+/// Aggregates over a user's word pairs and review history. `time_zone` is
+/// an IANA name like `Europe/Berlin`, days are local to it.
+#[async_trait]
+pub trait IStatsStore: Send + Sync {
+    type Error: std::error::Error + Send + Sync;
+
+    async fn time_zone_exists(&self, time_zone: &str) -> Result<bool, Self::Error>;
+
+    async fn today(&self, time_zone: &str) -> Result<NaiveDate, Self::Error>;
+
+    /// Active word pairs and their cards per language pair.
+    async fn language_pairs(&self, user_id: &ID) -> Result<Vec<LanguagePairStats>, Self::Error>;
+
+    /// Word pairs added on each of the last `days` days up to today, days
+    /// without any included.
+    async fn added_per_day(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        days: i64,
+    ) -> Result<Vec<DailyCount>, Self::Error>;
+
+    /// Like `added_per_day` for the last `weeks` weeks, starting on Monday.
+    async fn added_per_week(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        weeks: i64,
+    ) -> Result<Vec<DailyCount>, Self::Error>;
+
+    async fn reviews_per_day(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        days: i64,
+    ) -> Result<Vec<DailyReviews>, Self::Error>;
+
+    async fn retention(
+        &self,
+        user_id: &ID,
+        since: &DateTime<Utc>,
+    ) -> Result<RetentionCount, Self::Error>;
+
+    /// The days with reviews, latest first.
+    async fn review_days(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        limit: i64,
+    ) -> Result<Vec<NaiveDate>, Self::Error>;
+}
@@ -1 +1,2 @@
+pub mod revocation_cache;
 pub mod token;
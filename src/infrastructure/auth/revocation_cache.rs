@@ -0,0 +1,165 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+
+use crate::{
+    api::auth::models::Claims,
+    domain::{
+        models::revocation::{RevokedToken, UserRevocation},
+        types::ID,
+    },
+};
+
+#[derive(Debug, Default)]
+struct Revocations {
+    /// `jti` to the token `exp`, after which the entry can be dropped.
+    tokens: HashMap<ID, DateTime<Utc>>,
+    /// User id to the issue time cutoff.
+    users: HashMap<ID, DateTime<Utc>>,
+}
+
+/// In-memory copy of the revocation store, so `auth_middleware` can check
+/// every request without a database round trip.
+#[derive(Debug, Clone, Default)]
+pub struct RevocationCache {
+    revocations: Arc<RwLock<Revocations>>,
+}
+
+impl RevocationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn is_revoked(&self, claims: &Claims) -> bool {
+        let revocations = self.revocations.read().await;
+
+        if revocations.tokens.contains_key(&claims.jti) {
+            return true;
+        }
+
+        // `iat` only has second precision, so a token issued within the
+        // cutoff second is rejected as well.
+        revocations
+            .users
+            .get(&claims.sub)
+            .is_some_and(|revoked_before| claims.iat <= *revoked_before)
+    }
+
+    pub async fn revoke_token(&self, token: &RevokedToken) {
+        self.revocations
+            .write()
+            .await
+            .tokens
+            .insert(token.jti, token.expires_at);
+    }
+
+    pub async fn revoke_user(&self, revocation: &UserRevocation) {
+        let mut revocations = self.revocations.write().await;
+
+        let revoked_before = revocations
+            .users
+            .entry(revocation.user_id)
+            .or_insert(revocation.revoked_before);
+
+        *revoked_before = (*revoked_before).max(revocation.revoked_before);
+    }
+
+    /// Swaps the cached entries for a fresh copy of the store.
+    pub async fn replace(&self, tokens: &[RevokedToken], users: &[UserRevocation]) {
+        let mut revocations = self.revocations.write().await;
+
+        revocations.tokens = tokens
+            .iter()
+            .map(|token| (token.jti, token.expires_at))
+            .collect();
+        revocations.users = users
+            .iter()
+            .map(|revocation| (revocation.user_id, revocation.revoked_before))
+            .collect();
+    }
+
+    pub async fn purge_expired(&self, now: &DateTime<Utc>) {
+        self.revocations
+            .write()
+            .await
+            .tokens
+            .retain(|_, expires_at| *expires_at > *now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    use crate::domain::models::role::Role;
+
+    use super::*;
+
+    fn claims(sub: ID, iat: DateTime<Utc>) -> Claims {
+        Claims::new(
+            sub,
+            Uuid::new_v4(),
+            iat,
+            iat + Duration::minutes(5),
+            Role::User,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_revoke_token() {
+        let cache = RevocationCache::new();
+        let claims = claims(Uuid::new_v4(), Utc::now());
+
+        assert!(!cache.is_revoked(&claims).await);
+
+        cache
+            .revoke_token(&RevokedToken::new(claims.jti, claims.sub, claims.exp))
+            .await;
+
+        assert!(cache.is_revoked(&claims).await);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_user_before() {
+        let cache = RevocationCache::new();
+        let user_id = Uuid::new_v4();
+        let cutoff = Utc::now();
+
+        cache
+            .revoke_user(&UserRevocation::new(user_id, cutoff))
+            .await;
+        cache
+            .revoke_user(&UserRevocation::new(user_id, cutoff - Duration::hours(1)))
+            .await;
+
+        let old = claims(user_id, cutoff - Duration::minutes(1));
+        let new = claims(user_id, cutoff + Duration::minutes(1));
+        let other_user = claims(Uuid::new_v4(), cutoff - Duration::minutes(1));
+
+        assert!(cache.is_revoked(&old).await);
+        assert!(!cache.is_revoked(&new).await);
+        assert!(!cache.is_revoked(&other_user).await);
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired() {
+        let cache = RevocationCache::new();
+        let now = Utc::now();
+
+        let expired = claims(Uuid::new_v4(), now - Duration::minutes(10));
+        let active = claims(Uuid::new_v4(), now);
+
+        for claims in [&expired, &active] {
+            cache
+                .revoke_token(&RevokedToken::new(claims.jti, claims.sub, claims.exp))
+                .await;
+        }
+
+        cache.purge_expired(&now).await;
+
+        assert!(!cache.is_revoked(&expired).await);
+        assert!(cache.is_revoked(&active).await);
+    }
+}
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+use thiserror::Error;
+
+use crate::{
+    domain::{
+        models::{role::Role, token::TokenPair},
+        traits::auth::{public_key_source::IPublicKeySource, token_issuer::ITokenIssuer},
+        types::ID,
+    },
+    infrastructure::{
+        auth::token::local::local_issuer::{LocalTokenIssuer, LocalTokenIssuerError},
+        external_api::auth::auth_requests::{AuthCommunicator, AuthCommunicatorError},
+    },
+};
+
+/// Token issuer selected at startup by the `TOKEN_ISSUER` variable:
+/// `local` signs tokens in-process, anything else delegates to the auth service.
+#[derive(Clone)]
+pub enum TokenIssuer {
+    Remote(AuthCommunicator),
+    Local(Box<LocalTokenIssuer>),
+}
+
+#[derive(Debug, Error)]
+pub enum TokenIssuerError {
+    #[error("Auth communicator error: {0}")]
+    AuthCommunicator(#[from] AuthCommunicatorError),
+
+    #[error("Local token issuer error: {0}")]
+    Local(#[from] LocalTokenIssuerError),
+}
+
+impl TokenIssuer {
+    pub fn from_env() -> Result<Self, TokenIssuerError> {
+        match std::env::var("TOKEN_ISSUER").as_deref() {
+            Ok("local") => Ok(Self::Local(Box::new(LocalTokenIssuer::from_env()?))),
+            _ => Ok(Self::Remote(AuthCommunicator)),
+        }
+    }
+}
+
+#[async_trait]
+impl ITokenIssuer for TokenIssuer {
+    type Error = TokenIssuerError;
+
+    async fn issue(&self, user_id: &ID, role: Role) -> Result<TokenPair, Self::Error> {
+        let token_pair = match self {
+            Self::Remote(communicator) => communicator.issue(user_id, role).await?,
+            Self::Local(issuer) => issuer.issue(user_id, role).await?,
+        };
+
+        Ok(token_pair)
+    }
+
+    async fn refresh(
+        &self,
+        user_id: &ID,
+        role: Role,
+        encrypted_refresh_token: &str,
+        nonce: &str,
+    ) -> Result<TokenPair, Self::Error> {
+        let token_pair = match self {
+            Self::Remote(communicator) => {
+                communicator
+                    .refresh(user_id, role, encrypted_refresh_token, nonce)
+                    .await?
+            }
+            Self::Local(issuer) => {
+                issuer
+                    .refresh(user_id, role, encrypted_refresh_token, nonce)
+                    .await?
+            }
+        };
+
+        Ok(token_pair)
+    }
+}
+
+#[async_trait]
+impl IPublicKeySource for TokenIssuer {
+    type Error = TokenIssuerError;
+
+    async fn fetch_jwks(&self) -> Result<JwkSet, Self::Error> {
+        let jwks = match self {
+            Self::Remote(communicator) => communicator.fetch_jwks().await?,
+            Self::Local(issuer) => issuer.fetch_jwks().await?,
+        };
+
+        Ok(jwks)
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, TimeZone, Utc};
+use jsonwebtoken::dangerous::insecure_decode;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::error;
@@ -11,12 +12,25 @@ pub struct JwksClaims {
     pub jti: uuid::Uuid,
     pub iat: usize,
     pub exp: usize,
-    //pub aud: String,
-    //pub iss: Vec<String>,
-    //pub role: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
     //pub subscription: bool,
 }
 
+/// `aud` may be a single string or an array of strings (RFC 7519, 4.1.3).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
 #[derive(Debug, Error)]
 pub enum JwksClaimsError {
     #[error("Try from int error: {0}")]
@@ -24,6 +38,9 @@ pub enum JwksClaimsError {
 
     #[error("Datetime error: {0}")]
     Datetime(String),
+
+    #[error("JWT error: {0}")]
+    Jwt(#[from] jsonwebtoken::errors::Error),
 }
 
 impl JwksClaims {
@@ -36,8 +53,18 @@ impl JwksClaims {
             jti: value.jti,
             iat: iat,
             exp: exp,
+            nbf: None,
+            iss: None,
+            aud: None,
+            role: Some(value.role.as_str().to_string()),
         })
     }
+
+    /// Reads the claims without checking the signature. Only for tokens that
+    /// were just received from our own issuer.
+    pub fn read_unverified(token: &str) -> Result<Self, JwksClaimsError> {
+        Ok(insecure_decode::<Self>(token)?.claims)
+    }
 }
 
 pub fn datetime_to_usize(datetime: DateTime<Utc>) -> Result<usize, JwksClaimsError> {
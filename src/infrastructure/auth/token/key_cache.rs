@@ -0,0 +1,365 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::jwk::JwkSet;
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{error, warn};
+
+use crate::{
+    domain::traits::auth::public_key_source::IPublicKeySource,
+    infrastructure::utils::io::files::files_io::FileIO,
+};
+
+const DEFAULT_TTL_SECONDS: i64 = 60 * 60;
+const DEFAULT_MIN_REFRESH_INTERVAL_SECONDS: i64 = 30;
+
+#[derive(Debug, Clone)]
+pub struct KeyCacheConfig {
+    /// How long a fetched key set is used before it is fetched again.
+    pub ttl: Duration,
+    /// Lower bound between forced refreshes, so a stream of badly signed
+    /// tokens cannot hammer the key source.
+    pub min_refresh_interval: Duration,
+    /// Where the last fetched key set is persisted and read back when the
+    /// source is unreachable.
+    pub fallback_file_io: Option<FileIO>,
+}
+
+impl KeyCacheConfig {
+    pub fn from_env() -> Self {
+        let seconds = |name: &str, default: i64| {
+            Duration::seconds(
+                std::env::var(name)
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(default),
+            )
+        };
+
+        Self {
+            ttl: seconds("PUBLIC_KEY_CACHE_TTL_SECONDS", DEFAULT_TTL_SECONDS),
+            min_refresh_interval: seconds(
+                "PUBLIC_KEY_MIN_REFRESH_SECONDS",
+                DEFAULT_MIN_REFRESH_INTERVAL_SECONDS,
+            ),
+            fallback_file_io: std::env::var("PUBLIC_KEY_CACHE_PATH")
+                .ok()
+                .map(|path| FileIO::new(&path)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedKeys {
+    jwks: JwkSet,
+    fetched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Error)]
+pub enum KeyCacheError {
+    #[error("Key source error: {0}")]
+    Source(String),
+}
+
+/// Shared in-memory copy of the token verification keys (JWKS).
+#[derive(Clone)]
+pub struct PublicKeyCache<Source> {
+    source: Source,
+    config: KeyCacheConfig,
+    cached: Arc<RwLock<Option<CachedKeys>>>,
+    /// When the source last failed, to wait `min_refresh_interval` before
+    /// trying it again.
+    failed_at: Arc<RwLock<Option<DateTime<Utc>>>>,
+    refresh_lock: Arc<Mutex<()>>,
+}
+
+impl<Source> PublicKeyCache<Source>
+where
+    Source: IPublicKeySource,
+{
+    pub fn new(source: Source, config: KeyCacheConfig) -> Self {
+        Self {
+            source,
+            config,
+            cached: Arc::new(RwLock::new(None)),
+            failed_at: Arc::new(RwLock::new(None)),
+            refresh_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Returns the cached key set, fetching it first if it is missing or expired.
+    pub async fn get(&self) -> Result<JwkSet, KeyCacheError> {
+        if let Some(jwks) = self.fresh(self.config.ttl).await {
+            return Ok(jwks);
+        }
+
+        self.fetch(self.config.ttl).await
+    }
+
+    /// Refetches the key set when a token refers to an unknown key, unless it
+    /// was fetched within `min_refresh_interval`.
+    pub async fn refresh(&self) -> Result<JwkSet, KeyCacheError> {
+        self.fetch(self.config.min_refresh_interval).await
+    }
+
+    /// Fetches from the source unless another caller already did so within
+    /// `max_age`. Falls back to the stale set or the persisted file on failure.
+    /// After a failure the source is left alone for `min_refresh_interval`.
+    async fn fetch(&self, max_age: Duration) -> Result<JwkSet, KeyCacheError> {
+        let _guard = self.refresh_lock.lock().await;
+
+        if let Some(jwks) = self.fresh(max_age).await {
+            return Ok(jwks);
+        }
+
+        let recently_failed = self
+            .failed_at
+            .read()
+            .await
+            .is_some_and(|failed_at| Utc::now() - failed_at < self.config.min_refresh_interval);
+
+        if recently_failed {
+            return match self.cached.read().await.as_ref() {
+                Some(cached) => Ok(cached.jwks.clone()),
+                None => Err(KeyCacheError::Source(
+                    "Key source failed recently".to_string(),
+                )),
+            };
+        }
+
+        match self.source.fetch_jwks().await {
+            Ok(jwks) => {
+                *self.failed_at.write().await = None;
+
+                self.store(&jwks).await;
+                self.persist(&jwks);
+
+                Ok(jwks)
+            }
+            Err(error) => {
+                error!("Couldn't fetch JWKS: {}", error);
+
+                *self.failed_at.write().await = Some(Utc::now());
+
+                if let Some(cached) = self.cached.read().await.as_ref() {
+                    warn!("Serving stale JWKS");
+                    return Ok(cached.jwks.clone());
+                }
+
+                let jwks = self
+                    .read_persisted()
+                    .ok_or(KeyCacheError::Source(error.to_string()))?;
+
+                self.store(&jwks).await;
+
+                Ok(jwks)
+            }
+        }
+    }
+
+    async fn fresh(&self, max_age: Duration) -> Option<JwkSet> {
+        self.cached
+            .read()
+            .await
+            .as_ref()
+            .filter(|cached| Utc::now() - cached.fetched_at < max_age)
+            .map(|cached| cached.jwks.clone())
+    }
+
+    async fn store(&self, jwks: &JwkSet) {
+        *self.cached.write().await = Some(CachedKeys {
+            jwks: jwks.clone(),
+            fetched_at: Utc::now(),
+        });
+    }
+
+    fn persist(&self, jwks: &JwkSet) {
+        if let Some(file_io) = &self.config.fallback_file_io
+            && let Ok(data) = serde_json::to_string(jwks)
+        {
+            let _ = file_io.write(&data);
+        }
+    }
+
+    fn read_persisted(&self) -> Option<JwkSet> {
+        let data = self.config.fallback_file_io.as_ref()?.read().ok()?;
+
+        serde_json::from_slice(&data).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    fn jwks(kid: &str) -> JwkSet {
+        serde_json::from_value(serde_json::json!({
+            "keys": [{ "kty": "RSA", "kid": kid, "n": "AQAB", "e": "AQAB" }]
+        }))
+        .unwrap()
+    }
+
+    #[derive(Clone, Default)]
+    struct TestKeySource {
+        jwks: Arc<std::sync::Mutex<Option<JwkSet>>>,
+        fetches: Arc<AtomicUsize>,
+    }
+
+    impl TestKeySource {
+        fn with_key(kid: &str) -> Self {
+            let source = Self::default();
+            source.set(Some(kid));
+            source
+        }
+
+        fn set(&self, kid: Option<&str>) {
+            *self.jwks.lock().unwrap() = kid.map(jwks);
+        }
+
+        fn fetches(&self) -> usize {
+            self.fetches.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl IPublicKeySource for TestKeySource {
+        type Error = std::io::Error;
+
+        async fn fetch_jwks(&self) -> Result<JwkSet, Self::Error> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+
+            self.jwks
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or(std::io::Error::other("auth service is down"))
+        }
+    }
+
+    fn config(ttl: Duration, min_refresh_interval: Duration) -> KeyCacheConfig {
+        KeyCacheConfig {
+            ttl,
+            min_refresh_interval,
+            fallback_file_io: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_within_ttl() {
+        let source = TestKeySource::with_key("first");
+        let cache =
+            PublicKeyCache::new(source.clone(), config(Duration::hours(1), Duration::zero()));
+
+        assert_eq!(cache.get().await.unwrap(), jwks("first"));
+
+        source.set(Some("second"));
+
+        assert_eq!(cache.get().await.unwrap(), jwks("first"));
+        assert_eq!(source.fetches(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_refetch_after_ttl() {
+        let source = TestKeySource::with_key("first");
+        let cache = PublicKeyCache::new(source.clone(), config(Duration::zero(), Duration::zero()));
+
+        assert_eq!(cache.get().await.unwrap(), jwks("first"));
+
+        source.set(Some("second"));
+
+        assert_eq!(cache.get().await.unwrap(), jwks("second"));
+        assert_eq!(source.fetches(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_picks_up_rotation() {
+        let source = TestKeySource::with_key("first");
+        let cache =
+            PublicKeyCache::new(source.clone(), config(Duration::hours(1), Duration::zero()));
+
+        cache.get().await.unwrap();
+        source.set(Some("second"));
+
+        assert_eq!(cache.refresh().await.unwrap(), jwks("second"));
+        assert_eq!(cache.get().await.unwrap(), jwks("second"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_is_rate_limited() {
+        let source = TestKeySource::with_key("first");
+        let cache = PublicKeyCache::new(
+            source.clone(),
+            config(Duration::hours(1), Duration::hours(1)),
+        );
+
+        cache.get().await.unwrap();
+        source.set(Some("second"));
+
+        assert_eq!(cache.refresh().await.unwrap(), jwks("first"));
+        assert_eq!(source.fetches(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_serve_stale_when_source_fails() {
+        let source = TestKeySource::with_key("first");
+        let cache = PublicKeyCache::new(source.clone(), config(Duration::zero(), Duration::zero()));
+
+        cache.get().await.unwrap();
+        source.set(None);
+
+        assert_eq!(cache.get().await.unwrap(), jwks("first"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_is_not_retried_at_once() {
+        let source = TestKeySource::with_key("first");
+        let cache =
+            PublicKeyCache::new(source.clone(), config(Duration::zero(), Duration::hours(1)));
+
+        cache.get().await.unwrap();
+        source.set(None);
+
+        assert_eq!(cache.get().await.unwrap(), jwks("first"));
+        assert_eq!(cache.get().await.unwrap(), jwks("first"));
+        assert_eq!(cache.refresh().await.unwrap(), jwks("first"));
+        assert_eq!(source.fetches(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_file() {
+        let path = std::env::temp_dir().join(format!("wordy-{}.json", uuid::Uuid::new_v4()));
+        let file_io = FileIO::new(path.to_str().unwrap());
+
+        let source = TestKeySource::with_key("persisted");
+        let mut cache_config = config(Duration::hours(1), Duration::zero());
+        cache_config.fallback_file_io = Some(file_io.clone());
+
+        PublicKeyCache::new(source.clone(), cache_config.clone())
+            .get()
+            .await
+            .unwrap();
+
+        source.set(None);
+
+        let restarted = PublicKeyCache::new(source, cache_config);
+
+        assert_eq!(restarted.get().await.unwrap(), jwks("persisted"));
+
+        file_io.remove().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_without_fallback() {
+        let cache = PublicKeyCache::new(
+            TestKeySource::default(),
+            config(Duration::hours(1), Duration::zero()),
+        );
+
+        assert!(matches!(cache.get().await, Err(KeyCacheError::Source(_))));
+    }
+}
@@ -0,0 +1,259 @@
+use argon2::password_hash::rand_core::OsRng;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{
+    Algorithm, EncodingKey, Header, encode,
+    jwk::{Jwk, JwkSet, ThumbprintHash},
+};
+use rsa::{
+    RsaPrivateKey,
+    pkcs1::DecodeRsaPrivateKey,
+    pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding},
+};
+use thiserror::Error;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    api::auth::models::Claims,
+    domain::models::role::Role,
+    domain::{
+        models::token::TokenPair,
+        traits::auth::{public_key_source::IPublicKeySource, token_issuer::ITokenIssuer},
+        types::ID,
+    },
+    infrastructure::{
+        auth::token::jwks::{
+            claims::{Audience, JwksClaims, JwksClaimsError},
+            jwks_validator::TokenValidationConfig,
+        },
+        utils::secret_token::generate_token,
+    },
+};
+
+const DEFAULT_ACCESS_TTL_SECONDS: i64 = 15 * 60;
+const RSA_KEY_BITS: usize = 2048;
+
+/// In-process RS256 issuer, used instead of the external auth service in local
+/// development and tests.
+#[derive(Clone)]
+pub struct LocalTokenIssuer {
+    encoding_key: EncodingKey,
+    jwk: Jwk,
+    access_ttl: Duration,
+    issuer: Option<String>,
+    audience: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum LocalTokenIssuerError {
+    #[error("Rsa error: {0}")]
+    Rsa(#[from] rsa::Error),
+
+    #[error("Pkcs8 error: {0}")]
+    Pkcs8(#[from] rsa::pkcs8::Error),
+
+    #[error("JWT error: {0}")]
+    Jwt(#[from] jsonwebtoken::errors::Error),
+
+    #[error("Jwks claims error: {0}")]
+    JwksClaims(#[from] JwksClaimsError),
+
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("Private key is neither PKCS#8 nor PKCS#1 PEM")]
+    InvalidPrivateKey,
+}
+
+impl LocalTokenIssuer {
+    pub fn new(private_pem: &str, access_ttl: Duration) -> Result<Self, LocalTokenIssuerError> {
+        let private_key = RsaPrivateKey::from_pkcs8_pem(private_pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs1_pem(private_pem))
+            .map_err(|error| {
+                error!("Couldn't parse local private key: {}", error);
+                LocalTokenIssuerError::InvalidPrivateKey
+            })?;
+
+        Self::from_private_key(&private_key, access_ttl)
+    }
+
+    /// Generates a throwaway key pair, so tokens do not survive a restart.
+    pub fn generate(access_ttl: Duration) -> Result<Self, LocalTokenIssuerError> {
+        let private_key = RsaPrivateKey::new(&mut OsRng, RSA_KEY_BITS)?;
+
+        Self::from_private_key(&private_key, access_ttl)
+    }
+
+    pub fn from_env() -> Result<Self, LocalTokenIssuerError> {
+        let access_ttl = Duration::seconds(
+            std::env::var("ACCESS_TOKEN_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_ACCESS_TTL_SECONDS),
+        );
+
+        let issuer = match std::env::var("LOCAL_AUTH_PRIVATE_KEY_PATH") {
+            Ok(path) => Self::new(&std::fs::read_to_string(path)?, access_ttl)?,
+            Err(_) => Self::generate(access_ttl)?,
+        };
+
+        // Sign with the first accepted values, so our own validator takes the tokens.
+        let validation_config = TokenValidationConfig::from_env();
+
+        Ok(issuer.with_claims(
+            validation_config.issuers.first().cloned(),
+            validation_config.audience.first().cloned(),
+        ))
+    }
+
+    /// Sets the `iss` and `aud` written into issued tokens.
+    pub fn with_claims(mut self, issuer: Option<String>, audience: Option<String>) -> Self {
+        self.issuer = issuer;
+        self.audience = audience;
+        self
+    }
+
+    /// Public half of the signing key, with the RFC 7638 thumbprint as `kid`.
+    pub fn jwk(&self) -> &Jwk {
+        &self.jwk
+    }
+
+    fn from_private_key(
+        private_key: &RsaPrivateKey,
+        access_ttl: Duration,
+    ) -> Result<Self, LocalTokenIssuerError> {
+        let private_pem = private_key.to_pkcs8_pem(LineEnding::LF)?;
+
+        let encoding_key = EncodingKey::from_rsa_pem(private_pem.as_bytes())?;
+
+        let mut jwk = Jwk::from_encoding_key(&encoding_key, Algorithm::RS256)?;
+        jwk.common.key_id = Some(jwk.thumbprint(ThumbprintHash::SHA256));
+
+        Ok(Self {
+            encoding_key,
+            jwk,
+            access_ttl,
+            issuer: None,
+            audience: None,
+        })
+    }
+}
+
+#[async_trait]
+impl ITokenIssuer for LocalTokenIssuer {
+    type Error = LocalTokenIssuerError;
+
+    async fn issue(&self, user_id: &ID, role: Role) -> Result<TokenPair, Self::Error> {
+        let iat = Utc::now();
+        let claims = Claims::new(*user_id, Uuid::new_v4(), iat, iat + self.access_ttl, role);
+
+        let mut jwks_claims = JwksClaims::from_domain_claims(&claims)?;
+        jwks_claims.nbf = Some(jwks_claims.iat);
+        jwks_claims.iss = self.issuer.clone();
+        jwks_claims.aud = self.audience.clone().map(Audience::Single);
+
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = self.jwk.common.key_id.clone();
+
+        let access_token = encode(&header, &jwks_claims, &self.encoding_key)?;
+
+        Ok(TokenPair::new(
+            access_token,
+            generate_token::<32>(),
+            generate_token::<12>(),
+        ))
+    }
+
+    /// The refresh token is an opaque random value here, so refreshing is
+    /// issuing a new pair.
+    async fn refresh(
+        &self,
+        user_id: &ID,
+        role: Role,
+        _encrypted_refresh_token: &str,
+        _nonce: &str,
+    ) -> Result<TokenPair, Self::Error> {
+        self.issue(user_id, role).await
+    }
+}
+
+#[async_trait]
+impl IPublicKeySource for LocalTokenIssuer {
+    type Error = LocalTokenIssuerError;
+
+    async fn fetch_jwks(&self) -> Result<JwkSet, Self::Error> {
+        Ok(JwkSet {
+            keys: vec![self.jwk.clone()],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::auth::token::jwks::jwks_validator::JwksTokenValidator;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_issue_verifiable_token() {
+        let issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+
+        let user_id = Uuid::new_v4();
+
+        let token_pair = issuer.issue(&user_id, Role::User).await.unwrap();
+
+        let validator = JwksTokenValidator::default();
+
+        let kid = validator.key_id(&token_pair.access_token).unwrap();
+
+        assert_eq!(kid, issuer.jwk().common.key_id);
+
+        let claims = validator
+            .verify(&token_pair.access_token, issuer.jwk())
+            .unwrap();
+
+        assert_eq!(claims.sub, user_id);
+        assert!(claims.exp > claims.iat);
+        assert_ne!(token_pair.encrypted_refresh_token, token_pair.nonce);
+    }
+
+    #[tokio::test]
+    async fn test_reject_foreign_key() {
+        let issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+        let other_issuer = LocalTokenIssuer::generate(Duration::minutes(5)).unwrap();
+
+        let token_pair = issuer.issue(&Uuid::new_v4(), Role::User).await.unwrap();
+
+        let res =
+            JwksTokenValidator::default().verify(&token_pair.access_token, other_issuer.jwk());
+
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_issue_configured_issuer_and_audience() {
+        let issuer = LocalTokenIssuer::generate(Duration::minutes(5))
+            .unwrap()
+            .with_claims(Some("wordy-auth".to_string()), Some("wordy".to_string()));
+
+        let token_pair = issuer.issue(&Uuid::new_v4(), Role::User).await.unwrap();
+
+        let config = |issuer: &str| TokenValidationConfig {
+            issuers: vec![issuer.to_string()],
+            audience: vec!["wordy".to_string()],
+            leeway: 0,
+        };
+
+        assert!(
+            JwksTokenValidator::new(config("wordy-auth"))
+                .verify(&token_pair.access_token, issuer.jwk())
+                .is_ok()
+        );
+        assert!(
+            JwksTokenValidator::new(config("someone-else"))
+                .verify(&token_pair.access_token, issuer.jwk())
+                .is_err()
+        );
+    }
+}
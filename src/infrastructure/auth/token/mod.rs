@@ -1 +1,4 @@
+pub mod issuer;
 pub mod jwks;
+pub mod key_cache;
+pub mod local;
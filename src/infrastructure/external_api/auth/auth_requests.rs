@@ -1,7 +1,23 @@
-use std::env::VarError;
+use std::{env::VarError, time::Duration};
 
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
 use thiserror::Error;
 
+use crate::{
+    api::login::models::{ClaimsDTO, RefreshClaimsDTO},
+    domain::{
+        models::{role::Role, token::TokenPair},
+        traits::auth::{public_key_source::IPublicKeySource, token_issuer::ITokenIssuer},
+        types::ID,
+    },
+};
+
+/// Token verification waits on the key set, so a hung auth service must not
+/// hold requests for long.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Clone, Copy)]
 pub struct AuthCommunicator;
 
@@ -15,16 +31,101 @@ pub enum AuthCommunicatorError {
 }
 
 impl AuthCommunicator {
-    pub async fn get_public_pem(&self) -> Result<String, AuthCommunicatorError> {
-        let client = reqwest::Client::new();
+    fn client(&self) -> Result<reqwest::Client, AuthCommunicatorError> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(client)
+    }
+
+    /// `JWKS_URL` if set, otherwise the well-known path of the auth service.
+    fn jwks_url(&self) -> Result<String, AuthCommunicatorError> {
+        match std::env::var("JWKS_URL") {
+            Ok(url) => Ok(url),
+            Err(_) => Ok(format!(
+                "{}/.well-known/jwks.json",
+                std::env::var("AUTH_ADDRESS")?
+            )),
+        }
+    }
+
+    pub async fn get_jwks(&self) -> Result<JwkSet, AuthCommunicatorError> {
+        let client = self.client()?;
+
+        let resp = client
+            .get(self.jwks_url()?)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let jwks = resp.json::<JwkSet>().await?;
+
+        Ok(jwks)
+    }
+}
+
+#[async_trait]
+impl IPublicKeySource for AuthCommunicator {
+    type Error = AuthCommunicatorError;
+
+    async fn fetch_jwks(&self) -> Result<JwkSet, Self::Error> {
+        self.get_jwks().await
+    }
+}
+
+#[async_trait]
+impl ITokenIssuer for AuthCommunicator {
+    type Error = AuthCommunicatorError;
+
+    async fn issue(&self, user_id: &ID, role: Role) -> Result<TokenPair, Self::Error> {
+        let client = self.client()?;
+
+        let claims = ClaimsDTO {
+            sub: *user_id,
+            role,
+        };
+
+        let resp = client
+            .post(format!("{}/generate", std::env::var("AUTH_ADDRESS")?))
+            .json(&claims)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let (access_token, (encrypted_refresh_token, nonce)) =
+            resp.json::<(String, (String, String))>().await?;
+
+        Ok(TokenPair::new(access_token, encrypted_refresh_token, nonce))
+    }
+
+    async fn refresh(
+        &self,
+        user_id: &ID,
+        role: Role,
+        encrypted_refresh_token: &str,
+        nonce: &str,
+    ) -> Result<TokenPair, Self::Error> {
+        let client = self.client()?;
+
+        let claims = RefreshClaimsDTO {
+            sub: *user_id,
+            role,
+            encrypted_refresh_token: encrypted_refresh_token.to_string(),
+            nonce: nonce.to_string(),
+        };
 
         let resp = client
-            .post(format!("{}/key/public", std::env::var("AUTH_ADDRESS")?))
+            .post(format!("{}/refresh", std::env::var("AUTH_ADDRESS")?))
+            .json(&claims)
             .send()
-            .await?;
+            .await?
+            .error_for_status()?;
 
-        let public_pem: String = resp.json::<String>().await?;
+        let (access_token, (encrypted_refresh_token, nonce)) =
+            resp.json::<(String, (String, String))>().await?;
 
-        Ok(public_pem)
+        Ok(TokenPair::new(access_token, encrypted_refresh_token, nonce))
     }
 }
@@ -1,4 +1,5 @@
 pub mod auth;
 pub mod external_api;
+pub mod scheduler;
 pub mod storage;
 pub mod utils;
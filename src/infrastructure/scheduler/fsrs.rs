@@ -0,0 +1,233 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{
+    models::review::{Grade, ReviewState},
+    traits::review::scheduler::IScheduler,
+};
+
+/// Default parameters of FSRS 4.5.
+const DEFAULT_WEIGHTS: [f64; 17] = [
+    0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461, 2.1072,
+    0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+];
+const DECAY: f64 = -0.5;
+const FACTOR: f64 = 19.0 / 81.0;
+const MAX_INTERVAL_DAYS: f64 = 36500.0;
+
+pub const DEFAULT_DESIRED_RETENTION: f64 = 0.9;
+
+/// Free Spaced Repetition Scheduler. Models each card by its stability
+/// and difficulty and schedules it for when the chance of remembering it
+/// drops to `desired_retention`.
+#[derive(Debug, Clone)]
+pub struct FsrsScheduler {
+    pub desired_retention: f64,
+    pub weights: [f64; 17],
+}
+
+impl Default for FsrsScheduler {
+    fn default() -> Self {
+        Self {
+            desired_retention: DEFAULT_DESIRED_RETENTION,
+            weights: DEFAULT_WEIGHTS,
+        }
+    }
+}
+
+impl FsrsScheduler {
+    pub fn new(desired_retention: f64) -> Self {
+        Self {
+            desired_retention: desired_retention.clamp(0.7, 0.99),
+            ..Self::default()
+        }
+    }
+
+    fn rating(grade: Grade) -> f64 {
+        match grade {
+            Grade::Again => 1.0,
+            Grade::Hard => 2.0,
+            Grade::Good => 3.0,
+            Grade::Easy => 4.0,
+        }
+    }
+
+    /// Chance of remembering a card `elapsed_days` after the last review.
+    pub fn retrievability(elapsed_days: f64, stability: f64) -> f64 {
+        (1.0 + FACTOR * elapsed_days / stability).powf(DECAY)
+    }
+
+    fn initial_stability(&self, grade: Grade) -> f64 {
+        self.weights[Self::rating(grade) as usize - 1]
+    }
+
+    fn initial_difficulty(&self, grade: Grade) -> f64 {
+        (self.weights[4] - (Self::rating(grade) - 3.0) * self.weights[5]).clamp(1.0, 10.0)
+    }
+
+    fn next_difficulty(&self, difficulty: f64, grade: Grade) -> f64 {
+        let difficulty = difficulty - self.weights[6] * (Self::rating(grade) - 3.0);
+
+        // Mean reversion towards the difficulty of an easy first answer.
+        (self.weights[7] * self.initial_difficulty(Grade::Easy)
+            + (1.0 - self.weights[7]) * difficulty)
+            .clamp(1.0, 10.0)
+    }
+
+    fn recall_stability(
+        &self,
+        difficulty: f64,
+        stability: f64,
+        retrievability: f64,
+        grade: Grade,
+    ) -> f64 {
+        let w = &self.weights;
+
+        let hard_penalty = if grade == Grade::Hard { w[15] } else { 1.0 };
+        let easy_bonus = if grade == Grade::Easy { w[16] } else { 1.0 };
+
+        stability
+            * (1.0
+                + w[8].exp()
+                    * (11.0 - difficulty)
+                    * stability.powf(-w[9])
+                    * ((w[10] * (1.0 - retrievability)).exp() - 1.0)
+                    * hard_penalty
+                    * easy_bonus)
+    }
+
+    fn forget_stability(&self, difficulty: f64, stability: f64, retrievability: f64) -> f64 {
+        let w = &self.weights;
+
+        (w[11]
+            * difficulty.powf(-w[12])
+            * ((stability + 1.0).powf(w[13]) - 1.0)
+            * (w[14] * (1.0 - retrievability)).exp())
+        .min(stability)
+    }
+
+    fn interval_days(&self, stability: f64) -> i32 {
+        let interval = stability / FACTOR * (self.desired_retention.powf(1.0 / DECAY) - 1.0);
+
+        interval.round().clamp(1.0, MAX_INTERVAL_DAYS) as i32
+    }
+}
+
+impl IScheduler for FsrsScheduler {
+    fn schedule(&self, state: &ReviewState, grade: Grade, now: &DateTime<Utc>) -> ReviewState {
+        let mut next = state.clone();
+
+        // Cards reviewed with SM-2 so far take their interval as stability.
+        let memory = match (state.stability, state.difficulty, state.last_reviewed_at) {
+            (Some(stability), Some(difficulty), Some(last_reviewed_at)) => {
+                Some((stability, difficulty, last_reviewed_at))
+            }
+            (_, _, Some(last_reviewed_at)) => Some((
+                (state.interval_days as f64).max(1.0),
+                self.initial_difficulty(Grade::Good),
+                last_reviewed_at,
+            )),
+            _ => None,
+        };
+
+        let (stability, difficulty) = match memory {
+            Some((stability, difficulty, last_reviewed_at)) => {
+                let elapsed_days = ((*now - last_reviewed_at).num_seconds() as f64
+                    / Duration::days(1).num_seconds() as f64)
+                    .max(0.0);
+                let retrievability = Self::retrievability(elapsed_days, stability);
+
+                let stability = match grade {
+                    Grade::Again => self.forget_stability(difficulty, stability, retrievability),
+                    _ => self.recall_stability(difficulty, stability, retrievability, grade),
+                };
+
+                (stability, self.next_difficulty(difficulty, grade))
+            }
+            None => (
+                self.initial_stability(grade),
+                self.initial_difficulty(grade),
+            ),
+        };
+
+        if grade.is_pass() {
+            next.repetitions += 1;
+        } else {
+            if state.repetitions > 0 {
+                next.lapses += 1;
+            }
+
+            next.repetitions = 0;
+        }
+
+        next.stability = Some(stability);
+        next.difficulty = Some(difficulty);
+        next.interval_days = self.interval_days(stability);
+        next.due_at = *now + Duration::days(next.interval_days as i64);
+        next.last_reviewed_at = Some(*now);
+
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn review(scheduler: &FsrsScheduler, grades: &[Grade]) -> ReviewState {
+        let state = ReviewState::new(Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+
+        grades.iter().fold(state, |state, grade| {
+            scheduler.schedule(&state, *grade, &state.due_at)
+        })
+    }
+
+    #[test]
+    fn test_first_review() {
+        let scheduler = FsrsScheduler::default();
+
+        let intervals: Vec<i32> = [Grade::Again, Grade::Hard, Grade::Good, Grade::Easy]
+            .iter()
+            .map(|grade| review(&scheduler, &[*grade]).interval_days)
+            .collect();
+
+        assert_eq!(intervals, vec![1, 1, 4, 14]);
+
+        let res = review(&scheduler, &[Grade::Again]);
+
+        assert!(res.difficulty.unwrap() > review(&scheduler, &[Grade::Easy]).difficulty.unwrap());
+    }
+
+    #[test]
+    fn test_stability_grows_and_drops() {
+        let scheduler = FsrsScheduler::default();
+
+        let learned = review(&scheduler, &[Grade::Good, Grade::Good, Grade::Good]);
+        let forgotten = scheduler.schedule(&learned, Grade::Again, &learned.due_at);
+
+        assert!(learned.interval_days > 30);
+        assert!(forgotten.stability.unwrap() < learned.stability.unwrap());
+        assert_eq!(forgotten.lapses, 1);
+        assert_eq!(forgotten.repetitions, 0);
+    }
+
+    #[test]
+    fn test_desired_retention() {
+        let relaxed = review(&FsrsScheduler::new(0.8), &[Grade::Good, Grade::Good]);
+        let strict = review(&FsrsScheduler::new(0.95), &[Grade::Good, Grade::Good]);
+
+        assert!(relaxed.interval_days > strict.interval_days);
+    }
+
+    #[test]
+    fn test_retrievability_at_interval() {
+        let scheduler = FsrsScheduler::default();
+
+        let res = review(&scheduler, &[Grade::Good, Grade::Good]);
+        let retrievability =
+            FsrsScheduler::retrievability(res.interval_days as f64, res.stability.unwrap());
+
+        assert!((retrievability - 0.9).abs() < 0.01);
+    }
+}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::review::{Grade, ReviewState},
+        traits::review::scheduler::IScheduler,
+    },
+    infrastructure::scheduler::{
+        fsrs::{DEFAULT_DESIRED_RETENTION, FsrsScheduler},
+        sm2::Sm2Scheduler,
+    },
+};
+
+pub mod fsrs;
+pub mod sm2;
+
+/// The scheduler picked by `REVIEW_SCHEDULER`, `sm2` (default) or `fsrs`.
+#[derive(Debug, Clone)]
+pub enum Scheduler {
+    Sm2(Sm2Scheduler),
+    Fsrs(FsrsScheduler),
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::Sm2(Sm2Scheduler)
+    }
+}
+
+impl Scheduler {
+    pub fn from_env() -> Self {
+        match std::env::var("REVIEW_SCHEDULER").as_deref() {
+            Ok("fsrs") => Self::Fsrs(FsrsScheduler::new(
+                std::env::var("FSRS_DESIRED_RETENTION")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(DEFAULT_DESIRED_RETENTION),
+            )),
+            _ => Self::default(),
+        }
+    }
+}
+
+impl IScheduler for Scheduler {
+    fn schedule(&self, state: &ReviewState, grade: Grade, now: &DateTime<Utc>) -> ReviewState {
+        match self {
+            Self::Sm2(scheduler) => scheduler.schedule(state, grade, now),
+            Self::Fsrs(scheduler) => scheduler.schedule(state, grade, now),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{
+    models::review::{Grade, ReviewState},
+    traits::review::scheduler::IScheduler,
+};
+
+const MIN_EASE: f64 = 1.3;
+/// About a hundred years, the same bound as FSRS.
+const MAX_INTERVAL_DAYS: f64 = 36500.0;
+
+/// SuperMemo 2. The grades map to the qualities 2 (forgotten) to 5.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sm2Scheduler;
+
+impl Sm2Scheduler {
+    fn quality(grade: Grade) -> f64 {
+        match grade {
+            Grade::Again => 2.0,
+            Grade::Hard => 3.0,
+            Grade::Good => 4.0,
+            Grade::Easy => 5.0,
+        }
+    }
+}
+
+impl IScheduler for Sm2Scheduler {
+    fn schedule(&self, state: &ReviewState, grade: Grade, now: &DateTime<Utc>) -> ReviewState {
+        let mut next = state.clone();
+
+        if grade.is_pass() {
+            let penalty = 5.0 - Self::quality(grade);
+
+            next.ease = (state.ease + 0.1 - penalty * (0.08 + penalty * 0.02)).max(MIN_EASE);
+            next.repetitions += 1;
+            next.interval_days = match next.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (state.interval_days as f64 * next.ease)
+                    .round()
+                    .min(MAX_INTERVAL_DAYS) as i32,
+            };
+        } else {
+            // A forgotten card starts over, keeping its ease.
+            if state.repetitions > 0 {
+                next.lapses += 1;
+            }
+
+            next.repetitions = 0;
+            next.interval_days = 1;
+        }
+
+        next.due_at = *now + Duration::days(next.interval_days as i64);
+        next.last_reviewed_at = Some(*now);
+
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn review(state: &ReviewState, grades: &[Grade]) -> ReviewState {
+        grades.iter().fold(state.clone(), |state, grade| {
+            Sm2Scheduler.schedule(&state, *grade, &state.due_at)
+        })
+    }
+
+    #[test]
+    fn test_intervals_grow() {
+        let state = ReviewState::new(Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+
+        let intervals: Vec<i32> = (1..=4)
+            .map(|count| review(&state, &vec![Grade::Good; count]).interval_days)
+            .collect();
+
+        assert_eq!(intervals, vec![1, 6, 15, 38]);
+    }
+
+    #[test]
+    fn test_ease() {
+        let state = ReviewState::new(Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+
+        assert_eq!(review(&state, &[Grade::Easy]).ease, 2.6);
+        assert_eq!(review(&state, &[Grade::Good]).ease, 2.5);
+        assert!((review(&state, &[Grade::Hard]).ease - 2.36).abs() < 1e-9);
+        assert_eq!(review(&state, &[Grade::Hard; 20]).ease, MIN_EASE);
+    }
+
+    #[test]
+    fn test_interval_bounded() {
+        let now = Utc::now();
+        let mut state = ReviewState::new(Uuid::new_v4(), Uuid::new_v4(), now);
+
+        // Cards can be graded before they are due, so the interval keeps
+        // growing without the review date moving.
+        for _ in 0..50 {
+            state = Sm2Scheduler.schedule(&state, Grade::Easy, &now);
+        }
+
+        assert_eq!(state.interval_days, MAX_INTERVAL_DAYS as i32);
+        assert_eq!(state.due_at, now + Duration::days(MAX_INTERVAL_DAYS as i64));
+    }
+
+    #[test]
+    fn test_lapse() {
+        let state = ReviewState::new(Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+
+        let res = review(&state, &[Grade::Again]);
+
+        assert_eq!(res.lapses, 0);
+
+        let res = review(&state, &[Grade::Good, Grade::Good, Grade::Again]);
+
+        assert_eq!(res.lapses, 1);
+        assert_eq!(res.repetitions, 0);
+        assert_eq!(res.interval_days, 1);
+        assert_eq!(res.ease, 2.5);
+        assert_eq!(
+            res.due_at,
+            res.last_reviewed_at.unwrap() + Duration::days(1)
+        );
+    }
+}
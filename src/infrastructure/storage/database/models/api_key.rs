@@ -0,0 +1,68 @@
+use crate::{
+    domain::{models::api_key::ApiKey, types::ID},
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ApiKeyEntity {
+    pub id: ID,
+    pub user_id: ID,
+
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub scope: String,
+
+    pub created_at: PrimitiveDateTime,
+    pub last_used_at: Option<PrimitiveDateTime>,
+    pub revoked_at: Option<PrimitiveDateTime>,
+}
+
+impl From<&ApiKey> for ApiKeyEntity {
+    fn from(value: &ApiKey) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            name: value.name.to_owned(),
+            prefix: value.prefix.to_owned(),
+            key_hash: value.key_hash.to_owned(),
+            scope: value.scope.as_str().to_string(),
+            created_at: convert_datetime_utc_to_primitive(&value.created_at),
+            last_used_at: value
+                .last_used_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+            revoked_at: value
+                .revoked_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        }
+    }
+}
+
+impl From<&ApiKeyEntity> for ApiKey {
+    fn from(value: &ApiKeyEntity) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            name: value.name.to_owned(),
+            prefix: value.prefix.to_owned(),
+            key_hash: value.key_hash.to_owned(),
+            // Unknown scopes fall back to the narrowest one.
+            scope: value.scope.parse().unwrap_or_default(),
+            created_at: convert_primitive_to_datetime_utc(&value.created_at),
+            last_used_at: value
+                .last_used_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+            revoked_at: value
+                .revoked_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{
+    domain::{
+        models::login_attempt::{Lockout, LoginAttempts},
+        types::ID,
+    },
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LoginAttemptsEntity {
+    pub subject: String,
+    pub failures: i32,
+    pub last_failure_at: PrimitiveDateTime,
+    pub locked_until: Option<PrimitiveDateTime>,
+}
+
+impl From<&LoginAttemptsEntity> for LoginAttempts {
+    fn from(value: &LoginAttemptsEntity) -> Self {
+        Self {
+            subject: value.subject.to_owned(),
+            failures: value.failures.max(0) as u32,
+            last_failure_at: convert_primitive_to_datetime_utc(&value.last_failure_at),
+            locked_until: value
+                .locked_until
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LockoutEntity {
+    pub id: ID,
+    pub subject: String,
+    pub failures: i32,
+    pub locked_until: PrimitiveDateTime,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl From<&Lockout> for LockoutEntity {
+    fn from(value: &Lockout) -> Self {
+        Self {
+            id: value.id,
+            subject: value.subject.to_owned(),
+            failures: value.failures.min(i32::MAX as u32) as i32,
+            locked_until: convert_datetime_utc_to_primitive(&value.locked_until),
+            created_at: convert_datetime_utc_to_primitive(&value.created_at),
+        }
+    }
+}
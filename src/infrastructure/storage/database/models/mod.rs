@@ -1,2 +1,10 @@
+pub mod api_key;
+pub mod login_attempt;
+pub mod password_reset_token;
+pub mod quiz;
+pub mod refresh_token;
+pub mod review;
+pub mod revocation;
+pub mod session;
 pub mod user;
 pub mod word_pair;
@@ -0,0 +1,52 @@
+use crate::{
+    domain::{models::password_reset_token::PasswordResetToken, types::ID},
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PasswordResetTokenEntity {
+    pub id: ID,
+    pub user_id: ID,
+
+    pub token_hash: String,
+
+    pub expires_at: PrimitiveDateTime,
+    pub used_at: Option<PrimitiveDateTime>,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl From<&PasswordResetToken> for PasswordResetTokenEntity {
+    fn from(value: &PasswordResetToken) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            token_hash: value.token_hash.to_owned(),
+            expires_at: convert_datetime_utc_to_primitive(&value.expires_at),
+            used_at: value
+                .used_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+            created_at: convert_datetime_utc_to_primitive(&value.created_at),
+        }
+    }
+}
+
+impl From<&PasswordResetTokenEntity> for PasswordResetToken {
+    fn from(value: &PasswordResetTokenEntity) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            token_hash: value.token_hash.to_owned(),
+            expires_at: convert_primitive_to_datetime_utc(&value.expires_at),
+            used_at: value
+                .used_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+            created_at: convert_primitive_to_datetime_utc(&value.created_at),
+        }
+    }
+}
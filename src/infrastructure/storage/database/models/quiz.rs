@@ -0,0 +1,99 @@
+use crate::{
+    domain::{
+        models::quiz::{Quiz, QuizQuestion},
+        types::ID,
+    },
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct QuizEntity {
+    pub id: ID,
+    pub user_id: ID,
+
+    /// The `u64` seed with its bits kept, Postgres has no unsigned integers.
+    pub seed: i64,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+
+    pub created_at: PrimitiveDateTime,
+    pub graded_at: Option<PrimitiveDateTime>,
+}
+
+impl From<&Quiz> for QuizEntity {
+    fn from(value: &Quiz) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            seed: value.seed as i64,
+            source_language: value.source_language.to_owned(),
+            target_language: value.target_language.to_owned(),
+            created_at: convert_datetime_utc_to_primitive(&value.created_at),
+            graded_at: value
+                .graded_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        }
+    }
+}
+
+impl From<(&QuizEntity, &[QuizQuestionEntity])> for Quiz {
+    fn from((quiz, questions): (&QuizEntity, &[QuizQuestionEntity])) -> Self {
+        Self {
+            id: quiz.id,
+            user_id: quiz.user_id,
+            seed: quiz.seed as u64,
+            source_language: quiz.source_language.to_owned(),
+            target_language: quiz.target_language.to_owned(),
+            questions: questions.iter().map(QuizQuestion::from).collect(),
+            created_at: convert_primitive_to_datetime_utc(&quiz.created_at),
+            graded_at: quiz
+                .graded_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct QuizQuestionEntity {
+    pub quiz_id: ID,
+    pub position: i32,
+    pub word_pair_id: ID,
+
+    pub prompt: String,
+    pub options: Vec<String>,
+    pub answer_index: i32,
+    pub selected: Option<i32>,
+}
+
+impl QuizQuestionEntity {
+    pub fn new(quiz_id: ID, question: &QuizQuestion) -> Self {
+        Self {
+            quiz_id,
+            position: question.position,
+            word_pair_id: question.word_pair_id,
+            prompt: question.prompt.to_owned(),
+            options: question.options.to_owned(),
+            answer_index: question.answer_index,
+            selected: question.selected,
+        }
+    }
+}
+
+impl From<&QuizQuestionEntity> for QuizQuestion {
+    fn from(value: &QuizQuestionEntity) -> Self {
+        Self {
+            position: value.position,
+            word_pair_id: value.word_pair_id,
+            prompt: value.prompt.to_owned(),
+            options: value.options.to_owned(),
+            answer_index: value.answer_index,
+            selected: value.selected,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    domain::{models::refresh_token::RefreshToken, types::ID},
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RefreshTokenEntity {
+    pub id: ID,
+    pub family_id: ID,
+    pub user_id: ID,
+
+    pub token_hash: String,
+    pub nonce: String,
+
+    pub access_jti: ID,
+    pub access_expires_at: PrimitiveDateTime,
+
+    pub expires_at: PrimitiveDateTime,
+    pub used_at: Option<PrimitiveDateTime>,
+    pub revoked_at: Option<PrimitiveDateTime>,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl From<&RefreshToken> for RefreshTokenEntity {
+    fn from(value: &RefreshToken) -> Self {
+        Self {
+            id: value.id,
+            family_id: value.family_id,
+            user_id: value.user_id,
+            token_hash: value.token_hash.to_owned(),
+            nonce: value.nonce.to_owned(),
+            access_jti: value.access_jti,
+            access_expires_at: convert_datetime_utc_to_primitive(&value.access_expires_at),
+            expires_at: convert_datetime_utc_to_primitive(&value.expires_at),
+            used_at: value
+                .used_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+            revoked_at: value
+                .revoked_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+            created_at: convert_datetime_utc_to_primitive(&value.created_at),
+        }
+    }
+}
+
+impl From<&RefreshTokenEntity> for RefreshToken {
+    fn from(value: &RefreshTokenEntity) -> Self {
+        Self {
+            id: value.id,
+            family_id: value.family_id,
+            user_id: value.user_id,
+            token_hash: value.token_hash.to_owned(),
+            nonce: value.nonce.to_owned(),
+            access_jti: value.access_jti,
+            access_expires_at: convert_primitive_to_datetime_utc(&value.access_expires_at),
+            expires_at: convert_primitive_to_datetime_utc(&value.expires_at),
+            used_at: value
+                .used_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+            revoked_at: value
+                .revoked_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+            created_at: convert_primitive_to_datetime_utc(&value.created_at),
+        }
+    }
+}
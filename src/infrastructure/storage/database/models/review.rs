@@ -0,0 +1,112 @@
+use crate::{
+    domain::{
+        models::review::{Grade, ReviewLog, ReviewState},
+        types::ID,
+    },
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReviewStateEntity {
+    pub word_pair_id: ID,
+    pub user_id: ID,
+
+    pub ease: f64,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub lapses: i32,
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
+
+    pub due_at: PrimitiveDateTime,
+    pub last_reviewed_at: Option<PrimitiveDateTime>,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl From<&ReviewState> for ReviewStateEntity {
+    fn from(value: &ReviewState) -> Self {
+        Self {
+            word_pair_id: value.word_pair_id,
+            user_id: value.user_id,
+            ease: value.ease,
+            interval_days: value.interval_days,
+            repetitions: value.repetitions,
+            lapses: value.lapses,
+            stability: value.stability,
+            difficulty: value.difficulty,
+            due_at: convert_datetime_utc_to_primitive(&value.due_at),
+            last_reviewed_at: value
+                .last_reviewed_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+            created_at: convert_datetime_utc_to_primitive(&value.created_at),
+        }
+    }
+}
+
+impl From<&ReviewStateEntity> for ReviewState {
+    fn from(value: &ReviewStateEntity) -> Self {
+        Self {
+            word_pair_id: value.word_pair_id,
+            user_id: value.user_id,
+            ease: value.ease,
+            interval_days: value.interval_days,
+            repetitions: value.repetitions,
+            lapses: value.lapses,
+            stability: value.stability,
+            difficulty: value.difficulty,
+            due_at: convert_primitive_to_datetime_utc(&value.due_at),
+            last_reviewed_at: value
+                .last_reviewed_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+            created_at: convert_primitive_to_datetime_utc(&value.created_at),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReviewLogEntity {
+    pub id: ID,
+    pub user_id: ID,
+    pub word_pair_id: ID,
+
+    pub grade: String,
+    pub previous_interval_days: i32,
+    pub interval_days: i32,
+
+    pub reviewed_at: PrimitiveDateTime,
+}
+
+impl From<&ReviewLog> for ReviewLogEntity {
+    fn from(value: &ReviewLog) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            word_pair_id: value.word_pair_id,
+            grade: value.grade.as_str().to_string(),
+            previous_interval_days: value.previous_interval_days,
+            interval_days: value.interval_days,
+            reviewed_at: convert_datetime_utc_to_primitive(&value.reviewed_at),
+        }
+    }
+}
+
+impl From<&ReviewLogEntity> for ReviewLog {
+    fn from(value: &ReviewLogEntity) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            word_pair_id: value.word_pair_id,
+            // Unknown grades count as forgotten.
+            grade: value.grade.parse().unwrap_or(Grade::Again),
+            previous_interval_days: value.previous_interval_days,
+            interval_days: value.interval_days,
+            reviewed_at: convert_primitive_to_datetime_utc(&value.reviewed_at),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    domain::{
+        models::revocation::{RevokedToken, UserRevocation},
+        types::ID,
+    },
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RevokedTokenEntity {
+    pub jti: ID,
+    pub user_id: ID,
+
+    pub expires_at: PrimitiveDateTime,
+    pub revoked_at: PrimitiveDateTime,
+}
+
+impl From<&RevokedToken> for RevokedTokenEntity {
+    fn from(value: &RevokedToken) -> Self {
+        Self {
+            jti: value.jti,
+            user_id: value.user_id,
+            expires_at: convert_datetime_utc_to_primitive(&value.expires_at),
+            revoked_at: convert_datetime_utc_to_primitive(&value.revoked_at),
+        }
+    }
+}
+
+impl From<&RevokedTokenEntity> for RevokedToken {
+    fn from(value: &RevokedTokenEntity) -> Self {
+        Self {
+            jti: value.jti,
+            user_id: value.user_id,
+            expires_at: convert_primitive_to_datetime_utc(&value.expires_at),
+            revoked_at: convert_primitive_to_datetime_utc(&value.revoked_at),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserRevocationEntity {
+    pub user_id: ID,
+    pub revoked_before: PrimitiveDateTime,
+}
+
+impl From<&UserRevocation> for UserRevocationEntity {
+    fn from(value: &UserRevocation) -> Self {
+        Self {
+            user_id: value.user_id,
+            revoked_before: convert_datetime_utc_to_primitive(&value.revoked_before),
+        }
+    }
+}
+
+impl From<&UserRevocationEntity> for UserRevocation {
+    fn from(value: &UserRevocationEntity) -> Self {
+        Self {
+            user_id: value.user_id,
+            revoked_before: convert_primitive_to_datetime_utc(&value.revoked_before),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    domain::{
+        models::session::{ReviewSession, SessionCard},
+        types::ID,
+    },
+    infrastructure::utils::convert::{
+        datetime_to_primitive::convert_datetime_utc_to_primitive,
+        primitive_to_datetime::convert_primitive_to_datetime_utc,
+    },
+};
+use sqlx::types::time::PrimitiveDateTime;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReviewSessionEntity {
+    pub id: ID,
+    pub user_id: ID,
+
+    pub direction: String,
+    pub source_language: Option<String>,
+    pub target_language: Option<String>,
+
+    pub created_at: PrimitiveDateTime,
+    pub finished_at: Option<PrimitiveDateTime>,
+}
+
+impl From<&ReviewSession> for ReviewSessionEntity {
+    fn from(value: &ReviewSession) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            direction: value.direction.as_str().to_string(),
+            source_language: value.source_language.to_owned(),
+            target_language: value.target_language.to_owned(),
+            created_at: convert_datetime_utc_to_primitive(&value.created_at),
+            finished_at: value
+                .finished_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        }
+    }
+}
+
+impl From<(&ReviewSessionEntity, &[SessionCardEntity])> for ReviewSession {
+    fn from((session, cards): (&ReviewSessionEntity, &[SessionCardEntity])) -> Self {
+        Self {
+            id: session.id,
+            user_id: session.user_id,
+            direction: session.direction.parse().unwrap_or_default(),
+            source_language: session.source_language.to_owned(),
+            target_language: session.target_language.to_owned(),
+            cards: cards.iter().map(SessionCard::from).collect(),
+            created_at: convert_primitive_to_datetime_utc(&session.created_at),
+            finished_at: session
+                .finished_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SessionCardEntity {
+    pub session_id: ID,
+    pub position: i32,
+    pub word_pair_id: ID,
+
+    pub direction: String,
+    pub is_new: bool,
+    pub prompt: String,
+    pub expected: String,
+
+    pub grade: Option<String>,
+    pub answer: Option<String>,
+    pub answered_at: Option<PrimitiveDateTime>,
+    pub duration_ms: Option<i64>,
+}
+
+impl SessionCardEntity {
+    pub fn new(session_id: ID, card: &SessionCard) -> Self {
+        Self {
+            session_id,
+            position: card.position,
+            word_pair_id: card.word_pair_id,
+            direction: card.direction.as_str().to_string(),
+            is_new: card.is_new,
+            prompt: card.prompt.to_owned(),
+            expected: card.expected.to_owned(),
+            grade: card.grade.map(|grade| grade.as_str().to_string()),
+            answer: card.answer.to_owned(),
+            answered_at: card
+                .answered_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+            duration_ms: card.duration_ms,
+        }
+    }
+}
+
+impl From<&SessionCardEntity> for SessionCard {
+    fn from(value: &SessionCardEntity) -> Self {
+        Self {
+            position: value.position,
+            word_pair_id: value.word_pair_id,
+            direction: value.direction.parse().unwrap_or_default(),
+            is_new: value.is_new,
+            prompt: value.prompt.to_owned(),
+            expected: value.expected.to_owned(),
+            grade: value.grade.as_ref().and_then(|grade| grade.parse().ok()),
+            answer: value.answer.to_owned(),
+            answered_at: value
+                .answered_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+            duration_ms: value.duration_ms,
+        }
+    }
+}
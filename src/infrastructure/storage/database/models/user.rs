@@ -15,6 +15,10 @@ pub struct UserEntity {
     pub key: String,
     pub name: String,
 
+    pub role: String,
+    pub disabled_at: Option<PrimitiveDateTime>,
+    pub delete_after: Option<PrimitiveDateTime>,
+
     pub created_at: PrimitiveDateTime,
     pub updated_at: PrimitiveDateTime,
 }
@@ -29,6 +33,15 @@ impl From<&User> for UserEntity {
             hashed_password: value.hashed_password.to_owned(),
             key: value.key.to_owned(),
             name: value.name.to_owned(),
+            role: value.role.as_str().to_string(),
+            disabled_at: value
+                .disabled_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+            delete_after: value
+                .delete_after
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
             created_at: created_at,
             updated_at: updated_at,
         }
@@ -45,6 +58,16 @@ impl From<&UserEntity> for User {
             hashed_password: value.hashed_password.to_owned(),
             key: value.key.to_owned(),
             name: value.name.to_owned(),
+            // Unknown roles fall back to the least privileged one.
+            role: value.role.parse().unwrap_or_default(),
+            disabled_at: value
+                .disabled_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+            delete_after: value
+                .delete_after
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
             created_at: created_datetime,
             updated_at: updated_datetime,
         }
@@ -1,5 +1,8 @@
 use crate::{
-    domain::{models::word_pair::WordPair, types::ID},
+    domain::{
+        models::word_pair::{WordPair, WordPairSearchHit},
+        types::ID,
+    },
     infrastructure::utils::convert::{
         datetime_to_primitive::convert_datetime_utc_to_primitive,
         primitive_to_datetime::convert_primitive_to_datetime_utc,
@@ -16,8 +19,11 @@ pub struct WordPairEntity {
     pub source_text: String,
     pub target_language: String,
     pub source_language: String,
+    pub source_key: String,
 
     pub created_at: PrimitiveDateTime,
+    pub updated_at: PrimitiveDateTime,
+    pub deleted_at: Option<PrimitiveDateTime>,
 }
 
 impl From<&WordPair> for WordPairEntity {
@@ -31,7 +37,13 @@ impl From<&WordPair> for WordPairEntity {
             source_text: value.source_text.to_owned(),
             target_language: value.target_language.to_owned(),
             source_language: value.source_language.to_owned(),
+            source_key: value.source_key(),
             created_at: created_at,
+            updated_at: convert_datetime_utc_to_primitive(&value.updated_at),
+            deleted_at: value
+                .deleted_at
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
         }
     }
 }
@@ -48,6 +60,32 @@ impl From<&WordPairEntity> for WordPair {
             target_language: value.target_language.to_owned(),
             source_language: value.source_language.to_owned(),
             created_at: created_datetime,
+            updated_at: convert_primitive_to_datetime_utc(&value.updated_at),
+            deleted_at: value
+                .deleted_at
+                .as_ref()
+                .map(convert_primitive_to_datetime_utc),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WordPairSearchEntity {
+    #[sqlx(flatten)]
+    pub word_pair: WordPairEntity,
+
+    pub rank: f32,
+    pub source_highlight: String,
+    pub target_highlight: String,
+}
+
+impl From<&WordPairSearchEntity> for WordPairSearchHit {
+    fn from(value: &WordPairSearchEntity) -> Self {
+        Self {
+            word_pair: WordPair::from(&value.word_pair),
+            rank: value.rank,
+            source_highlight: value.source_highlight.to_owned(),
+            target_highlight: value.target_highlight.to_owned(),
         }
     }
 }
@@ -0,0 +1,144 @@
+use crate::{
+    domain::{
+        models::api_key::ApiKey,
+        traits::repositories::{api_key_repository::IApiKeyRepository, repository::Repository},
+        types::ID,
+    },
+    infrastructure::{
+        storage::database::models::api_key::ApiKeyEntity,
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct ApiKeyPostgresRepository {
+    db: PgPool,
+}
+
+#[async_trait]
+impl Repository for ApiKeyPostgresRepository {
+    type Pool = PgPool;
+    type Item = ApiKey;
+    type Entity = ApiKeyEntity;
+    type Error = Error;
+
+    fn new(db: Self::Pool) -> Self {
+        Self { db }
+    }
+
+    async fn insert(&self, api_key: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(api_key);
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO api_keys (id, user_id, name, prefix, key_hash, scope, created_at, last_used_at, revoked_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                 RETURNING *",
+        )
+        .bind(entity.id)
+        .bind(entity.user_id)
+        .bind(&entity.name)
+        .bind(&entity.prefix)
+        .bind(&entity.key_hash)
+        .bind(&entity.scope)
+        .bind(entity.created_at)
+        .bind(entity.last_used_at)
+        .bind(entity.revoked_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>("SELECT * FROM api_keys WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM api_keys WHERE id = $1")
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IApiKeyRepository for ApiKeyPostgresRepository {
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM api_keys WHERE user_id = $1 ORDER BY created_at",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn select_active_by_hash(&self, key_hash: &str) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+        )
+        .bind(key_hash)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn revoke(
+        &self,
+        id: &ID,
+        user_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE api_keys SET revoked_at = $1
+                 WHERE id = $2 AND user_id = $3 AND revoked_at IS NULL
+                 RETURNING *",
+        )
+        .bind(convert_datetime_utc_to_primitive(revoked_at))
+        .bind(id)
+        .bind(user_id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn revoke_by_user_id(
+        &self,
+        user_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<u64, Self::Error> {
+        let res = sqlx::query(
+            "UPDATE api_keys SET revoked_at = $1
+                 WHERE user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(convert_datetime_utc_to_primitive(revoked_at))
+        .bind(user_id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn update_last_used(&self, id: &ID, used_at: &DateTime<Utc>) -> Result<(), Self::Error> {
+        sqlx::query("UPDATE api_keys SET last_used_at = $1 WHERE id = $2")
+            .bind(convert_datetime_utc_to_primitive(used_at))
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    domain::{
+        models::login_attempt::{Lockout, LoginAttempts},
+        traits::auth::login_attempt_store::ILoginAttemptStore,
+    },
+    infrastructure::{
+        storage::database::models::login_attempt::{LockoutEntity, LoginAttemptsEntity},
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct LoginAttemptPostgresStore {
+    db: PgPool,
+}
+
+impl LoginAttemptPostgresStore {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ILoginAttemptStore for LoginAttemptPostgresStore {
+    type Error = Error;
+
+    async fn get(&self, subject: &str) -> Result<Option<LoginAttempts>, Self::Error> {
+        let db_entity = sqlx::query_as::<_, LoginAttemptsEntity>(
+            "SELECT subject, failures, last_failure_at, locked_until FROM login_attempts WHERE subject = $1",
+        )
+        .bind(subject)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(db_entity.as_ref().map(LoginAttempts::from))
+    }
+
+    async fn record_failure(
+        &self,
+        subject: &str,
+        now: &DateTime<Utc>,
+        window: &Duration,
+    ) -> Result<LoginAttempts, Self::Error> {
+        let db_entity = sqlx::query_as::<_, LoginAttemptsEntity>(
+            "INSERT INTO login_attempts (subject, failures, last_failure_at)
+                 VALUES ($1, 1, $2)
+                 ON CONFLICT (subject) DO UPDATE SET
+                     failures = CASE
+                         WHEN login_attempts.last_failure_at < $3 THEN 1
+                         ELSE login_attempts.failures + 1
+                     END,
+                     last_failure_at = EXCLUDED.last_failure_at
+                 RETURNING *",
+        )
+        .bind(subject)
+        .bind(convert_datetime_utc_to_primitive(now))
+        .bind(convert_datetime_utc_to_primitive(&(*now - *window)))
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(LoginAttempts::from(&db_entity))
+    }
+
+    async fn lock(&self, subject: &str, until: &DateTime<Utc>) -> Result<(), Self::Error> {
+        sqlx::query("UPDATE login_attempts SET locked_until = $1 WHERE subject = $2")
+            .bind(convert_datetime_utc_to_primitive(until))
+            .bind(subject)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn clear(&self, subject: &str) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM login_attempts WHERE subject = $1")
+            .bind(subject)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_lockout(&self, lockout: &Lockout) -> Result<(), Self::Error> {
+        let entity = LockoutEntity::from(lockout);
+
+        sqlx::query(
+            "INSERT INTO login_lockouts (id, subject, failures, locked_until, created_at)
+                 VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(entity.id)
+        .bind(&entity.subject)
+        .bind(entity.failures)
+        .bind(entity.locked_until)
+        .bind(entity.created_at)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_stale(
+        &self,
+        now: &DateTime<Utc>,
+        window: &Duration,
+    ) -> Result<u64, Self::Error> {
+        let res = sqlx::query(
+            "DELETE FROM login_attempts
+                 WHERE last_failure_at < $2 AND (locked_until IS NULL OR locked_until <= $1)",
+        )
+        .bind(convert_datetime_utc_to_primitive(now))
+        .bind(convert_datetime_utc_to_primitive(&(*now - *window)))
+        .execute(&self.db)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+}
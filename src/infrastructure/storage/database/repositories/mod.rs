@@ -1,2 +1,11 @@
+pub mod api_key_repository;
+pub mod login_attempt_store;
+pub mod password_reset_token_repository;
+pub mod quiz_repository;
+pub mod refresh_token_repository;
+pub mod review_repository;
+pub mod revocation_repository;
+pub mod session_repository;
+pub mod stats_store;
 pub mod user_repository;
 pub mod word_pair_repository;
@@ -0,0 +1,100 @@
+use crate::{
+    domain::{
+        models::password_reset_token::PasswordResetToken,
+        traits::repositories::{
+            password_reset_token_repository::IPasswordResetTokenRepository, repository::Repository,
+        },
+        types::ID,
+    },
+    infrastructure::{
+        storage::database::models::password_reset_token::PasswordResetTokenEntity,
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct PasswordResetTokenPostgresRepository {
+    db: PgPool,
+}
+
+#[async_trait]
+impl Repository for PasswordResetTokenPostgresRepository {
+    type Pool = PgPool;
+    type Item = PasswordResetToken;
+    type Entity = PasswordResetTokenEntity;
+    type Error = Error;
+
+    fn new(db: Self::Pool) -> Self {
+        Self { db }
+    }
+
+    async fn insert(&self, token: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(token);
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO password_reset_tokens (id, user_id, token_hash, expires_at, used_at, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 RETURNING *",
+        )
+        .bind(entity.id)
+        .bind(entity.user_id)
+        .bind(&entity.token_hash)
+        .bind(entity.expires_at)
+        .bind(entity.used_at)
+        .bind(entity.created_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity =
+            sqlx::query_as::<_, Self::Entity>("SELECT * FROM password_reset_tokens WHERE id = $1")
+                .bind(id)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM password_reset_tokens WHERE id = $1")
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IPasswordResetTokenRepository for PasswordResetTokenPostgresRepository {
+    async fn select_by_hash(&self, token_hash: &str) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM password_reset_tokens WHERE token_hash = $1",
+        )
+        .bind(token_hash)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn mark_used(&self, id: &ID, used_at: &DateTime<Utc>) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE password_reset_tokens SET used_at = $1
+                 WHERE id = $2 AND used_at IS NULL AND expires_at > $1
+                 RETURNING *",
+        )
+        .bind(convert_datetime_utc_to_primitive(used_at))
+        .bind(id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use crate::{
+    domain::{
+        models::{
+            quiz::Quiz,
+            word_pair::{WordPair, WordPairFilter},
+        },
+        traits::repositories::{quiz_repository::IQuizRepository, repository::Repository},
+        types::ID,
+    },
+    infrastructure::{
+        storage::database::models::{
+            quiz::{QuizEntity, QuizQuestionEntity},
+            word_pair::WordPairEntity,
+        },
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct QuizPostgresRepository {
+    db: PgPool,
+}
+
+#[async_trait]
+impl Repository for QuizPostgresRepository {
+    type Pool = PgPool;
+    type Item = Quiz;
+    type Entity = QuizEntity;
+    type Error = Error;
+
+    fn new(db: Self::Pool) -> Self {
+        Self { db }
+    }
+
+    async fn insert(&self, quiz: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(quiz);
+
+        let mut tx = self.db.begin().await?;
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO quizzes (id, user_id, seed, source_language, target_language, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 RETURNING *",
+        )
+        .bind(entity.id)
+        .bind(entity.user_id)
+        .bind(entity.seed)
+        .bind(&entity.source_language)
+        .bind(&entity.target_language)
+        .bind(entity.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut questions = Vec::with_capacity(quiz.questions.len());
+
+        for question in &quiz.questions {
+            let question = QuizQuestionEntity::new(quiz.id, question);
+
+            let db_question = sqlx::query_as::<_, QuizQuestionEntity>(
+                "INSERT INTO quiz_questions (quiz_id, position, word_pair_id, prompt, options, answer_index)
+                     VALUES ($1, $2, $3, $4, $5, $6)
+                     RETURNING *",
+            )
+            .bind(question.quiz_id)
+            .bind(question.position)
+            .bind(question.word_pair_id)
+            .bind(&question.prompt)
+            .bind(&question.options)
+            .bind(question.answer_index)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            questions.push(db_question);
+        }
+
+        tx.commit().await?;
+
+        Ok(Self::Item::from((&db_entity, questions.as_slice())))
+    }
+
+    async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>("SELECT * FROM quizzes WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.db)
+            .await?;
+
+        let questions = sqlx::query_as::<_, QuizQuestionEntity>(
+            "SELECT * FROM quiz_questions WHERE quiz_id = $1 ORDER BY position",
+        )
+        .bind(id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(Self::Item::from((&db_entity, questions.as_slice())))
+    }
+
+    async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+        let res = sqlx::query("DELETE FROM quizzes WHERE id = $1")
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        if res.rows_affected() == 0 {
+            return Err(Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IQuizRepository for QuizPostgresRepository {
+    async fn select_word_pairs(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, WordPairEntity>(
+            "SELECT * FROM word_pairs
+                 WHERE user_id = $1 AND deleted_at IS NULL
+                     AND ($2::TEXT IS NULL OR source_language = $2)
+                     AND ($3::TEXT IS NULL OR target_language = $3)
+                     AND ($4::TIMESTAMP IS NULL OR created_at >= $4)
+                     AND ($5::TIMESTAMP IS NULL OR created_at < $5)
+                 ORDER BY created_at, id
+                 LIMIT $6",
+        )
+        .bind(user_id)
+        .bind(&filter.source_language)
+        .bind(&filter.target_language)
+        .bind(
+            filter
+                .created_from
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        )
+        .bind(
+            filter
+                .created_to
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        )
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(WordPair::from).collect())
+    }
+
+    async fn select_by_target_language(
+        &self,
+        user_id: &ID,
+        target_language: &str,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, WordPairEntity>(
+            "SELECT * FROM word_pairs
+                 WHERE user_id = $1 AND deleted_at IS NULL AND target_language = $2
+                 ORDER BY created_at, id
+                 LIMIT $3",
+        )
+        .bind(user_id)
+        .bind(target_language)
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(WordPair::from).collect())
+    }
+
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Quiz>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, QuizEntity>(
+            "SELECT * FROM quizzes WHERE user_id = $1 ORDER BY created_at, id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        let ids: Vec<ID> = db_entities.iter().map(|entity| entity.id).collect();
+
+        let mut questions: HashMap<ID, Vec<QuizQuestionEntity>> = HashMap::new();
+
+        for question in sqlx::query_as::<_, QuizQuestionEntity>(
+            "SELECT * FROM quiz_questions WHERE quiz_id = ANY($1) ORDER BY position",
+        )
+        .bind(&ids)
+        .fetch_all(&self.db)
+        .await?
+        {
+            questions
+                .entry(question.quiz_id)
+                .or_default()
+                .push(question);
+        }
+
+        let quizzes = db_entities
+            .iter()
+            .map(|entity| {
+                let questions = questions.remove(&entity.id).unwrap_or_default();
+
+                Quiz::from((entity, questions.as_slice()))
+            })
+            .collect();
+
+        Ok(quizzes)
+    }
+
+    async fn submit(&self, quiz: &Quiz) -> Result<Quiz, Self::Error> {
+        let entity = QuizEntity::from(quiz);
+
+        let mut tx = self.db.begin().await?;
+
+        let db_entity = sqlx::query_as::<_, QuizEntity>(
+            "UPDATE quizzes SET graded_at = $2
+                 WHERE id = $1 AND graded_at IS NULL
+                 RETURNING *",
+        )
+        .bind(entity.id)
+        .bind(entity.graded_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut questions = Vec::with_capacity(quiz.questions.len());
+
+        for question in &quiz.questions {
+            let question = QuizQuestionEntity::new(quiz.id, question);
+
+            let db_question = sqlx::query_as::<_, QuizQuestionEntity>(
+                "UPDATE quiz_questions SET selected = $3
+                     WHERE quiz_id = $1 AND position = $2
+                     RETURNING *",
+            )
+            .bind(question.quiz_id)
+            .bind(question.position)
+            .bind(question.selected)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            questions.push(db_question);
+        }
+
+        tx.commit().await?;
+
+        Ok(Quiz::from((&db_entity, questions.as_slice())))
+    }
+}
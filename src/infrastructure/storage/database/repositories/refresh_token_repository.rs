@@ -0,0 +1,172 @@
+use crate::{
+    domain::{
+        models::refresh_token::RefreshToken,
+        traits::repositories::{
+            refresh_token_repository::IRefreshTokenRepository, repository::Repository,
+        },
+        types::ID,
+    },
+    infrastructure::{
+        storage::database::models::refresh_token::RefreshTokenEntity,
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct RefreshTokenPostgresRepository {
+    db: PgPool,
+}
+
+#[async_trait]
+impl Repository for RefreshTokenPostgresRepository {
+    type Pool = PgPool;
+    type Item = RefreshToken;
+    type Entity = RefreshTokenEntity;
+    type Error = Error;
+
+    fn new(db: Self::Pool) -> Self {
+        Self { db }
+    }
+
+    async fn insert(&self, token: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(token);
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO refresh_tokens (id, family_id, user_id, token_hash, nonce, access_jti, access_expires_at, expires_at, used_at, revoked_at, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                 RETURNING *",
+        )
+        .bind(entity.id)
+        .bind(entity.family_id)
+        .bind(entity.user_id)
+        .bind(&entity.token_hash)
+        .bind(&entity.nonce)
+        .bind(entity.access_jti)
+        .bind(entity.access_expires_at)
+        .bind(entity.expires_at)
+        .bind(entity.used_at)
+        .bind(entity.revoked_at)
+        .bind(entity.created_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity =
+            sqlx::query_as::<_, Self::Entity>("SELECT * FROM refresh_tokens WHERE id = $1")
+                .bind(id)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM refresh_tokens WHERE id = $1")
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IRefreshTokenRepository for RefreshTokenPostgresRepository {
+    async fn select_by_hash(&self, token_hash: &str) -> Result<Self::Item, Self::Error> {
+        let db_entity =
+            sqlx::query_as::<_, Self::Entity>("SELECT * FROM refresh_tokens WHERE token_hash = $1")
+                .bind(token_hash)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_by_access_jti(&self, access_jti: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity =
+            sqlx::query_as::<_, Self::Entity>("SELECT * FROM refresh_tokens WHERE access_jti = $1")
+                .bind(access_jti)
+                .fetch_one(&self.db)
+                .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn mark_used(&self, id: &ID, used_at: &DateTime<Utc>) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE refresh_tokens SET used_at = $1
+                 WHERE id = $2 AND used_at IS NULL AND revoked_at IS NULL
+                 RETURNING *",
+        )
+        .bind(convert_datetime_utc_to_primitive(used_at))
+        .bind(id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn clear_used(&self, id: &ID) -> Result<(), Self::Error> {
+        sqlx::query(
+            "UPDATE refresh_tokens SET used_at = NULL WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_family(
+        &self,
+        family_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE refresh_tokens SET revoked_at = COALESCE(revoked_at, $1)
+                 WHERE family_id = $2
+                 RETURNING *",
+        )
+        .bind(convert_datetime_utc_to_primitive(revoked_at))
+        .bind(family_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn revoke_by_user_id(
+        &self,
+        user_id: &ID,
+        revoked_at: &DateTime<Utc>,
+    ) -> Result<u64, Self::Error> {
+        let res = sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = $1
+                 WHERE user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(convert_datetime_utc_to_primitive(revoked_at))
+        .bind(user_id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn delete_expired(&self, now: &DateTime<Utc>) -> Result<u64, Self::Error> {
+        let res = sqlx::query(
+            "DELETE FROM refresh_tokens
+                 WHERE expires_at <= $1 OR (revoked_at IS NOT NULL AND access_expires_at <= $1)",
+        )
+        .bind(convert_datetime_utc_to_primitive(now))
+        .execute(&self.db)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+}
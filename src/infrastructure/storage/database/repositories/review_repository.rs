@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use crate::{
+    domain::{
+        models::{
+            review::{DueCard, ReviewLog, ReviewState},
+            word_pair::{WordPair, WordPairFilter},
+        },
+        traits::repositories::{repository::Repository, review_repository::IReviewRepository},
+        types::ID,
+    },
+    infrastructure::{
+        storage::database::models::{
+            review::{ReviewLogEntity, ReviewStateEntity},
+            word_pair::WordPairEntity,
+        },
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct ReviewPostgresRepository {
+    db: PgPool,
+}
+
+#[async_trait]
+impl Repository for ReviewPostgresRepository {
+    type Pool = PgPool;
+    type Item = ReviewState;
+    type Entity = ReviewStateEntity;
+    type Error = Error;
+
+    fn new(db: Self::Pool) -> Self {
+        Self { db }
+    }
+
+    async fn insert(&self, state: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(state);
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO review_states (word_pair_id, user_id, ease, interval_days, repetitions, lapses, stability, difficulty, due_at, last_reviewed_at, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                 RETURNING *",
+        )
+        .bind(entity.word_pair_id)
+        .bind(entity.user_id)
+        .bind(entity.ease)
+        .bind(entity.interval_days)
+        .bind(entity.repetitions)
+        .bind(entity.lapses)
+        .bind(entity.stability)
+        .bind(entity.difficulty)
+        .bind(entity.due_at)
+        .bind(entity.last_reviewed_at)
+        .bind(entity.created_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_by_id(&self, word_pair_id: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM review_states WHERE word_pair_id = $1",
+        )
+        .bind(word_pair_id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn delete_by_id(&self, word_pair_id: &ID) -> Result<(), Self::Error> {
+        let res = sqlx::query("DELETE FROM review_states WHERE word_pair_id = $1")
+            .bind(word_pair_id)
+            .execute(&self.db)
+            .await?;
+
+        if res.rows_affected() == 0 {
+            return Err(Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IReviewRepository for ReviewPostgresRepository {
+    async fn select_due(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        now: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<DueCard>, Self::Error> {
+        let states = sqlx::query_as::<_, ReviewStateEntity>(
+            "SELECT review_states.* FROM review_states
+                 JOIN word_pairs ON word_pairs.id = review_states.word_pair_id
+                 WHERE review_states.user_id = $1 AND review_states.due_at <= $2
+                     AND word_pairs.deleted_at IS NULL
+                     AND ($3::TEXT IS NULL OR word_pairs.source_language = $3)
+                     AND ($4::TEXT IS NULL OR word_pairs.target_language = $4)
+                     AND ($5::TIMESTAMP IS NULL OR word_pairs.created_at >= $5)
+                     AND ($6::TIMESTAMP IS NULL OR word_pairs.created_at < $6)
+                 ORDER BY review_states.due_at, review_states.word_pair_id
+                 LIMIT $7",
+        )
+        .bind(user_id)
+        .bind(convert_datetime_utc_to_primitive(now))
+        .bind(&filter.source_language)
+        .bind(&filter.target_language)
+        .bind(
+            filter
+                .created_from
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        )
+        .bind(
+            filter
+                .created_to
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        )
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        let ids: Vec<ID> = states.iter().map(|state| state.word_pair_id).collect();
+
+        let mut word_pairs: HashMap<ID, WordPair> =
+            sqlx::query_as::<_, WordPairEntity>("SELECT * FROM word_pairs WHERE id = ANY($1)")
+                .bind(&ids)
+                .fetch_all(&self.db)
+                .await?
+                .iter()
+                .map(|entity| (entity.id, WordPair::from(entity)))
+                .collect();
+
+        let cards = states
+            .iter()
+            .filter_map(|state| {
+                Some(DueCard {
+                    word_pair: word_pairs.remove(&state.word_pair_id)?,
+                    review: ReviewState::from(state),
+                })
+            })
+            .collect();
+
+        Ok(cards)
+    }
+
+    async fn select_new(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, WordPairEntity>(
+            "SELECT * FROM word_pairs
+                 WHERE user_id = $1 AND deleted_at IS NULL
+                     AND NOT EXISTS (SELECT 1 FROM review_states WHERE review_states.word_pair_id = word_pairs.id)
+                     AND ($2::TEXT IS NULL OR source_language = $2)
+                     AND ($3::TEXT IS NULL OR target_language = $3)
+                     AND ($4::TIMESTAMP IS NULL OR created_at >= $4)
+                     AND ($5::TIMESTAMP IS NULL OR created_at < $5)
+                 ORDER BY created_at, id
+                 LIMIT $6",
+        )
+        .bind(user_id)
+        .bind(&filter.source_language)
+        .bind(&filter.target_language)
+        .bind(filter.created_from.as_ref().map(convert_datetime_utc_to_primitive))
+        .bind(filter.created_to.as_ref().map(convert_datetime_utc_to_primitive))
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(WordPair::from).collect())
+    }
+
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM review_states WHERE user_id = $1 ORDER BY due_at, word_pair_id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn select_logs_by_user_id(&self, user_id: &ID) -> Result<Vec<ReviewLog>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, ReviewLogEntity>(
+            "SELECT * FROM review_logs WHERE user_id = $1 ORDER BY reviewed_at, id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(ReviewLog::from).collect())
+    }
+
+    async fn upsert(&self, state: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(state);
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO review_states (word_pair_id, user_id, ease, interval_days, repetitions, lapses, stability, difficulty, due_at, last_reviewed_at, created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                 ON CONFLICT (word_pair_id) DO UPDATE SET
+                     ease = EXCLUDED.ease,
+                     interval_days = EXCLUDED.interval_days,
+                     repetitions = EXCLUDED.repetitions,
+                     lapses = EXCLUDED.lapses,
+                     stability = EXCLUDED.stability,
+                     difficulty = EXCLUDED.difficulty,
+                     due_at = EXCLUDED.due_at,
+                     last_reviewed_at = EXCLUDED.last_reviewed_at
+                 RETURNING *",
+        )
+        .bind(entity.word_pair_id)
+        .bind(entity.user_id)
+        .bind(entity.ease)
+        .bind(entity.interval_days)
+        .bind(entity.repetitions)
+        .bind(entity.lapses)
+        .bind(entity.stability)
+        .bind(entity.difficulty)
+        .bind(entity.due_at)
+        .bind(entity.last_reviewed_at)
+        .bind(entity.created_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn insert_log(&self, log: &ReviewLog) -> Result<ReviewLog, Self::Error> {
+        let entity = ReviewLogEntity::from(log);
+
+        let db_entity = sqlx::query_as::<_, ReviewLogEntity>(
+            "INSERT INTO review_logs (id, user_id, word_pair_id, grade, previous_interval_days, interval_days, reviewed_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)
+                 RETURNING *",
+        )
+        .bind(entity.id)
+        .bind(entity.user_id)
+        .bind(entity.word_pair_id)
+        .bind(&entity.grade)
+        .bind(entity.previous_interval_days)
+        .bind(entity.interval_days)
+        .bind(entity.reviewed_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(ReviewLog::from(&db_entity))
+    }
+}
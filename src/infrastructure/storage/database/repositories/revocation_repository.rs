@@ -0,0 +1,127 @@
+use crate::{
+    domain::{
+        models::revocation::{RevokedToken, UserRevocation},
+        traits::repositories::{
+            repository::Repository, revocation_repository::IRevocationRepository,
+        },
+        types::ID,
+    },
+    infrastructure::{
+        storage::database::models::revocation::{RevokedTokenEntity, UserRevocationEntity},
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct RevocationPostgresRepository {
+    db: PgPool,
+}
+
+#[async_trait]
+impl Repository for RevocationPostgresRepository {
+    type Pool = PgPool;
+    type Item = RevokedToken;
+    type Entity = RevokedTokenEntity;
+    type Error = Error;
+
+    fn new(db: Self::Pool) -> Self {
+        Self { db }
+    }
+
+    async fn insert(&self, token: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(token);
+
+        // Revoking the same token twice keeps the first record.
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO revoked_tokens (jti, user_id, expires_at, revoked_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (jti) DO UPDATE SET jti = EXCLUDED.jti
+                 RETURNING *",
+        )
+        .bind(entity.jti)
+        .bind(entity.user_id)
+        .bind(entity.expires_at)
+        .bind(entity.revoked_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_by_id(&self, jti: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "SELECT jti, user_id, expires_at, revoked_at FROM revoked_tokens WHERE jti = $1",
+        )
+        .bind(jti)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn delete_by_id(&self, jti: &ID) -> Result<(), Self::Error> {
+        sqlx::query("DELETE FROM revoked_tokens WHERE jti = $1")
+            .bind(jti)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IRevocationRepository for RevocationPostgresRepository {
+    async fn select_active(&self, now: &DateTime<Utc>) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT jti, user_id, expires_at, revoked_at FROM revoked_tokens WHERE expires_at > $1",
+        )
+        .bind(convert_datetime_utc_to_primitive(now))
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn delete_expired(&self, now: &DateTime<Utc>) -> Result<u64, Self::Error> {
+        let res = sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= $1")
+            .bind(convert_datetime_utc_to_primitive(now))
+            .execute(&self.db)
+            .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn upsert_user_revocation(
+        &self,
+        revocation: &UserRevocation,
+    ) -> Result<UserRevocation, Self::Error> {
+        let entity = UserRevocationEntity::from(revocation);
+
+        let db_entity = sqlx::query_as::<_, UserRevocationEntity>(
+            "INSERT INTO user_token_revocations (user_id, revoked_before)
+                 VALUES ($1, $2)
+                 ON CONFLICT (user_id) DO UPDATE
+                 SET revoked_before = GREATEST(user_token_revocations.revoked_before, EXCLUDED.revoked_before)
+                 RETURNING *",
+        )
+        .bind(entity.user_id)
+        .bind(entity.revoked_before)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(UserRevocation::from(&db_entity))
+    }
+
+    async fn select_user_revocations(&self) -> Result<Vec<UserRevocation>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, UserRevocationEntity>(
+            "SELECT user_id, revoked_before FROM user_token_revocations",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(UserRevocation::from).collect())
+    }
+}
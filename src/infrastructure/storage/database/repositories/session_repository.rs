@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use crate::{
+    domain::{
+        models::session::{ReviewSession, SessionCard},
+        traits::repositories::{repository::Repository, session_repository::ISessionRepository},
+        types::ID,
+    },
+    infrastructure::{
+        storage::database::models::session::{ReviewSessionEntity, SessionCardEntity},
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct SessionPostgresRepository {
+    db: PgPool,
+}
+
+impl SessionPostgresRepository {
+    async fn select_cards(&self, session_id: &ID) -> Result<Vec<SessionCardEntity>, Error> {
+        sqlx::query_as::<_, SessionCardEntity>(
+            "SELECT * FROM review_session_cards WHERE session_id = $1 ORDER BY position",
+        )
+        .bind(session_id)
+        .fetch_all(&self.db)
+        .await
+    }
+}
+
+#[async_trait]
+impl Repository for SessionPostgresRepository {
+    type Pool = PgPool;
+    type Item = ReviewSession;
+    type Entity = ReviewSessionEntity;
+    type Error = Error;
+
+    fn new(db: Self::Pool) -> Self {
+        Self { db }
+    }
+
+    async fn insert(&self, session: &Self::Item) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(session);
+
+        let mut tx = self.db.begin().await?;
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "INSERT INTO review_sessions (id, user_id, direction, source_language, target_language, created_at, finished_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)
+                 RETURNING *",
+        )
+        .bind(entity.id)
+        .bind(entity.user_id)
+        .bind(&entity.direction)
+        .bind(&entity.source_language)
+        .bind(&entity.target_language)
+        .bind(entity.created_at)
+        .bind(entity.finished_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut cards = Vec::with_capacity(session.cards.len());
+
+        for card in &session.cards {
+            let card = SessionCardEntity::new(session.id, card);
+
+            let db_card = sqlx::query_as::<_, SessionCardEntity>(
+                "INSERT INTO review_session_cards (session_id, position, word_pair_id, direction, is_new, prompt, expected, grade, answer, answered_at, duration_ms)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                     RETURNING *",
+            )
+            .bind(card.session_id)
+            .bind(card.position)
+            .bind(card.word_pair_id)
+            .bind(&card.direction)
+            .bind(card.is_new)
+            .bind(&card.prompt)
+            .bind(&card.expected)
+            .bind(&card.grade)
+            .bind(&card.answer)
+            .bind(card.answered_at)
+            .bind(card.duration_ms)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            cards.push(db_card);
+        }
+
+        tx.commit().await?;
+
+        Ok(Self::Item::from((&db_entity, cards.as_slice())))
+    }
+
+    async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity =
+            sqlx::query_as::<_, Self::Entity>("SELECT * FROM review_sessions WHERE id = $1")
+                .bind(id)
+                .fetch_one(&self.db)
+                .await?;
+
+        let cards = self.select_cards(id).await?;
+
+        Ok(Self::Item::from((&db_entity, cards.as_slice())))
+    }
+
+    async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+        let res = sqlx::query("DELETE FROM review_sessions WHERE id = $1")
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+
+        if res.rows_affected() == 0 {
+            return Err(Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ISessionRepository for SessionPostgresRepository {
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM review_sessions WHERE user_id = $1 ORDER BY created_at, id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        let ids: Vec<ID> = db_entities.iter().map(|entity| entity.id).collect();
+
+        let mut cards: HashMap<ID, Vec<SessionCardEntity>> = HashMap::new();
+
+        for card in sqlx::query_as::<_, SessionCardEntity>(
+            "SELECT * FROM review_session_cards WHERE session_id = ANY($1) ORDER BY position",
+        )
+        .bind(&ids)
+        .fetch_all(&self.db)
+        .await?
+        {
+            cards.entry(card.session_id).or_default().push(card);
+        }
+
+        let sessions = db_entities
+            .iter()
+            .map(|entity| {
+                let cards = cards.remove(&entity.id).unwrap_or_default();
+
+                Self::Item::from((entity, cards.as_slice()))
+            })
+            .collect();
+
+        Ok(sessions)
+    }
+
+    async fn answer_card(
+        &self,
+        session_id: &ID,
+        card: &SessionCard,
+    ) -> Result<SessionCard, Self::Error> {
+        let entity = SessionCardEntity::new(*session_id, card);
+
+        let db_entity = sqlx::query_as::<_, SessionCardEntity>(
+            "UPDATE review_session_cards SET
+                 grade = $3,
+                 answer = $4,
+                 answered_at = $5,
+                 duration_ms = $6
+                 WHERE session_id = $1 AND position = $2 AND grade IS NULL
+                 RETURNING *",
+        )
+        .bind(entity.session_id)
+        .bind(entity.position)
+        .bind(&entity.grade)
+        .bind(&entity.answer)
+        .bind(entity.answered_at)
+        .bind(entity.duration_ms)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(SessionCard::from(&db_entity))
+    }
+
+    async fn clear_card(&self, session_id: &ID, position: i32) -> Result<(), Self::Error> {
+        sqlx::query(
+            "UPDATE review_session_cards SET
+                 grade = NULL,
+                 answer = NULL,
+                 answered_at = NULL,
+                 duration_ms = NULL
+                 WHERE session_id = $1 AND position = $2",
+        )
+        .bind(session_id)
+        .bind(position)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn finish(
+        &self,
+        session_id: &ID,
+        finished_at: &DateTime<Utc>,
+    ) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE review_sessions SET finished_at = $2 WHERE id = $1 RETURNING *",
+        )
+        .bind(session_id)
+        .bind(convert_datetime_utc_to_primitive(finished_at))
+        .fetch_one(&self.db)
+        .await?;
+
+        let cards = self.select_cards(session_id).await?;
+
+        Ok(Self::Item::from((&db_entity, cards.as_slice())))
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    domain::{
+        models::stats::{
+            DailyCount, DailyReviews, LanguagePairStats, MATURE_INTERVAL_DAYS, RetentionCount,
+        },
+        traits::stats::stats_store::IStatsStore,
+        types::ID,
+    },
+    infrastructure::utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Error, postgres::PgPool};
+
+#[derive(Clone)]
+pub struct StatsPostgresStore {
+    db: PgPool,
+}
+
+impl StatsPostgresStore {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl IStatsStore for StatsPostgresStore {
+    type Error = Error;
+
+    async fn time_zone_exists(&self, time_zone: &str) -> Result<bool, Self::Error> {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1)",
+        )
+        .bind(time_zone)
+        .fetch_one(&self.db)
+        .await
+    }
+
+    async fn today(&self, time_zone: &str) -> Result<NaiveDate, Self::Error> {
+        sqlx::query_scalar::<_, NaiveDate>("SELECT (NOW() AT TIME ZONE $1)::DATE")
+            .bind(time_zone)
+            .fetch_one(&self.db)
+            .await
+    }
+
+    async fn language_pairs(&self, user_id: &ID) -> Result<Vec<LanguagePairStats>, Self::Error> {
+        let rows = sqlx::query_as::<_, (String, String, i64, i64, i64, i64)>(
+            "SELECT word_pairs.source_language, word_pairs.target_language,
+                     COUNT(*),
+                     COUNT(*) FILTER (WHERE review_states.word_pair_id IS NULL),
+                     COUNT(*) FILTER (WHERE review_states.interval_days < $2),
+                     COUNT(*) FILTER (WHERE review_states.interval_days >= $2)
+                 FROM word_pairs
+                 LEFT JOIN review_states ON review_states.word_pair_id = word_pairs.id
+                 WHERE word_pairs.user_id = $1 AND word_pairs.deleted_at IS NULL
+                 GROUP BY word_pairs.source_language, word_pairs.target_language
+                 ORDER BY word_pairs.source_language, word_pairs.target_language",
+        )
+        .bind(user_id)
+        .bind(MATURE_INTERVAL_DAYS)
+        .fetch_all(&self.db)
+        .await?;
+
+        let stats = rows
+            .into_iter()
+            .map(
+                |(
+                    source_language,
+                    target_language,
+                    word_pairs,
+                    new_cards,
+                    young_cards,
+                    mature_cards,
+                )| {
+                    LanguagePairStats {
+                        source_language,
+                        target_language,
+                        word_pairs,
+                        new_cards,
+                        young_cards,
+                        mature_cards,
+                    }
+                },
+            )
+            .collect();
+
+        Ok(stats)
+    }
+
+    async fn added_per_day(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        days: i64,
+    ) -> Result<Vec<DailyCount>, Self::Error> {
+        let rows = sqlx::query_as::<_, (NaiveDate, i64)>(
+            "WITH days AS (
+                 SELECT generate_series(
+                     (NOW() AT TIME ZONE $2)::DATE - ($3::INTEGER - 1),
+                     (NOW() AT TIME ZONE $2)::DATE,
+                     INTERVAL '1 day'
+                 )::DATE AS day
+             )
+             SELECT days.day, COUNT(word_pairs.id)
+                 FROM days
+                 LEFT JOIN word_pairs ON word_pairs.user_id = $1
+                     AND word_pairs.deleted_at IS NULL
+                     AND ((word_pairs.created_at AT TIME ZONE 'UTC') AT TIME ZONE $2)::DATE = days.day
+                 GROUP BY days.day
+                 ORDER BY days.day",
+        )
+        .bind(user_id)
+        .bind(time_zone)
+        .bind(days)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(day, count)| DailyCount { day, count })
+            .collect())
+    }
+
+    async fn added_per_week(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        weeks: i64,
+    ) -> Result<Vec<DailyCount>, Self::Error> {
+        let rows = sqlx::query_as::<_, (NaiveDate, i64)>(
+            "WITH weeks AS (
+                 SELECT generate_series(
+                     date_trunc('week', NOW() AT TIME ZONE $2) - ($3::INTEGER - 1) * INTERVAL '1 week',
+                     date_trunc('week', NOW() AT TIME ZONE $2),
+                     INTERVAL '1 week'
+                 )::DATE AS week
+             )
+             SELECT weeks.week, COUNT(word_pairs.id)
+                 FROM weeks
+                 LEFT JOIN word_pairs ON word_pairs.user_id = $1
+                     AND word_pairs.deleted_at IS NULL
+                     AND date_trunc('week', (word_pairs.created_at AT TIME ZONE 'UTC') AT TIME ZONE $2)::DATE = weeks.week
+                 GROUP BY weeks.week
+                 ORDER BY weeks.week",
+        )
+        .bind(user_id)
+        .bind(time_zone)
+        .bind(weeks)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(day, count)| DailyCount { day, count })
+            .collect())
+    }
+
+    async fn reviews_per_day(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        days: i64,
+    ) -> Result<Vec<DailyReviews>, Self::Error> {
+        let rows = sqlx::query_as::<_, (NaiveDate, i64, i64)>(
+            "WITH days AS (
+                 SELECT generate_series(
+                     (NOW() AT TIME ZONE $2)::DATE - ($3::INTEGER - 1),
+                     (NOW() AT TIME ZONE $2)::DATE,
+                     INTERVAL '1 day'
+                 )::DATE AS day
+             )
+             SELECT days.day,
+                     COUNT(review_logs.id),
+                     COUNT(review_logs.id) FILTER (WHERE review_logs.grade <> 'again')
+                 FROM days
+                 LEFT JOIN review_logs ON review_logs.user_id = $1
+                     AND ((review_logs.reviewed_at AT TIME ZONE 'UTC') AT TIME ZONE $2)::DATE = days.day
+                 GROUP BY days.day
+                 ORDER BY days.day",
+        )
+        .bind(user_id)
+        .bind(time_zone)
+        .bind(days)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(day, reviews, correct)| DailyReviews {
+                day,
+                reviews,
+                correct,
+            })
+            .collect())
+    }
+
+    async fn retention(
+        &self,
+        user_id: &ID,
+        since: &DateTime<Utc>,
+    ) -> Result<RetentionCount, Self::Error> {
+        let (reviews, remembered) = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT COUNT(*), COUNT(*) FILTER (WHERE grade <> 'again')
+                 FROM review_logs
+                 WHERE user_id = $1 AND reviewed_at >= $2 AND previous_interval_days > 0",
+        )
+        .bind(user_id)
+        .bind(convert_datetime_utc_to_primitive(since))
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(RetentionCount {
+            reviews,
+            remembered,
+        })
+    }
+
+    async fn review_days(
+        &self,
+        user_id: &ID,
+        time_zone: &str,
+        limit: i64,
+    ) -> Result<Vec<NaiveDate>, Self::Error> {
+        sqlx::query_scalar::<_, NaiveDate>(
+            "SELECT DISTINCT ((reviewed_at AT TIME ZONE 'UTC') AT TIME ZONE $2)::DATE AS day
+                 FROM review_logs
+                 WHERE user_id = $1
+                 ORDER BY day DESC
+                 LIMIT $3",
+        )
+        .bind(user_id)
+        .bind(time_zone)
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await
+    }
+}
@@ -1,12 +1,19 @@
 use crate::{
     domain::{
-        models::user::User,
+        models::{role::Role, user::User},
         traits::repositories::{repository::Repository, user_repository::IUserRepository},
         types::ID,
     },
-    infrastructure::storage::database::models::user::UserEntity,
+    infrastructure::{
+        storage::database::models::user::UserEntity,
+        utils::{
+            convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+            string::escape_like::escape_like,
+        },
+    },
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{Error, postgres::PgPool};
 
 #[derive(Clone)]
@@ -29,14 +36,17 @@ impl Repository for UserPostgresRepository {
         let entity = Self::Entity::from(user);
 
         let db_entity = sqlx::query_as::<_, Self::Entity>(
-            "INSERT INTO users (id, hashed_password, key, name, created_at, updated_at) 
-                 VALUES ($1, $2, $3, $4, $5, $6) 
+            "INSERT INTO users (id, hashed_password, key, name, role, disabled_at, delete_after, created_at, updated_at) 
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) 
                  RETURNING *",
         )
         .bind(&entity.id)
         .bind(&entity.hashed_password)
         .bind(&entity.key)
         .bind(&entity.name)
+        .bind(&entity.role)
+        .bind(entity.disabled_at)
+        .bind(entity.delete_after)
         .bind(&entity.created_at)
         .bind(&entity.updated_at)
         .fetch_one(&self.db)
@@ -49,7 +59,7 @@ impl Repository for UserPostgresRepository {
 
     async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
         let db_entity = sqlx::query_as::<_, Self::Entity>(
-            "SELECT id, key, name, hashed_password, created_at, updated_at FROM users WHERE id = $1"
+            "SELECT id, key, name, hashed_password, role, disabled_at, delete_after, created_at, updated_at FROM users WHERE id = $1"
             )
             .bind(id)
             .fetch_one(&self.db)
@@ -74,7 +84,7 @@ impl Repository for UserPostgresRepository {
 impl IUserRepository for UserPostgresRepository {
     async fn select_by_key(&self, key: &str) -> Result<Self::Item, Self::Error> {
         let db_entity = sqlx::query_as::<_, Self::Entity>(
-            "SELECT id, key, name, hashed_password, created_at, updated_at FROM users WHERE key=$1",
+            "SELECT id, key, name, hashed_password, role, disabled_at, delete_after, created_at, updated_at FROM users WHERE key=$1",
         )
         .bind(key)
         .fetch_one(&self.db)
@@ -95,7 +105,6 @@ impl IUserRepository for UserPostgresRepository {
             .bind(&entity.name)
             .bind(&entity.hashed_password)
             .bind(&entity.updated_at)
-            .bind(&entity.created_at)
             .bind(&entity.id)
             .fetch_one(&self.db)
             .await?;
@@ -104,4 +113,101 @@ impl IUserRepository for UserPostgresRepository {
 
         Ok(updated_user)
     }
+
+    async fn select_page(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM users
+                 WHERE $1::TEXT IS NULL OR lower(key) LIKE $1 || '%' OR lower(name) LIKE $1 || '%'
+                 ORDER BY key
+                 LIMIT $2 OFFSET $3",
+        )
+        .bind(search.map(|search| escape_like(&search.to_lowercase())))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn count(&self, search: Option<&str>) -> Result<i64, Self::Error> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM users
+                 WHERE $1::TEXT IS NULL OR lower(key) LIKE $1 || '%' OR lower(name) LIKE $1 || '%'",
+        )
+        .bind(search.map(|search| escape_like(&search.to_lowercase())))
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn update_disabled(
+        &self,
+        id: &ID,
+        disabled_at: Option<&DateTime<Utc>>,
+    ) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE users SET disabled_at = $1 WHERE id = $2 RETURNING *",
+        )
+        .bind(disabled_at.map(convert_datetime_utc_to_primitive))
+        .bind(id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn update_role(&self, id: &ID, role: Role) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE users SET role = $1 WHERE id = $2 RETURNING *",
+        )
+        .bind(role.as_str())
+        .bind(id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn update_delete_after(
+        &self,
+        id: &ID,
+        delete_after: Option<&DateTime<Utc>>,
+    ) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE users SET delete_after = $1 WHERE id = $2 RETURNING *",
+        )
+        .bind(delete_after.map(convert_datetime_utc_to_primitive))
+        .bind(id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_inactive_ids(&self) -> Result<Vec<ID>, Self::Error> {
+        let ids = sqlx::query_scalar::<_, ID>(
+            "SELECT id FROM users WHERE disabled_at IS NOT NULL OR delete_after IS NOT NULL",
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(ids)
+    }
+
+    async fn delete_due(&self, now: &DateTime<Utc>) -> Result<Vec<ID>, Self::Error> {
+        let ids =
+            sqlx::query_scalar::<_, ID>("DELETE FROM users WHERE delete_after <= $1 RETURNING id")
+                .bind(convert_datetime_utc_to_primitive(now))
+                .fetch_all(&self.db)
+                .await?;
+
+        Ok(ids)
+    }
 }
@@ -1,12 +1,19 @@
 use crate::{
     domain::{
-        models::word_pair::WordPair,
+        models::word_pair::{
+            HIGHLIGHT_END, HIGHLIGHT_START, LanguagePairCount, WordPair, WordPairCursor,
+            WordPairFilter, WordPairSearchHit, WordPairSort,
+        },
         traits::repositories::{repository::Repository, word_pair_repository::IWordPairRepository},
         types::ID,
     },
-    infrastructure::storage::database::models::word_pair::WordPairEntity,
+    infrastructure::{
+        storage::database::models::word_pair::{WordPairEntity, WordPairSearchEntity},
+        utils::convert::datetime_to_primitive::convert_datetime_utc_to_primitive,
+    },
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{Error, postgres::PgPool};
 
 #[derive(Clone)]
@@ -29,15 +36,18 @@ impl Repository for WordPairPostgresRepository {
         let entity = Self::Entity::from(word_pair);
 
         let db_entity = sqlx::query_as::<_, Self::Entity>(
-            "INSERT INTO word_pairs (id, user_id, target_text, source_text, target_language, source_language, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *", 
+            "INSERT INTO word_pairs (id, user_id, target_text, source_text, target_language, source_language, source_key, created_at, updated_at, deleted_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *", 
             )
-            .bind(&entity.id)
-            .bind(&entity.user_id)
+            .bind(entity.id)
+            .bind(entity.user_id)
             .bind(&entity.target_text)
             .bind(&entity.source_text)
             .bind(&entity.target_language)
             .bind(&entity.source_language)
-            .bind(&entity.created_at)
+            .bind(&entity.source_key)
+            .bind(entity.created_at)
+            .bind(entity.updated_at)
+            .bind(entity.deleted_at)
             .fetch_one(&self.db)
             .await?;
 
@@ -48,7 +58,7 @@ impl Repository for WordPairPostgresRepository {
 
     async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
         let db_entity = sqlx::query_as::<_, Self::Entity>(
-            "SELECT id, user_id, target_text, source_text, target_language, source_language, created_at FROM word_pairs WHERE id = $1"
+            "SELECT id, user_id, target_text, source_text, target_language, source_language, source_key, created_at, updated_at, deleted_at FROM word_pairs WHERE id = $1"
             )
             .bind(id)
             .fetch_one(&self.db)
@@ -60,11 +70,15 @@ impl Repository for WordPairPostgresRepository {
     }
 
     async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
-        sqlx::query("DELETE FROM word_pairs WHERE id = $1")
+        let res = sqlx::query("DELETE FROM word_pairs WHERE id = $1")
             .bind(id)
             .execute(&self.db)
             .await?;
 
+        if res.rows_affected() == 0 {
+            return Err(Error::RowNotFound);
+        }
+
         Ok(())
     }
 }
@@ -73,7 +87,7 @@ impl Repository for WordPairPostgresRepository {
 impl IWordPairRepository for WordPairPostgresRepository {
     async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
         let db_entities = sqlx::query_as::<_, Self::Entity>(
-            "SELECT id, user_id, target_text, source_text, target_language, source_language, created_at FROM word_pairs WHERE user_id = $1"
+            "SELECT id, user_id, target_text, source_text, target_language, source_language, source_key, created_at, updated_at, deleted_at FROM word_pairs WHERE user_id = $1 AND deleted_at IS NULL ORDER BY created_at, id"
             )
             .bind(user_id)
             .fetch_all(&self.db)
@@ -87,4 +101,281 @@ impl IWordPairRepository for WordPairPostgresRepository {
 
         Ok(word_pairs)
     }
+
+    async fn select_page(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        sort: WordPairSort,
+        after: Option<&WordPairCursor>,
+        limit: i64,
+    ) -> Result<Vec<Self::Item>, Self::Error> {
+        // Keyset pagination: every order ends with `id`, so the sort key and
+        // id of the last row pin down where the next page starts.
+        let (after_clause, order_by) = match sort {
+            WordPairSort::Newest => (
+                "(created_at, id) < ($6::TIMESTAMP, $7)",
+                "created_at DESC, id DESC",
+            ),
+            WordPairSort::Oldest => ("(created_at, id) > ($6::TIMESTAMP, $7)", "created_at, id"),
+            WordPairSort::SourceText => ("(source_text, id) > ($6, $7)", "source_text, id"),
+            WordPairSort::TargetText => ("(target_text, id) > ($6, $7)", "target_text, id"),
+        };
+
+        let query = format!(
+            "SELECT * FROM word_pairs
+                 WHERE user_id = $1 AND deleted_at IS NULL
+                     AND ($2::TEXT IS NULL OR source_language = $2)
+                     AND ($3::TEXT IS NULL OR target_language = $3)
+                     AND ($4::TIMESTAMP IS NULL OR created_at >= $4)
+                     AND ($5::TIMESTAMP IS NULL OR created_at < $5)
+                     AND ($6::TEXT IS NULL OR {after_clause})
+                 ORDER BY {order_by}
+                 LIMIT $8"
+        );
+
+        let db_entities = sqlx::query_as::<_, Self::Entity>(&query)
+            .bind(user_id)
+            .bind(&filter.source_language)
+            .bind(&filter.target_language)
+            .bind(
+                filter
+                    .created_from
+                    .as_ref()
+                    .map(convert_datetime_utc_to_primitive),
+            )
+            .bind(
+                filter
+                    .created_to
+                    .as_ref()
+                    .map(convert_datetime_utc_to_primitive),
+            )
+            .bind(after.map(|cursor| cursor.key.as_str()))
+            .bind(after.map(|cursor| cursor.id))
+            .bind(limit)
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn search(
+        &self,
+        user_id: &ID,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<WordPairSearchHit>, Self::Error> {
+        // Each text is matched with the search configuration of its own
+        // language, trigram similarity catches typos the stemmer doesn't.
+        let db_entities = sqlx::query_as::<_, WordPairSearchEntity>(
+            "WITH matches AS (
+                     SELECT *,
+                         websearch_to_tsquery(word_pair_search_config(source_language), $2) AS source_query,
+                         websearch_to_tsquery(word_pair_search_config(target_language), $2) AS target_query
+                     FROM word_pairs
+                     WHERE user_id = $1 AND deleted_at IS NULL
+                 )
+                 SELECT id, user_id, target_text, source_text, target_language, source_language,
+                     source_key, created_at, updated_at, deleted_at,
+                     (ts_rank(to_tsvector(word_pair_search_config(source_language), source_text), source_query)
+                         + ts_rank(to_tsvector(word_pair_search_config(target_language), target_text), target_query)
+                         + GREATEST(similarity(source_text, $2), similarity(target_text, $2)))::REAL AS rank,
+                     ts_headline(word_pair_search_config(source_language), source_text, source_query, $3) AS source_highlight,
+                     ts_headline(word_pair_search_config(target_language), target_text, target_query, $3) AS target_highlight
+                 FROM matches
+                 WHERE to_tsvector(word_pair_search_config(source_language), source_text) @@ source_query
+                     OR to_tsvector(word_pair_search_config(target_language), target_text) @@ target_query
+                     OR source_text % $2
+                     OR target_text % $2
+                 ORDER BY rank DESC, id
+                 LIMIT $4",
+        )
+        .bind(user_id)
+        .bind(query)
+        .bind(format!(
+            "StartSel={HIGHLIGHT_START}, StopSel={HIGHLIGHT_END}, HighlightAll=true"
+        ))
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(WordPairSearchHit::from).collect())
+    }
+
+    async fn count(&self, user_id: &ID, filter: &WordPairFilter) -> Result<i64, Self::Error> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM word_pairs
+                 WHERE user_id = $1 AND deleted_at IS NULL
+                     AND ($2::TEXT IS NULL OR source_language = $2)
+                     AND ($3::TEXT IS NULL OR target_language = $3)
+                     AND ($4::TIMESTAMP IS NULL OR created_at >= $4)
+                     AND ($5::TIMESTAMP IS NULL OR created_at < $5)",
+        )
+        .bind(user_id)
+        .bind(&filter.source_language)
+        .bind(&filter.target_language)
+        .bind(
+            filter
+                .created_from
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        )
+        .bind(
+            filter
+                .created_to
+                .as_ref()
+                .map(convert_datetime_utc_to_primitive),
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn count_by_language_pair(
+        &self,
+        user_id: &ID,
+    ) -> Result<Vec<LanguagePairCount>, Self::Error> {
+        let rows = sqlx::query_as::<_, (String, String, i64)>(
+            "SELECT source_language, target_language, COUNT(*) FROM word_pairs
+                 WHERE user_id = $1 AND deleted_at IS NULL
+                 GROUP BY source_language, target_language
+                 ORDER BY source_language, target_language",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        let counts = rows
+            .into_iter()
+            .map(
+                |(source_language, target_language, count)| LanguagePairCount {
+                    source_language,
+                    target_language,
+                    count,
+                },
+            )
+            .collect();
+
+        Ok(counts)
+    }
+
+    async fn update_by_id(
+        &self,
+        updated_word_pair: &Self::Item,
+    ) -> Result<Self::Item, Self::Error> {
+        let entity = Self::Entity::from(updated_word_pair);
+
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE word_pairs
+                 SET target_text = $1, source_text = $2, target_language = $3, source_language = $4, source_key = $5, updated_at = $6
+                 WHERE id = $7 AND deleted_at IS NULL
+                 RETURNING *",
+        )
+        .bind(&entity.target_text)
+        .bind(&entity.source_text)
+        .bind(&entity.target_language)
+        .bind(&entity.source_language)
+        .bind(&entity.source_key)
+        .bind(entity.updated_at)
+        .bind(entity.id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn select_same_source(
+        &self,
+        word_pair: &Self::Item,
+    ) -> Result<Vec<Self::Item>, Self::Error> {
+        let entity = Self::Entity::from(word_pair);
+
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM word_pairs
+                 WHERE user_id = $1 AND source_language = $2 AND target_language = $3
+                     AND source_key = $4 AND id <> $5 AND deleted_at IS NULL
+                 ORDER BY created_at, id",
+        )
+        .bind(entity.user_id)
+        .bind(&entity.source_language)
+        .bind(&entity.target_language)
+        .bind(&entity.source_key)
+        .bind(entity.id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn select_duplicates(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM (
+                     SELECT *, COUNT(*) OVER (PARTITION BY source_language, target_language, source_key) AS copies
+                     FROM word_pairs
+                     WHERE user_id = $1 AND deleted_at IS NULL
+                 ) AS word_pairs
+                 WHERE copies > 1
+                 ORDER BY source_language, target_language, source_key, created_at, id",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn select_trash_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+        let db_entities = sqlx::query_as::<_, Self::Entity>(
+            "SELECT * FROM word_pairs
+                 WHERE user_id = $1 AND deleted_at IS NOT NULL
+                 ORDER BY deleted_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(db_entities.iter().map(Self::Item::from).collect())
+    }
+
+    async fn trash_by_id(
+        &self,
+        id: &ID,
+        deleted_at: &DateTime<Utc>,
+    ) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE word_pairs SET deleted_at = $1
+                 WHERE id = $2 AND deleted_at IS NULL
+                 RETURNING *",
+        )
+        .bind(convert_datetime_utc_to_primitive(deleted_at))
+        .bind(id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn restore_by_id(&self, id: &ID, user_id: &ID) -> Result<Self::Item, Self::Error> {
+        let db_entity = sqlx::query_as::<_, Self::Entity>(
+            "UPDATE word_pairs SET deleted_at = NULL
+                 WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+                 RETURNING *",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(Self::Item::from(&db_entity))
+    }
+
+    async fn delete_trashed(&self, deleted_before: &DateTime<Utc>) -> Result<u64, Self::Error> {
+        let res = sqlx::query("DELETE FROM word_pairs WHERE deleted_at < $1")
+            .bind(convert_datetime_utc_to_primitive(deleted_before))
+            .execute(&self.db)
+            .await?;
+
+        Ok(res.rows_affected())
+    }
 }
@@ -0,0 +1,102 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{
+    models::login_attempt::{Lockout, LoginAttempts},
+    traits::auth::login_attempt_store::ILoginAttemptStore,
+};
+
+/// Process-local attempt store for tests and single instance setups.
+#[derive(Debug, Clone, Default)]
+pub struct LoginAttemptMemoryStore {
+    attempts: Arc<Mutex<HashMap<String, LoginAttempts>>>,
+    lockouts: Arc<Mutex<Vec<Lockout>>>,
+}
+
+impl LoginAttemptMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lockouts(&self) -> Vec<Lockout> {
+        self.lockouts.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ILoginAttemptStore for LoginAttemptMemoryStore {
+    type Error = Infallible;
+
+    async fn get(&self, subject: &str) -> Result<Option<LoginAttempts>, Self::Error> {
+        Ok(self.attempts.lock().unwrap().get(subject).cloned())
+    }
+
+    async fn record_failure(
+        &self,
+        subject: &str,
+        now: &DateTime<Utc>,
+        window: &Duration,
+    ) -> Result<LoginAttempts, Self::Error> {
+        let mut attempts = self.attempts.lock().unwrap();
+
+        let entry = attempts
+            .entry(subject.to_string())
+            .or_insert_with(|| LoginAttempts {
+                subject: subject.to_string(),
+                failures: 0,
+                last_failure_at: *now,
+                locked_until: None,
+            });
+
+        if entry.last_failure_at < *now - *window {
+            entry.failures = 0;
+        }
+
+        entry.failures += 1;
+        entry.last_failure_at = *now;
+
+        Ok(entry.clone())
+    }
+
+    async fn lock(&self, subject: &str, until: &DateTime<Utc>) -> Result<(), Self::Error> {
+        if let Some(entry) = self.attempts.lock().unwrap().get_mut(subject) {
+            entry.locked_until = Some(*until);
+        }
+
+        Ok(())
+    }
+
+    async fn clear(&self, subject: &str) -> Result<(), Self::Error> {
+        self.attempts.lock().unwrap().remove(subject);
+
+        Ok(())
+    }
+
+    async fn insert_lockout(&self, lockout: &Lockout) -> Result<(), Self::Error> {
+        self.lockouts.lock().unwrap().push(lockout.clone());
+
+        Ok(())
+    }
+
+    async fn delete_stale(
+        &self,
+        now: &DateTime<Utc>,
+        window: &Duration,
+    ) -> Result<u64, Self::Error> {
+        let mut attempts = self.attempts.lock().unwrap();
+        let before = attempts.len();
+
+        attempts.retain(|_, entry| {
+            entry.last_failure_at >= *now - *window
+                || entry.locked_until.is_some_and(|until| until > *now)
+        });
+
+        Ok((before - attempts.len()) as u64)
+    }
+}
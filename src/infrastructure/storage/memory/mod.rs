@@ -0,0 +1,4 @@
+pub mod login_attempt_store;
+pub mod quiz_repository;
+pub mod review_repository;
+pub mod session_repository;
@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        models::{
+            quiz::Quiz,
+            word_pair::{WordPair, WordPairFilter},
+        },
+        traits::repositories::{quiz_repository::IQuizRepository, repository::Repository},
+        types::ID,
+    },
+    infrastructure::storage::database::models::quiz::QuizEntity,
+};
+
+/// Process-local quizzes for tests. Questions are only drawn from word
+/// pairs added with `add_word_pair`.
+#[derive(Debug, Clone, Default)]
+pub struct QuizMemoryRepository {
+    quizzes: Arc<Mutex<HashMap<ID, Quiz>>>,
+    word_pairs: Arc<Mutex<Vec<WordPair>>>,
+}
+
+impl QuizMemoryRepository {
+    pub fn add_word_pair(&self, word_pair: &WordPair) {
+        self.word_pairs.lock().unwrap().push(word_pair.clone());
+    }
+
+    /// The user's active word pairs passing `predicate`, oldest first.
+    fn select_active(
+        &self,
+        user_id: &ID,
+        limit: i64,
+        predicate: impl Fn(&WordPair) -> bool,
+    ) -> Vec<WordPair> {
+        let mut word_pairs: Vec<WordPair> = self
+            .word_pairs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|word_pair| {
+                word_pair.user_id == *user_id && !word_pair.is_deleted() && predicate(word_pair)
+            })
+            .cloned()
+            .collect();
+
+        word_pairs.sort_by_key(|word_pair| (word_pair.created_at, word_pair.id));
+        word_pairs.truncate(limit as usize);
+
+        word_pairs
+    }
+}
+
+#[async_trait]
+impl Repository for QuizMemoryRepository {
+    type Pool = ();
+    type Item = Quiz;
+    type Entity = QuizEntity;
+    type Error = sqlx::Error;
+
+    fn new(_db: Self::Pool) -> Self {
+        Self::default()
+    }
+
+    async fn insert(&self, quiz: &Self::Item) -> Result<Self::Item, Self::Error> {
+        self.quizzes.lock().unwrap().insert(quiz.id, quiz.clone());
+
+        Ok(quiz.clone())
+    }
+
+    async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+        self.quizzes
+            .lock()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+        self.quizzes
+            .lock()
+            .unwrap()
+            .remove(id)
+            .map(|_| ())
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+}
+
+#[async_trait]
+impl IQuizRepository for QuizMemoryRepository {
+    async fn select_word_pairs(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error> {
+        Ok(self.select_active(user_id, limit, |word_pair| filter.matches(word_pair)))
+    }
+
+    async fn select_by_target_language(
+        &self,
+        user_id: &ID,
+        target_language: &str,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error> {
+        Ok(self.select_active(user_id, limit, |word_pair| {
+            word_pair.target_language == target_language
+        }))
+    }
+
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Quiz>, Self::Error> {
+        let mut quizzes: Vec<Quiz> = self
+            .quizzes
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|quiz| quiz.user_id == *user_id)
+            .cloned()
+            .collect();
+
+        quizzes.sort_by_key(|quiz| (quiz.created_at, quiz.id));
+
+        Ok(quizzes)
+    }
+
+    async fn submit(&self, quiz: &Quiz) -> Result<Quiz, Self::Error> {
+        let mut quizzes = self.quizzes.lock().unwrap();
+
+        match quizzes.get(&quiz.id) {
+            Some(stored) if !stored.is_graded() => {
+                quizzes.insert(quiz.id, quiz.clone());
+
+                Ok(quiz.clone())
+            }
+            _ => Err(sqlx::Error::RowNotFound),
+        }
+    }
+}
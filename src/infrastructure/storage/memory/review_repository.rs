@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::{
+            review::{DueCard, ReviewLog, ReviewState},
+            word_pair::{WordPair, WordPairFilter},
+        },
+        traits::repositories::{repository::Repository, review_repository::IReviewRepository},
+        types::ID,
+    },
+    infrastructure::storage::database::models::review::ReviewStateEntity,
+};
+
+/// Process-local review states for tests. Due and new cards are only
+/// listed for word pairs added with `add_word_pair`.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewMemoryRepository {
+    states: Arc<Mutex<HashMap<ID, ReviewState>>>,
+    logs: Arc<Mutex<Vec<ReviewLog>>>,
+    word_pairs: Arc<Mutex<HashMap<ID, WordPair>>>,
+}
+
+impl ReviewMemoryRepository {
+    pub fn add_word_pair(&self, word_pair: &WordPair) {
+        self.word_pairs
+            .lock()
+            .unwrap()
+            .insert(word_pair.id, word_pair.clone());
+    }
+
+    pub fn logs(&self) -> Vec<ReviewLog> {
+        self.logs.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Repository for ReviewMemoryRepository {
+    type Pool = ();
+    type Item = ReviewState;
+    type Entity = ReviewStateEntity;
+    type Error = sqlx::Error;
+
+    fn new(_db: Self::Pool) -> Self {
+        Self::default()
+    }
+
+    async fn insert(&self, state: &Self::Item) -> Result<Self::Item, Self::Error> {
+        self.states
+            .lock()
+            .unwrap()
+            .insert(state.word_pair_id, state.clone());
+
+        Ok(state.clone())
+    }
+
+    async fn select_by_id(&self, word_pair_id: &ID) -> Result<Self::Item, Self::Error> {
+        self.states
+            .lock()
+            .unwrap()
+            .get(word_pair_id)
+            .cloned()
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn delete_by_id(&self, word_pair_id: &ID) -> Result<(), Self::Error> {
+        self.states
+            .lock()
+            .unwrap()
+            .remove(word_pair_id)
+            .map(|_| ())
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+}
+
+#[async_trait]
+impl IReviewRepository for ReviewMemoryRepository {
+    async fn select_due(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        now: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<DueCard>, Self::Error> {
+        let word_pairs = self.word_pairs.lock().unwrap();
+
+        let mut cards: Vec<DueCard> = self
+            .states
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|state| state.user_id == *user_id && state.is_due(now))
+            .filter_map(|state| {
+                let word_pair = word_pairs.get(&state.word_pair_id)?;
+
+                (!word_pair.is_deleted() && filter.matches(word_pair)).then(|| DueCard {
+                    word_pair: word_pair.clone(),
+                    review: state.clone(),
+                })
+            })
+            .collect();
+
+        cards.sort_by_key(|card| (card.review.due_at, card.review.word_pair_id));
+        cards.truncate(limit as usize);
+
+        Ok(cards)
+    }
+
+    async fn select_new(
+        &self,
+        user_id: &ID,
+        filter: &WordPairFilter,
+        limit: i64,
+    ) -> Result<Vec<WordPair>, Self::Error> {
+        let states = self.states.lock().unwrap();
+
+        let mut word_pairs: Vec<WordPair> = self
+            .word_pairs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|word_pair| {
+                word_pair.user_id == *user_id
+                    && !word_pair.is_deleted()
+                    && !states.contains_key(&word_pair.id)
+                    && filter.matches(word_pair)
+            })
+            .cloned()
+            .collect();
+
+        word_pairs.sort_by_key(|word_pair| (word_pair.created_at, word_pair.id));
+        word_pairs.truncate(limit as usize);
+
+        Ok(word_pairs)
+    }
+
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+        let mut states: Vec<ReviewState> = self
+            .states
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|state| state.user_id == *user_id)
+            .cloned()
+            .collect();
+
+        states.sort_by_key(|state| (state.due_at, state.word_pair_id));
+
+        Ok(states)
+    }
+
+    async fn select_logs_by_user_id(&self, user_id: &ID) -> Result<Vec<ReviewLog>, Self::Error> {
+        let mut logs: Vec<ReviewLog> = self
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|log| log.user_id == *user_id)
+            .cloned()
+            .collect();
+
+        logs.sort_by_key(|log| (log.reviewed_at, log.id));
+
+        Ok(logs)
+    }
+
+    async fn upsert(&self, state: &Self::Item) -> Result<Self::Item, Self::Error> {
+        self.insert(state).await
+    }
+
+    async fn insert_log(&self, log: &ReviewLog) -> Result<ReviewLog, Self::Error> {
+        self.logs.lock().unwrap().push(log.clone());
+
+        Ok(log.clone())
+    }
+}
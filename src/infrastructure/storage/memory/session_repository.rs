@@ -0,0 +1,139 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{
+        models::session::{ReviewSession, SessionCard},
+        traits::repositories::{repository::Repository, session_repository::ISessionRepository},
+        types::ID,
+    },
+    infrastructure::storage::database::models::session::ReviewSessionEntity,
+};
+
+/// Process-local sessions for tests.
+#[derive(Debug, Clone, Default)]
+pub struct SessionMemoryRepository {
+    sessions: Arc<Mutex<HashMap<ID, ReviewSession>>>,
+}
+
+#[async_trait]
+impl Repository for SessionMemoryRepository {
+    type Pool = ();
+    type Item = ReviewSession;
+    type Entity = ReviewSessionEntity;
+    type Error = sqlx::Error;
+
+    fn new(_db: Self::Pool) -> Self {
+        Self::default()
+    }
+
+    async fn insert(&self, session: &Self::Item) -> Result<Self::Item, Self::Error> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session.id, session.clone());
+
+        Ok(session.clone())
+    }
+
+    async fn select_by_id(&self, id: &ID) -> Result<Self::Item, Self::Error> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn delete_by_id(&self, id: &ID) -> Result<(), Self::Error> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .remove(id)
+            .map(|_| ())
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+}
+
+#[async_trait]
+impl ISessionRepository for SessionMemoryRepository {
+    async fn select_by_user_id(&self, user_id: &ID) -> Result<Vec<Self::Item>, Self::Error> {
+        let mut sessions: Vec<ReviewSession> = self
+            .sessions
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|session| session.user_id == *user_id)
+            .cloned()
+            .collect();
+
+        sessions.sort_by_key(|session| (session.created_at, session.id));
+
+        Ok(sessions)
+    }
+
+    async fn answer_card(
+        &self,
+        session_id: &ID,
+        card: &SessionCard,
+    ) -> Result<SessionCard, Self::Error> {
+        let mut sessions = self.sessions.lock().unwrap();
+
+        let stored = sessions
+            .get_mut(session_id)
+            .and_then(|session| {
+                session
+                    .cards
+                    .iter_mut()
+                    .find(|stored| stored.position == card.position)
+            })
+            .filter(|stored| !stored.is_answered())
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        stored.grade = card.grade;
+        stored.answer = card.answer.clone();
+        stored.answered_at = card.answered_at;
+        stored.duration_ms = card.duration_ms;
+
+        Ok(stored.clone())
+    }
+
+    async fn clear_card(&self, session_id: &ID, position: i32) -> Result<(), Self::Error> {
+        let mut sessions = self.sessions.lock().unwrap();
+
+        if let Some(stored) = sessions.get_mut(session_id).and_then(|session| {
+            session
+                .cards
+                .iter_mut()
+                .find(|stored| stored.position == position)
+        }) {
+            stored.grade = None;
+            stored.answer = None;
+            stored.answered_at = None;
+            stored.duration_ms = None;
+        }
+
+        Ok(())
+    }
+
+    async fn finish(
+        &self,
+        session_id: &ID,
+        finished_at: &DateTime<Utc>,
+    ) -> Result<Self::Item, Self::Error> {
+        let mut sessions = self.sessions.lock().unwrap();
+
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        session.finished_at = Some(*finished_at);
+
+        Ok(session.clone())
+    }
+}
@@ -1 +1,3 @@
 pub mod database;
+#[cfg(test)]
+pub mod memory;
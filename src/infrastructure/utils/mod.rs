@@ -1,4 +1,6 @@
 pub mod convert;
 pub mod io;
 pub mod password;
+pub mod quiz;
+pub mod secret_token;
 pub mod string;
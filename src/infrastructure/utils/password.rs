@@ -1,11 +1,169 @@
 use argon2::{
-    Argon2,
+    Algorithm, Argon2, Params, ParamsBuilder, Version,
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
 };
+use thiserror::Error;
 
 use crate::domain::traits::crypto::crypto::ICrypto;
 
-pub struct Argon2Crypto;
+/// Server-side secret mixed into every hash. `id` is stored in the PHC
+/// string as `keyid`, so hashes made before a pepper was introduced, or with
+/// one of the previous peppers, are still recognised and upgraded on the
+/// next login.
+#[derive(Debug, Clone)]
+pub struct Pepper {
+    pub id: String,
+    pub secret: String,
+}
+
+impl Pepper {
+    fn new(id: String, secret: String) -> Result<Self, Argon2ConfigError> {
+        if id.is_empty() || id.len() > Params::MAX_KEYID_LEN {
+            return Err(Argon2ConfigError::InvalidPepperId(id));
+        }
+
+        Ok(Self { id, secret })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Argon2ConfigError {
+    #[error("Pepper id `{0}` must be 1 to 8 bytes long")]
+    InvalidPepperId(String),
+
+    #[error("PASSWORD_PEPPER_PREVIOUS entries must look like `id:secret`")]
+    InvalidPreviousPepper,
+}
+
+#[derive(Debug, Clone)]
+pub struct Argon2Config {
+    pub algorithm: Algorithm,
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub pepper: Option<Pepper>,
+    /// Peppers rotated out, only used to verify hashes made with them.
+    pub previous_peppers: Vec<Pepper>,
+}
+
+impl Default for Argon2Config {
+    fn default() -> Self {
+        Self {
+            algorithm: Algorithm::default(),
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+            pepper: None,
+            previous_peppers: Vec::new(),
+        }
+    }
+}
+
+impl Argon2Config {
+    /// Reads `PASSWORD_PEPPER_PREVIOUS` as a comma separated list of
+    /// `id:secret` pairs.
+    pub fn from_env() -> Result<Self, Argon2ConfigError> {
+        let default = Self::default();
+
+        let number = |name: &str, default: u32| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+
+        let pepper = std::env::var("PASSWORD_PEPPER")
+            .ok()
+            .map(|secret| {
+                Pepper::new(
+                    std::env::var("PASSWORD_PEPPER_ID").unwrap_or("1".to_string()),
+                    secret,
+                )
+            })
+            .transpose()?;
+
+        let previous_peppers = std::env::var("PASSWORD_PEPPER_PREVIOUS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (id, secret) = entry
+                    .split_once(':')
+                    .ok_or(Argon2ConfigError::InvalidPreviousPepper)?;
+
+                Pepper::new(id.to_string(), secret.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            algorithm: std::env::var("ARGON2_VARIANT")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default.algorithm),
+            m_cost: number("ARGON2_M_COST", default.m_cost),
+            t_cost: number("ARGON2_T_COST", default.t_cost),
+            p_cost: number("ARGON2_P_COST", default.p_cost),
+            pepper,
+            previous_peppers,
+        })
+    }
+
+    /// The current or a previous pepper with the given `keyid`.
+    fn pepper_by_id(&self, keyid: &[u8]) -> Option<&Pepper> {
+        self.pepper
+            .iter()
+            .chain(&self.previous_peppers)
+            .find(|pepper| pepper.id.as_bytes() == keyid)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Argon2Crypto {
+    config: Argon2Config,
+}
+
+impl Argon2Crypto {
+    pub fn new(config: Argon2Config) -> Self {
+        Self { config }
+    }
+
+    fn params(&self) -> Result<Params, argon2::password_hash::Error> {
+        let mut builder = ParamsBuilder::new();
+
+        builder
+            .m_cost(self.config.m_cost)
+            .t_cost(self.config.t_cost)
+            .p_cost(self.config.p_cost);
+
+        if let Some(pepper) = &self.config.pepper {
+            builder.keyid(pepper.id.as_bytes().try_into()?);
+        }
+
+        Ok(builder.build()?)
+    }
+
+    fn argon2<'a>(
+        &'a self,
+        pepper: Option<&'a Pepper>,
+    ) -> Result<Argon2<'a>, argon2::password_hash::Error> {
+        let params = self.params()?;
+
+        match pepper {
+            Some(pepper) => Ok(Argon2::new_with_secret(
+                pepper.secret.as_bytes(),
+                self.config.algorithm,
+                Version::default(),
+                params,
+            )?),
+            None => Ok(Argon2::new(
+                self.config.algorithm,
+                Version::default(),
+                params,
+            )),
+        }
+    }
+}
 
 impl ICrypto for Argon2Crypto {
     type Error = argon2::password_hash::Error;
@@ -13,7 +171,7 @@ impl ICrypto for Argon2Crypto {
     fn hash(&self, source: &str) -> Result<String, Self::Error> {
         let salt = SaltString::generate(&mut OsRng);
 
-        let argon2 = Argon2::default();
+        let argon2 = self.argon2(self.config.pepper.as_ref())?;
 
         let password_hash = argon2.hash_password(source.as_bytes(), &salt)?.to_string();
 
@@ -21,12 +179,145 @@ impl ICrypto for Argon2Crypto {
     }
 
     fn verify(&self, source: &str, hashed: &str) -> Result<(), Self::Error> {
-        let parsed_hash = PasswordHash::new(&hashed)?;
+        let parsed_hash = PasswordHash::new(hashed)?;
+
+        let keyid = Params::try_from(&parsed_hash)?.keyid().to_vec();
+
+        // The stored hash carries its own cost parameters, only the secret
+        // has to be picked to match the one it was made with.
+        let pepper = match keyid.is_empty() {
+            true => None,
+            false => Some(
+                self.config
+                    .pepper_by_id(&keyid)
+                    .ok_or(argon2::password_hash::Error::Password)?,
+            ),
+        };
 
-        let argon2 = Argon2::default();
+        let argon2 = self.argon2(pepper)?;
 
         argon2.verify_password(source.as_bytes(), &parsed_hash)?;
 
         Ok(())
     }
+
+    fn needs_rehash(&self, hashed: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hashed) else {
+            return true;
+        };
+
+        let Ok(params) = Params::try_from(&parsed_hash) else {
+            return true;
+        };
+
+        let Ok(wanted) = self.params() else {
+            return false;
+        };
+
+        parsed_hash.algorithm != self.config.algorithm.ident()
+            || parsed_hash.version != Some(Version::default().into())
+            || params.m_cost() != wanted.m_cost()
+            || params.t_cost() != wanted.t_cost()
+            || params.p_cost() != wanted.p_cost()
+            || params.keyid() != wanted.keyid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(m_cost: u32, pepper: Option<&str>) -> Argon2Config {
+        Argon2Config {
+            algorithm: Algorithm::Argon2id,
+            m_cost,
+            t_cost: 1,
+            p_cost: 1,
+            pepper: pepper.map(|id| Pepper {
+                id: id.to_string(),
+                secret: format!("secret-{id}"),
+            }),
+            previous_peppers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_hash_uses_configured_params() {
+        let crypto = Argon2Crypto::new(config(8192, None));
+
+        let hashed = crypto.hash("hefkajdf42").unwrap();
+
+        assert!(hashed.contains("m=8192,t=1,p=1"));
+        assert!(crypto.verify("hefkajdf42", &hashed).is_ok());
+        assert!(!crypto.needs_rehash(&hashed));
+    }
+
+    #[test]
+    fn test_needs_rehash_after_params_change() {
+        let hashed = Argon2Crypto::new(config(8192, None))
+            .hash("hefkajdf42")
+            .unwrap();
+
+        let crypto = Argon2Crypto::new(config(16384, None));
+
+        assert!(crypto.verify("hefkajdf42", &hashed).is_ok());
+        assert!(crypto.needs_rehash(&hashed));
+    }
+
+    #[test]
+    fn test_pepper() {
+        let crypto = Argon2Crypto::new(config(8192, Some("1")));
+
+        let hashed = crypto.hash("hefkajdf42").unwrap();
+
+        assert!(crypto.verify("hefkajdf42", &hashed).is_ok());
+        assert!(
+            Argon2Crypto::new(config(8192, None))
+                .verify("hefkajdf42", &hashed)
+                .is_err()
+        );
+        assert!(
+            Argon2Crypto::new(config(8192, Some("2")))
+                .verify("hefkajdf42", &hashed)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unpeppered_hash_is_upgraded() {
+        let hashed = Argon2Crypto::new(config(8192, None))
+            .hash("hefkajdf42")
+            .unwrap();
+
+        let crypto = Argon2Crypto::new(config(8192, Some("1")));
+
+        assert!(crypto.verify("hefkajdf42", &hashed).is_ok());
+        assert!(crypto.needs_rehash(&hashed));
+    }
+
+    #[test]
+    fn test_rotated_pepper_is_upgraded() {
+        let hashed = Argon2Crypto::new(config(8192, Some("1")))
+            .hash("hefkajdf42")
+            .unwrap();
+
+        let mut rotated = config(8192, Some("2"));
+        rotated.previous_peppers = config(8192, Some("1")).pepper.into_iter().collect();
+
+        let crypto = Argon2Crypto::new(rotated);
+
+        assert!(crypto.verify("hefkajdf42", &hashed).is_ok());
+        assert!(crypto.verify("wrong", &hashed).is_err());
+        assert!(crypto.needs_rehash(&hashed));
+    }
+
+    #[test]
+    fn test_pepper_id_length() {
+        assert!(Pepper::new("12345678".to_string(), "secret".to_string()).is_ok());
+        assert!(matches!(
+            Pepper::new("123456789".to_string(), "secret".to_string()),
+            Err(Argon2ConfigError::InvalidPepperId(_))
+        ));
+        assert!(Pepper::new(String::new(), "secret".to_string()).is_err());
+    }
 }
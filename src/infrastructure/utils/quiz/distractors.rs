@@ -0,0 +1,89 @@
+use std::{cmp::Reverse, collections::HashSet};
+
+use rand::{Rng, seq::SliceRandom};
+
+use crate::infrastructure::utils::string::normalize_text::normalize_text;
+
+/// Picks up to `count` distinct candidates that look most like `answer`:
+/// the longest shared prefix first, then the closest length. Candidates
+/// equal to the answer are skipped, ties are broken by `rng`.
+pub fn pick_distractors<R: Rng + ?Sized>(
+    answer: &str,
+    candidates: &[&str],
+    count: usize,
+    rng: &mut R,
+) -> Vec<String> {
+    let answer = normalize_text(answer);
+    let answer_length = answer.chars().count();
+
+    let mut seen = HashSet::from([answer.clone()]);
+
+    let mut candidates: Vec<(&str, String)> = candidates
+        .iter()
+        .map(|candidate| (*candidate, normalize_text(candidate)))
+        .filter(|(_, key)| !key.is_empty() && seen.insert(key.clone()))
+        .collect();
+
+    candidates.shuffle(rng);
+
+    candidates.sort_by_key(|(_, key)| {
+        let shared_prefix = answer
+            .chars()
+            .zip(key.chars())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        (
+            Reverse(shared_prefix),
+            answer_length.abs_diff(key.chars().count()),
+        )
+    });
+
+    candidates
+        .into_iter()
+        .take(count)
+        .map(|(candidate, _)| candidate.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn test_prefers_shared_prefix_then_length() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let res = pick_distractors(
+            "Haus",
+            &["Hund", "Hausboot", "Baum", "Maus", "Elefant", "haus"],
+            3,
+            &mut rng,
+        );
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[..2], ["Hausboot", "Hund"]);
+        assert!(["Baum", "Maus"].contains(&res[2].as_str()));
+    }
+
+    #[test]
+    fn test_skips_duplicates() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let res = pick_distractors("Katze", &["Hund", "hund ", "Katze"], 3, &mut rng);
+
+        assert_eq!(res, vec!["Hund"]);
+    }
+
+    #[test]
+    fn test_same_seed_same_distractors() {
+        let candidates = ["Tisch", "Tasse", "Tür", "Tal", "Tor", "Tag"];
+
+        let first = pick_distractors("Tee", &candidates, 3, &mut StdRng::seed_from_u64(42));
+        let second = pick_distractors("Tee", &candidates, 3, &mut StdRng::seed_from_u64(42));
+
+        assert_eq!(first, second);
+    }
+}
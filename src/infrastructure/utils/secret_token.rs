@@ -0,0 +1,13 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+
+/// Random base64url token of `N` bytes.
+pub fn generate_token<const N: usize>() -> String {
+    URL_SAFE_NO_PAD.encode(rand::random::<[u8; N]>())
+}
+
+/// SHA-256 of a random secret token, base64url encoded. The tokens carry
+/// enough entropy that an unsalted hash is safe to store and look up by.
+pub fn hash_token(token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,242 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+use crate::{
+    domain::models::{
+        answer_check::{AnswerCheck, DiffKind, DiffSegment, Verdict},
+        word_pair::alternatives,
+    },
+    infrastructure::utils::string::normalize_text::normalize_text,
+};
+
+/// Compatibility-normalizes the text, lowercases it, turns dashes and
+/// slashes into spaces, drops other punctuation and collapses whitespace.
+/// With `ignore_diacritics` accents are stripped as well.
+pub fn normalize_answer(value: &str, ignore_diacritics: bool) -> String {
+    let decomposed: String = value
+        .nfkd()
+        .filter(|c| !(ignore_diacritics && is_combining_mark(*c)))
+        .collect();
+
+    let stripped: String = decomposed
+        .nfc()
+        .filter_map(|c| match c {
+            '-' | '‐' | '–' | '—' | '/' => Some(' '),
+            c if c.is_alphanumeric() || c.is_whitespace() || is_combining_mark(c) => Some(c),
+            _ => None,
+        })
+        .collect();
+
+    normalize_text(&stripped)
+}
+
+/// Character edits the expected text tolerates while still being `Almost`.
+fn allowed_edits(expected: &[char]) -> usize {
+    (expected.len() / 4).max(1)
+}
+
+/// Levenshtein distance table of `a` against `b`.
+fn distances(a: &[char], b: &[char]) -> Vec<Vec<usize>> {
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    table[0] = (0..=b.len()).collect();
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = table[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+
+            table[i][j] = substitution
+                .min(table[i - 1][j] + 1)
+                .min(table[i][j - 1] + 1);
+        }
+    }
+
+    table
+}
+
+/// Levenshtein distance between `a` and `b`, counted in characters.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    distances(a, b)[a.len()][b.len()]
+}
+
+/// Walks the distance table back from the end, a substitution becomes an
+/// extra character followed by a missing one.
+fn diff(answer: &[char], expected: &[char]) -> Vec<DiffSegment> {
+    let table = distances(answer, expected);
+
+    let mut steps = Vec::new();
+    let (mut i, mut j) = (answer.len(), expected.len());
+
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && answer[i - 1] == expected[j - 1] && table[i][j] == table[i - 1][j - 1]
+        {
+            steps.push((DiffKind::Same, answer[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && table[i][j] == table[i - 1][j - 1] + 1 {
+            steps.push((DiffKind::Missing, expected[j - 1]));
+            steps.push((DiffKind::Extra, answer[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if j > 0 && (i == 0 || table[i][j] == table[i][j - 1] + 1) {
+            steps.push((DiffKind::Missing, expected[j - 1]));
+            j -= 1;
+        } else {
+            steps.push((DiffKind::Extra, answer[i - 1]));
+            i -= 1;
+        }
+    }
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+
+    for (kind, c) in steps.into_iter().rev() {
+        match segments.last_mut() {
+            Some(segment) if segment.kind == kind => segment.text.push(c),
+            _ => segments.push(DiffSegment {
+                kind,
+                text: c.to_string(),
+            }),
+        }
+    }
+
+    segments
+}
+
+/// Compares a typed answer with the expected text. Any of the comma or
+/// semicolon separated alternatives of `expected` is accepted.
+pub fn check_answer(answer: &str, expected: &str, ignore_diacritics: bool) -> AnswerCheck {
+    let normalized_answer: Vec<char> = normalize_answer(answer, ignore_diacritics)
+        .chars()
+        .collect();
+
+    let closest = alternatives(expected)
+        .map(|alternative| {
+            let normalized: Vec<char> = normalize_answer(alternative, ignore_diacritics)
+                .chars()
+                .collect();
+            let distance = edit_distance(&normalized_answer, &normalized);
+
+            (alternative, normalized, distance)
+        })
+        .min_by_key(|(_, _, distance)| *distance);
+
+    let Some((alternative, normalized_expected, distance)) = closest else {
+        return AnswerCheck {
+            verdict: Verdict::Wrong,
+            expected: expected.to_string(),
+            distance: normalized_answer.len(),
+            diff: Vec::new(),
+        };
+    };
+
+    let verdict = match distance {
+        0 if !normalized_expected.is_empty() => Verdict::Correct,
+        _ if normalized_answer.is_empty() => Verdict::Wrong,
+        distance if distance <= allowed_edits(&normalized_expected) => Verdict::Almost,
+        _ => Verdict::Wrong,
+    };
+
+    AnswerCheck {
+        verdict,
+        expected: alternative.to_string(),
+        distance,
+        diff: match verdict {
+            Verdict::Almost => diff(&normalized_answer, &normalized_expected),
+            _ => Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_answer() {
+        assert_eq!(
+            normalize_answer("  Guten   Morgen! ", false),
+            "guten morgen"
+        );
+        assert_eq!(normalize_answer("well-known", false), "well known");
+        assert_eq!(normalize_answer("ﬁsh", false), "fish");
+        assert_eq!(normalize_answer("Café", false), "café");
+        assert_eq!(normalize_answer("Cafe\u{301}", false), "café");
+        assert_eq!(normalize_answer("Café", true), "cafe");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let chars = |value: &str| value.chars().collect::<Vec<_>>();
+
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(edit_distance(&chars(""), &chars("abc")), 3);
+        assert_eq!(edit_distance(&chars("straße"), &chars("strasse")), 2);
+    }
+
+    #[test]
+    fn test_correct_ignoring_case_and_punctuation() {
+        let res = check_answer("guten morgen", "Guten Morgen!", false);
+
+        assert_eq!(res.verdict, Verdict::Correct);
+        assert_eq!(res.distance, 0);
+    }
+
+    #[test]
+    fn test_diacritics() {
+        assert_eq!(
+            check_answer("Muller", "Müller", true).verdict,
+            Verdict::Correct
+        );
+
+        let res = check_answer("Muller", "Müller", false);
+
+        assert_eq!(res.verdict, Verdict::Almost);
+        assert_eq!(
+            res.diff,
+            vec![
+                DiffSegment {
+                    kind: DiffKind::Same,
+                    text: "m".to_string(),
+                },
+                DiffSegment {
+                    kind: DiffKind::Extra,
+                    text: "u".to_string(),
+                },
+                DiffSegment {
+                    kind: DiffKind::Missing,
+                    text: "ü".to_string(),
+                },
+                DiffSegment {
+                    kind: DiffKind::Same,
+                    text: "ller".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_almost_and_wrong() {
+        let res = check_answer("Katz", "Katze", false);
+
+        assert_eq!(res.verdict, Verdict::Almost);
+        assert_eq!(res.diff.last().unwrap().kind, DiffKind::Missing);
+
+        assert_eq!(check_answer("Hund", "Katze", false).verdict, Verdict::Wrong);
+        assert_eq!(check_answer("", "Katze", false).verdict, Verdict::Wrong);
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let res = check_answer("koter", "Hund; Köter", true);
+
+        assert_eq!(res.verdict, Verdict::Correct);
+        assert_eq!(res.expected, "Köter");
+
+        let res = check_answer("well then", "Well, then", false);
+
+        assert_eq!(res.verdict, Verdict::Correct);
+        assert_eq!(res.expected, "Well, then");
+    }
+}
@@ -0,0 +1,26 @@
+/// Escapes `%`, `_` and `\` so user input matches literally in a `LIKE`
+/// pattern with the default escape character.
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for char in value.chars() {
+        if matches!(char, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+
+        escaped.push(char);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("ab"), "ab");
+        assert_eq!(escape_like("50%_off\\"), "50\\%\\_off\\\\");
+    }
+}
@@ -1 +1,4 @@
+pub mod answer_check;
+pub mod escape_like;
+pub mod normalize_text;
 pub mod to_title_case;
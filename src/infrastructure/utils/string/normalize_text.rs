@@ -0,0 +1,22 @@
+/// Lowercases the text, trims it and collapses inner whitespace, so texts
+/// that only differ in case or spacing compare equal. Matches
+/// `lower(btrim(regexp_replace(text, '\s+', ' ', 'g')))` in Postgres for
+/// ASCII whitespace, `btrim` alone would only strip spaces.
+pub fn normalize_text(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(normalize_text("Hello"), "hello");
+        assert_eq!(normalize_text("  Good \t Morning \n"), "good morning");
+    }
+}
@@ -1,38 +1,52 @@
-use axum::{
-    Router,
-    routing::{get, post},
-};
+use std::net::SocketAddr;
+
 use sqlx::postgres::{PgPool, PgPoolOptions};
+use tracing::error;
 
 use crate::{
-    api::{
-        auth::auth_middleware::auth_middleware,
-        translate::translate_handlers::translate,
-        user::user_handlers::{
-            create_user, delete_user_by_id, get_user_by_id, get_user_by_key, update_user_by_id,
-        },
-        word_pair::word_pair_handlers::{
-            add_word_pair_by_user_id, add_word_pair_by_user_key, delete_word_pair_by_id,
-            get_word_pair_by_id, get_word_pairs_by_user_id, get_word_pairs_by_user_key,
-            translate_and_add_word_pair_by_user_id, translate_and_add_word_pair_by_user_key,
-        },
-    },
+    api::router::app,
     application::services::{
-        auth_service::AuthService, translate_service::TranslateService, user_service::UserService,
+        api_key_service::ApiKeyService,
+        auth_service::AuthService,
+        login_throttle_service::{LoginThrottleConfig, LoginThrottleService},
+        password_reset_service::PasswordResetService,
+        quiz_service::QuizService,
+        review_service::ReviewService,
+        revocation_service::RevocationService,
+        session_service::SessionService,
+        stats_service::StatsService,
+        token_service::TokenService,
+        translate_service::TranslateService,
+        user_service::UserService,
         word_pair_service::WordPairService,
     },
     domain::traits::repositories::repository::Repository,
     infrastructure::{
+        auth::token::{
+            issuer::TokenIssuer, jwks::jwks_validator::TokenValidationConfig,
+            key_cache::KeyCacheConfig,
+        },
         external_api::translate::translate::TranslatorsTranslator,
+        scheduler::Scheduler,
         storage::database::repositories::{
+            api_key_repository::ApiKeyPostgresRepository,
+            login_attempt_store::LoginAttemptPostgresStore,
+            password_reset_token_repository::PasswordResetTokenPostgresRepository,
+            quiz_repository::QuizPostgresRepository,
+            refresh_token_repository::RefreshTokenPostgresRepository,
+            review_repository::ReviewPostgresRepository,
+            revocation_repository::RevocationPostgresRepository,
+            session_repository::SessionPostgresRepository, stats_store::StatsPostgresStore,
             user_repository::UserPostgresRepository,
             word_pair_repository::WordPairPostgresRepository,
         },
+        utils::password::{Argon2Config, Argon2Crypto},
     },
 };
 
 mod api;
 mod application;
+mod cli;
 mod domain;
 mod infrastructure;
 
@@ -41,25 +55,79 @@ pub struct AppState {
     pub translate_service: TranslateService<TranslatorsTranslator>,
     pub user_service: UserService<UserPostgresRepository>,
     pub word_pair_service: WordPairService<WordPairPostgresRepository>,
-    pub auth_service: AuthService,
+    pub auth_service: AuthService<TokenIssuer>,
+    pub token_service: TokenService<
+        TokenIssuer,
+        RefreshTokenPostgresRepository,
+        RevocationPostgresRepository,
+        UserPostgresRepository,
+    >,
+    pub revocation_service: RevocationService<RevocationPostgresRepository>,
+    pub password_reset_service:
+        PasswordResetService<PasswordResetTokenPostgresRepository, UserPostgresRepository>,
+    pub login_throttle_service: LoginThrottleService<LoginAttemptPostgresStore>,
+    pub api_key_service: ApiKeyService<ApiKeyPostgresRepository>,
+    pub review_service: ReviewService<ReviewPostgresRepository, Scheduler>,
+    pub session_service:
+        SessionService<SessionPostgresRepository, ReviewPostgresRepository, Scheduler>,
+    pub quiz_service: QuizService<QuizPostgresRepository>,
+    pub stats_service: StatsService<StatsPostgresStore>,
 }
 
 impl AppState {
-    pub fn new(db: PgPool) -> Self {
+    pub fn new(db: PgPool, token_issuer: TokenIssuer, argon2_config: Argon2Config) -> Self {
         let user_repo = UserPostgresRepository::new(db.clone());
         let word_pair_repo = WordPairPostgresRepository::new(db.clone());
+        let revocation_repo = RevocationPostgresRepository::new(db.clone());
+        let refresh_token_repo = RefreshTokenPostgresRepository::new(db.clone());
+        let password_reset_repo = PasswordResetTokenPostgresRepository::new(db.clone());
+        let login_attempt_store = LoginAttemptPostgresStore::new(db.clone());
+        let api_key_repo = ApiKeyPostgresRepository::new(db.clone());
+        let review_repo = ReviewPostgresRepository::new(db.clone());
+        let session_repo = SessionPostgresRepository::new(db.clone());
+        let quiz_repo = QuizPostgresRepository::new(db.clone());
+        let stats_store = StatsPostgresStore::new(db.clone());
         let translator = TranslatorsTranslator;
 
-        let user_service = UserService::new(user_repo);
+        let user_service = UserService::new(user_repo, Argon2Crypto::new(argon2_config));
         let word_pair_service = WordPairService::new(word_pair_repo);
         let translate_service = TranslateService::new(translator);
-        let auth_service = AuthService::new();
+        let auth_service = AuthService::new(
+            token_issuer.clone(),
+            KeyCacheConfig::from_env(),
+            TokenValidationConfig::from_env(),
+        );
+        let revocation_service = RevocationService::new(revocation_repo);
+        let token_service = TokenService::new(
+            token_issuer,
+            refresh_token_repo,
+            revocation_service.clone(),
+            user_service.clone(),
+        );
+        let password_reset_service =
+            PasswordResetService::new(password_reset_repo, user_service.clone());
+        let login_throttle_service =
+            LoginThrottleService::new(login_attempt_store, LoginThrottleConfig::from_env());
+        let api_key_service = ApiKeyService::new(api_key_repo);
+        let review_service = ReviewService::new(review_repo, Scheduler::from_env());
+        let session_service = SessionService::new(session_repo, review_service.clone());
+        let quiz_service = QuizService::new(quiz_repo);
+        let stats_service = StatsService::new(stats_store);
 
         Self {
             translate_service: translate_service,
             user_service: user_service,
             word_pair_service: word_pair_service,
             auth_service: auth_service,
+            token_service,
+            revocation_service,
+            password_reset_service,
+            login_throttle_service,
+            api_key_service,
+            review_service,
+            session_service,
+            quiz_service,
+            stats_service,
         }
     }
 }
@@ -74,50 +142,49 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .connect(&std::env::var("DATABASE_URL")?)
         .await?;
 
-    let state = AppState::new(pool);
-
-    let app: Router = Router::new()
-        .route("/", get(|| async { "Hello world!" }))
-        .route("/user/create/", post(create_user))
-        .route("/translate/", post(translate))
-        .route("/user/id/{id}/", get(get_user_by_id))
-        .route("/user/key/{key}/", get(get_user_by_key))
-        .route("/user/update/id/{id}/", post(update_user_by_id))
-        .route("/user/delete/id/{id}/", post(delete_user_by_id))
-        .route(
-            "/user/user_id/{user_id}/wordpair/save/",
-            post(add_word_pair_by_user_id),
-        )
-        .route(
-            "/user/key/{key}/wordpair/save/",
-            post(add_word_pair_by_user_key),
-        )
-        .route(
-            "/user/user_id/{user_id}/wordpair/create/",
-            post(translate_and_add_word_pair_by_user_id),
-        )
-        .route(
-            "/user/key/{key}/wordpair/create/",
-            post(translate_and_add_word_pair_by_user_key),
-        )
-        .route(
-            "/user/user_id/{user_id}/wordpairs/",
-            get(get_word_pairs_by_user_id),
-        )
-        .route(
-            "/user/key/{key}/wordpairs/",
-            get(get_word_pairs_by_user_key),
-        )
-        .route("/wordpair/id/{id}/", get(get_word_pair_by_id))
-        .route("/wordpair/delete/id/{id}/", post(delete_word_pair_by_id))
-        .layer(axum::middleware::from_fn_with_state(
-            state.clone(),
-            auth_middleware,
-        ))
-        .with_state(state);
+    let state = AppState::new(pool, TokenIssuer::from_env()?, Argon2Config::from_env()?);
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if !args.is_empty() {
+        return cli::run(&state, &args).await;
+    }
+
+    if let Err(error) = state.auth_service.warm_up().await {
+        error!("Couldn't load token verification key: {}", error);
+    }
+
+    if let Err(error) = state.revocation_service.load().await {
+        error!("Couldn't load token revocations: {}", error);
+    }
+
+    if let Err(error) = state.user_service.load_inactive().await {
+        error!("Couldn't load inactive users: {}", error);
+    }
+
+    let revocation_service = state.revocation_service.clone();
+    tokio::spawn(async move { revocation_service.run_purge().await });
+
+    let token_service = state.token_service.clone();
+    tokio::spawn(async move { token_service.run_purge().await });
+
+    let user_service = state.user_service.clone();
+    tokio::spawn(async move { user_service.run_purge().await });
+
+    let word_pair_service = state.word_pair_service.clone();
+    tokio::spawn(async move { word_pair_service.run_purge().await });
+
+    let login_throttle_service = state.login_throttle_service.clone();
+    tokio::spawn(async move { login_throttle_service.run_purge().await });
+
+    let app = app(state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }